@@ -0,0 +1,184 @@
+//! Connection, signer and submission settings shared by every command
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context as _, Result};
+use clap::ArgMatches;
+use lottery_client::state::RandomnessProvider;
+use serde_json::json;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::output::{print_record, Format, Record};
+
+/// Expand cluster monikers the way the Solana CLI does
+pub fn resolve_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://localhost:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Default keypair path of the Solana CLI
+pub fn default_keypair_path() -> String {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    format!("{}/.config/solana/id.json", home)
+}
+
+pub struct Context {
+    pub url: String,
+    pub signer: Keypair,
+    pub dry_run: bool,
+    pub format: Format,
+}
+
+impl Context {
+    pub fn from_matches(matches: &ArgMatches) -> Result<Self> {
+        let url = resolve_url(matches.value_of("url").expect("url has a default"));
+        let keypair_path = matches
+            .value_of("keypair")
+            .map(str::to_string)
+            .unwrap_or_else(default_keypair_path);
+        let signer = read_keypair_file(&keypair_path)
+            .map_err(|error| anyhow!("failed to read keypair {}: {}", keypair_path, error))?;
+        let format = Format::parse(matches.value_of("output").expect("output has a default"))?;
+
+        Ok(Self {
+            url,
+            signer,
+            dry_run: matches.is_present("dry-run"),
+            format,
+        })
+    }
+
+    pub fn rpc(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.url.clone(), CommitmentConfig::confirmed())
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    /// Sign with the CLI keypair and send, or simulate under `--dry-run`
+    pub fn submit(
+        &self,
+        rpc: &RpcClient,
+        action: &str,
+        instructions: &[Instruction],
+    ) -> Result<()> {
+        let blockhash = rpc
+            .get_latest_blockhash()
+            .context("failed to fetch blockhash")?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.pubkey()),
+            &[&self.signer],
+            blockhash,
+        );
+
+        if self.dry_run {
+            let result = rpc
+                .simulate_transaction(&transaction)
+                .context("simulation request failed")?
+                .value;
+            let logs = result.logs.unwrap_or_default();
+            let record = Record::new()
+                .field("action", action)
+                .field("simulated", true)
+                .field("error", result.err.map(|error| error.to_string()))
+                .field("units_consumed", result.units_consumed);
+            match self.format {
+                Format::Json => print_record(self.format, &record.field("logs", json!(logs)), None),
+                Format::Table => {
+                    print_record(self.format, &record, None);
+                    for line in &logs {
+                        println!("  {}", line);
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        let signature = rpc
+            .send_and_confirm_transaction(&transaction)
+            .with_context(|| format!("{} failed", action))?;
+        let record = Record::new()
+            .field("action", action)
+            .field("signature", signature.to_string());
+        print_record(self.format, &record, None);
+        Ok(())
+    }
+}
+
+/// Parse a required argument
+pub fn arg<T>(matches: &ArgMatches, name: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = matches
+        .value_of(name)
+        .ok_or_else(|| anyhow!("missing --{}", name))?;
+    value
+        .parse()
+        .map_err(|error| anyhow!("invalid {} {:?}: {}", name, value, error))
+}
+
+/// Parse an optional argument
+pub fn opt_arg<T>(matches: &ArgMatches, name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match matches.value_of(name) {
+        Some(_) => arg(matches, name).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// `--provider` values, in [`parse_provider`] order
+pub const PROVIDERS: [&str; 3] = ["orao", "signed-oracle", "slot-hash"];
+
+pub fn parse_provider(value: &str) -> Result<RandomnessProvider> {
+    match value {
+        "orao" => Ok(RandomnessProvider::Orao),
+        "signed-oracle" => Ok(RandomnessProvider::SignedOracle),
+        "slot-hash" => Ok(RandomnessProvider::SlotHash),
+        other => anyhow::bail!("unknown randomness provider {:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_url_expands_monikers() {
+        assert_eq!(resolve_url("devnet"), "https://api.devnet.solana.com");
+        assert_eq!(resolve_url("l"), "http://localhost:8899");
+        assert_eq!(
+            resolve_url("http://rpc.example:8899"),
+            "http://rpc.example:8899"
+        );
+    }
+
+    #[test]
+    fn test_parse_provider() {
+        for value in PROVIDERS {
+            assert!(parse_provider(value).is_ok());
+        }
+        assert_eq!(
+            parse_provider("slot-hash").unwrap(),
+            RandomnessProvider::SlotHash
+        );
+        assert!(parse_provider("chainlink").is_err());
+    }
+}
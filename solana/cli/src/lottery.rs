@@ -0,0 +1,561 @@
+//! `lottery-cli lottery ...`: telegram_lottery operations
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use clap::{Arg, ArgMatches, Command};
+use lottery_client::instruction::{self, CreateGameArgs};
+use lottery_client::state::{GameState, PlayerList, TreasuryState};
+use lottery_client::{pda, LotteryClient};
+use serde_json::{json, Value};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::context::{arg, opt_arg, parse_provider, Context, PROVIDERS};
+use crate::output::{print_record, print_table, Record, Table};
+
+pub fn command() -> Command<'static> {
+    let game_id = || Arg::new("game-id").required(true).help("Game identifier");
+    Command::new("lottery")
+        .about("Operate telegram_lottery games")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("init")
+                .about("Create the treasury, role registry, config and oracle registry (skips existing)")
+                .arg(Arg::new("mint").long("mint").takes_value(true).required(true))
+                .arg(Arg::new("fee-percent").long("fee-percent").takes_value(true).required(true))
+                .arg(
+                    Arg::new("treasury-authority")
+                        .long("treasury-authority")
+                        .takes_value(true)
+                        .help("Defaults to the CLI keypair"),
+                )
+                .arg(
+                    Arg::new("max-entry-fee")
+                        .long("max-entry-fee")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("create-game")
+                .about("Create a game")
+                .arg(game_id())
+                .arg(Arg::new("entry-fee").long("entry-fee").takes_value(true).required(true))
+                .arg(Arg::new("max-players").long("max-players").takes_value(true).required(true))
+                .arg(Arg::new("winners").long("winners").takes_value(true).default_value("1"))
+                .arg(
+                    Arg::new("deadline-minutes")
+                        .long("deadline-minutes")
+                        .takes_value(true)
+                        .default_value("30"),
+                )
+                .arg(
+                    Arg::new("provider")
+                        .long("provider")
+                        .takes_value(true)
+                        .possible_values(PROVIDERS)
+                        .default_value("orao"),
+                )
+                .arg(Arg::new("mint").long("mint").takes_value(true).required(true))
+                .arg(
+                    Arg::new("oracle")
+                        .long("oracle")
+                        .takes_value(true)
+                        .help("Approved oracle; defaults to the CLI keypair"),
+                ),
+        )
+        .subcommand(Command::new("list").about("List every game"))
+        .subcommand(
+            Command::new("show")
+                .about("Show a game, its players and its next step")
+                .arg(game_id()),
+        )
+        .subcommand(
+            Command::new("crank")
+                .about("Submit the game's next steps until it has to wait")
+                .arg(game_id())
+                .arg(
+                    Arg::new("max-steps")
+                        .long("max-steps")
+                        .takes_value(true)
+                        .default_value("10"),
+                ),
+        )
+        .subcommand(
+            Command::new("cancel")
+                .about("Cancel a game so players can request refunds")
+                .arg(game_id())
+                .arg(Arg::new("reason").long("reason").takes_value(true).required(true)),
+        )
+        .subcommand(
+            Command::new("withdraw")
+                .about("Treasury withdrawal proposals")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("propose")
+                        .arg(Arg::new("amount").long("amount").takes_value(true).required(true))
+                        .arg(
+                            Arg::new("destination")
+                                .long("destination")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Destination token account"),
+                        )
+                        .arg(Arg::new("mint").long("mint").takes_value(true).required(true)),
+                )
+                .subcommand(
+                    Command::new("approve").arg(Arg::new("proposal-id").required(true)),
+                )
+                .subcommand(
+                    Command::new("execute")
+                        .arg(Arg::new("proposal-id").required(true))
+                        .arg(
+                            Arg::new("destination")
+                                .long("destination")
+                                .takes_value(true)
+                                .required(true),
+                        )
+                        .arg(Arg::new("mint").long("mint").takes_value(true).required(true)),
+                )
+                .subcommand(Command::new("cancel").arg(Arg::new("proposal-id").required(true)))
+                .subcommand(Command::new("show").arg(Arg::new("proposal-id").required(true))),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Show or update the treasury fee, pause flags and game limits")
+                .subcommand_required(true)
+                .subcommand(Command::new("show"))
+                .subcommand(
+                    Command::new("set")
+                        .arg(Arg::new("fee-percent").long("fee-percent").takes_value(true))
+                        .arg(Arg::new("pause-flags").long("pause-flags").takes_value(true))
+                        .arg(Arg::new("max-entry-fee").long("max-entry-fee").takes_value(true))
+                        .arg(
+                            Arg::new("allowed-mint")
+                                .long("allowed-mint")
+                                .takes_value(true)
+                                .multiple_occurrences(true)
+                                .help("Replaces the allowed mint list; repeat for several"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("events")
+                .about("Decode the events of a game's recent transactions, oldest first")
+                .arg(game_id())
+                .arg(Arg::new("limit").long("limit").takes_value(true).default_value("100")),
+        )
+}
+
+pub fn run(ctx: &Context, matches: &ArgMatches) -> Result<()> {
+    let client = LotteryClient::new(ctx.rpc());
+    match matches.subcommand() {
+        Some(("init", args)) => init(ctx, &client, args),
+        Some(("create-game", args)) => create_game(ctx, &client, args),
+        Some(("list", _)) => list(ctx, &client),
+        Some(("show", args)) => show(ctx, &client, args.value_of("game-id").expect("required")),
+        Some(("crank", args)) => crank(ctx, &client, args),
+        Some(("cancel", args)) => {
+            let game_id = args.value_of("game-id").expect("required");
+            let reason = args.value_of("reason").expect("required");
+            let cancel = instruction::cancel_game(&ctx.pubkey(), game_id, reason);
+            ctx.submit(client.rpc(), "cancel game", &[cancel])
+        }
+        Some(("withdraw", args)) => withdraw(ctx, &client, args),
+        Some(("config", args)) => config(ctx, &client, args),
+        Some(("events", args)) => events(ctx, &client, args),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+fn init(ctx: &Context, client: &LotteryClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let admin = ctx.pubkey();
+    let mint: Pubkey = arg(args, "mint")?;
+    let treasury_authority = opt_arg(args, "treasury-authority")?.unwrap_or(admin);
+
+    let mut instructions = Vec::new();
+    if client.treasury()?.is_none() {
+        instructions.push(instruction::initialize(
+            &admin,
+            &treasury_authority,
+            &mint,
+            arg(args, "fee-percent")?,
+        ));
+    }
+    if client.role_registry()?.is_none() {
+        instructions.push(instruction::initialize_roles(&admin));
+    }
+    if client.config()?.is_none() {
+        instructions.push(instruction::initialize_config(
+            &admin,
+            vec![mint],
+            arg(args, "max-entry-fee")?,
+        ));
+    }
+    if client.oracle_registry()?.is_none() {
+        instructions.push(instruction::initialize_oracle_registry(&admin));
+    }
+
+    if instructions.is_empty() {
+        println!("already initialized");
+        return Ok(());
+    }
+    ctx.submit(client.rpc(), "initialize", &instructions)
+}
+
+fn create_game(ctx: &Context, client: &LotteryClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let game_args = CreateGameArgs {
+        game_id: arg(args, "game-id")?,
+        entry_fee: arg(args, "entry-fee")?,
+        max_players: arg(args, "max-players")?,
+        winner_count: arg(args, "winners")?,
+        payment_deadline_minutes: arg(args, "deadline-minutes")?,
+        randomness_provider: parse_provider(args.value_of("provider").expect("has default"))?,
+        token_mint: arg(args, "mint")?,
+        vrf_oracle: opt_arg(args, "oracle")?.unwrap_or_else(|| ctx.pubkey()),
+    };
+    let create = instruction::create_game(&ctx.pubkey(), &game_args);
+    ctx.submit(client.rpc(), "create game", &[create])
+}
+
+fn active_players(players: &PlayerList) -> usize {
+    players
+        .players
+        .iter()
+        .filter(|player| player.eliminated_round.is_none())
+        .count()
+}
+
+fn list(ctx: &Context, client: &LotteryClient<RpcClient>) -> Result<()> {
+    let games = client.all_games()?;
+    let player_lists: Vec<Option<PlayerList>> = client.fetch_many(
+        &games
+            .iter()
+            .map(|(_, game)| pda::player_list(&game.game_id))
+            .collect::<Vec<_>>(),
+    )?;
+
+    let mut table = Table::new(&[
+        "game_id",
+        "state",
+        "players",
+        "active",
+        "round",
+        "entry_fee",
+        "prize_pool",
+        "provider",
+    ]);
+    for ((_, game), players) in games.iter().zip(&player_lists) {
+        table.row(vec![
+            json!(game.game_id),
+            json!(format!("{:?}", game.state)),
+            json!(players.as_ref().map(|p| p.players.len())),
+            json!(players.as_ref().map(active_players)),
+            json!(game.current_round),
+            json!(game.entry_fee),
+            json!(game.prize_pool),
+            json!(format!("{:?}", game.randomness_provider)),
+        ]);
+    }
+    print_table(ctx.format, &table);
+    Ok(())
+}
+
+fn game_record(address: &Pubkey, game: &GameState) -> Record {
+    Record::new()
+        .field("game_id", game.game_id.clone())
+        .field("address", address.to_string())
+        .field("state", format!("{:?}", game.state))
+        .field("authority", game.authority.to_string())
+        .field("token_mint", game.token_mint.to_string())
+        .field("entry_fee", game.entry_fee)
+        .field("max_players", game.max_players)
+        .field("winner_count", game.winner_count)
+        .field("prize_pool", game.prize_pool)
+        .field("treasury_fee", game.treasury_fee)
+        .field("fee_bps", game.fee_bps)
+        .field(
+            "number_range",
+            format!("{}-{}", game.number_range.min, game.number_range.max),
+        )
+        .field("current_round", game.current_round)
+        .field("drawn_numbers", json!(game.drawn_numbers))
+        .field(
+            "randomness_provider",
+            format!("{:?}", game.randomness_provider),
+        )
+        .field("vrf_request_pending", game.vrf_request_pending)
+        .field("pending_round", game.pending_round)
+        .field("created_at", game.created_at)
+        .field("started_at", game.started_at)
+        .field("completed_at", game.completed_at)
+        .field("payment_deadline", game.payment_deadline)
+}
+
+fn show(ctx: &Context, client: &LotteryClient<RpcClient>, game_id: &str) -> Result<()> {
+    let snapshot = client
+        .snapshot(game_id)?
+        .ok_or_else(|| anyhow!("game {:?} not found", game_id))?;
+
+    let mut players = Table::new(&[
+        "wallet",
+        "telegram_id",
+        "number",
+        "eliminated_round",
+        "winner",
+        "claimed",
+        "prize",
+    ]);
+    for player in &snapshot.players.players {
+        players.row(vec![
+            json!(player.wallet.to_string()),
+            json!(player.telegram_id),
+            json!(player.selected_number),
+            json!(player.eliminated_round),
+            json!(player.is_winner),
+            json!(player.prize_claimed),
+            json!(player.prize_amount),
+        ]);
+    }
+
+    let record = game_record(&pda::game(game_id), &snapshot.game)
+        .field("active_players", snapshot.active_players())
+        .field("next_step", snapshot.next_step().to_string());
+    print_record(ctx.format, &record, Some(("players", &players)));
+    Ok(())
+}
+
+fn crank(ctx: &Context, client: &LotteryClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let game_id = args.value_of("game-id").expect("required");
+    let max_steps: usize = arg(args, "max-steps")?;
+    let operator = ctx.pubkey();
+
+    for _ in 0..max_steps {
+        let snapshot = client
+            .snapshot(game_id)?
+            .ok_or_else(|| anyhow!("game {:?} not found", game_id))?;
+        let step = snapshot.next_step();
+        let instruction = match client.crank_instruction(&operator, &snapshot)? {
+            Some(instruction) => instruction,
+            None => {
+                print_record(
+                    ctx.format,
+                    &Record::new().field("step", step.to_string()),
+                    None,
+                );
+                return Ok(());
+            }
+        };
+        ctx.submit(client.rpc(), &step.to_string(), &[instruction])?;
+        // Simulation leaves the game where it was
+        if ctx.dry_run {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+fn proposal_id(args: &ArgMatches) -> Result<u64> {
+    arg(args, "proposal-id")
+}
+
+fn withdraw(ctx: &Context, client: &LotteryClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let signer = ctx.pubkey();
+    let (action, instruction) = match args.subcommand() {
+        Some(("propose", args)) => {
+            let id = client.next_proposal_id()?;
+            let propose = instruction::propose_withdrawal(
+                &signer,
+                id,
+                arg(args, "amount")?,
+                &arg(args, "destination")?,
+                &arg(args, "mint")?,
+            );
+            (format!("propose withdrawal {}", id), propose)
+        }
+        Some(("approve", args)) => {
+            let id = proposal_id(args)?;
+            (
+                format!("approve withdrawal {}", id),
+                instruction::approve_withdrawal(&signer, id),
+            )
+        }
+        Some(("execute", args)) => {
+            let id = proposal_id(args)?;
+            let execute = instruction::withdraw_treasury(
+                &signer,
+                id,
+                &arg(args, "destination")?,
+                &arg(args, "mint")?,
+            );
+            (format!("execute withdrawal {}", id), execute)
+        }
+        Some(("cancel", args)) => {
+            let id = proposal_id(args)?;
+            (
+                format!("cancel withdrawal {}", id),
+                instruction::cancel_withdrawal(&signer, id),
+            )
+        }
+        Some(("show", args)) => {
+            let id = proposal_id(args)?;
+            let proposal = client
+                .withdrawal(id)?
+                .ok_or_else(|| anyhow!("withdrawal proposal {} not found", id))?;
+            let record = Record::new()
+                .field("proposal_id", proposal.id)
+                .field("address", pda::withdrawal(id).to_string())
+                .field("proposer", proposal.proposer.to_string())
+                .field("amount", proposal.amount)
+                .field("destination", proposal.destination.to_string())
+                .field(
+                    "approvals",
+                    json!(proposal
+                        .approvals
+                        .iter()
+                        .map(|key| key.to_string())
+                        .collect::<Vec<_>>()),
+                )
+                .field("executable_at", proposal.executable_at)
+                .field("executed", proposal.executed)
+                .field("cancelled", proposal.cancelled);
+            print_record(ctx.format, &record, None);
+            return Ok(());
+        }
+        _ => unreachable!("subcommand required"),
+    };
+    ctx.submit(client.rpc(), &action, &[instruction])
+}
+
+fn treasury_record(treasury: &TreasuryState) -> Record {
+    Record::new()
+        .field("treasury", pda::treasury().to_string())
+        .field("authority", treasury.authority.to_string())
+        .field("fee_percentage", treasury.fee_percentage)
+        .field("total_collected", treasury.total_collected)
+        .field("pending_withdrawal", treasury.pending_withdrawal)
+        .field("approval_threshold", treasury.approval_threshold)
+        .field("withdrawal_delay", treasury.withdrawal_delay)
+        .field("proposal_count", treasury.proposal_count)
+}
+
+fn config(ctx: &Context, client: &LotteryClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let signer = ctx.pubkey();
+    match args.subcommand() {
+        Some(("show", _)) => {
+            let mut record = match client.treasury()? {
+                Some(treasury) => treasury_record(&treasury),
+                None => Record::new().field("treasury", Value::Null),
+            };
+            record = match client.config()? {
+                Some(config) => record
+                    .field("pause_flags", config.pause_flags)
+                    .field("max_entry_fee", config.max_entry_fee)
+                    .field(
+                        "allowed_mints",
+                        json!(config
+                            .allowed_mints
+                            .iter()
+                            .map(|mint| mint.to_string())
+                            .collect::<Vec<_>>()),
+                    ),
+                None => record.field("config", Value::Null),
+            };
+            print_record(ctx.format, &record, None);
+            Ok(())
+        }
+        Some(("set", args)) => {
+            let mut instructions = Vec::new();
+            if let Some(fee_percentage) = opt_arg(args, "fee-percent")? {
+                instructions.push(instruction::update_fee_percentage(&signer, fee_percentage));
+            }
+            if let Some(pause_flags) = opt_arg(args, "pause-flags")? {
+                instructions.push(instruction::set_pause_flags(&signer, pause_flags));
+            }
+            let allowed_mints = args
+                .values_of("allowed-mint")
+                .map(|values| {
+                    values
+                        .map(|value| Pubkey::from_str(value).context("invalid allowed-mint"))
+                        .collect::<Result<Vec<_>>>()
+                })
+                .transpose()?;
+            let max_entry_fee = opt_arg(args, "max-entry-fee")?;
+            if allowed_mints.is_some() || max_entry_fee.is_some() {
+                instructions.push(instruction::update_config(
+                    &signer,
+                    allowed_mints,
+                    max_entry_fee,
+                ));
+            }
+            if instructions.is_empty() {
+                bail!("nothing to update");
+            }
+            ctx.submit(client.rpc(), "update config", &instructions)
+        }
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+fn events(ctx: &Context, client: &LotteryClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let game_id = args.value_of("game-id").expect("required");
+    let config = GetConfirmedSignaturesForAddress2Config {
+        limit: Some(arg(args, "limit")?),
+        ..GetConfirmedSignaturesForAddress2Config::default()
+    };
+    let signatures = client
+        .rpc()
+        .get_signatures_for_address_with_config(&pda::game(game_id), config)?;
+
+    let mut table = Table::new(&["slot", "signature", "event", "data"]);
+    // Newest first from the RPC; print in execution order
+    for status in signatures
+        .iter()
+        .rev()
+        .filter(|status| status.err.is_none())
+    {
+        let signature = Signature::from_str(&status.signature)?;
+        for event in client.events(&signature)? {
+            table.row(vec![
+                json!(status.slot),
+                json!(status.signature),
+                json!(event.name()),
+                json!(format!("{:?}", event)),
+            ]);
+        }
+    }
+    print_table(ctx.format, &table);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_parses_nested_withdrawal() {
+        let matches = command()
+            .try_get_matches_from(["lottery", "withdraw", "approve", "3"])
+            .unwrap();
+        let (_, withdraw) = matches.subcommand().unwrap();
+        let (name, approve) = withdraw.subcommand().unwrap();
+        assert_eq!(name, "approve");
+        assert_eq!(proposal_id(approve).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_create_game_requires_mint() {
+        let result = command().try_get_matches_from([
+            "lottery",
+            "create-game",
+            "g1",
+            "--entry-fee",
+            "10",
+            "--max-players",
+            "5",
+        ]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,86 @@
+//! Operator CLI for the `telegram_lottery` and `raffle_v4` programs
+//!
+//! Wraps `lottery-client` and `raffle-client`: initialization, game and
+//! raffle creation, listing and inspection, cranking the next legal step,
+//! cancellations, treasury withdrawals and config updates. Every command
+//! signs with the Solana CLI keypair by default, prints a table or
+//! `--output json`, and `--dry-run` simulates instead of sending.
+
+mod context;
+mod lottery;
+mod output;
+mod raffle;
+
+use clap::{Arg, Command};
+
+use crate::context::Context;
+
+fn cli() -> Command<'static> {
+    Command::new("lottery-cli")
+        .about("Operate the telegram_lottery and raffle_v4 programs")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .short('u')
+                .global(true)
+                .takes_value(true)
+                .default_value("localhost")
+                .help("RPC URL or moniker: localhost, devnet, testnet, mainnet-beta"),
+        )
+        .arg(
+            Arg::new("keypair")
+                .long("keypair")
+                .short('k')
+                .global(true)
+                .takes_value(true)
+                .help("Signer keypair [default: ~/.config/solana/id.json]"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .global(true)
+                .takes_value(true)
+                .possible_values(["table", "json"])
+                .default_value("table"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .global(true)
+                .help("Simulate transactions instead of sending them"),
+        )
+        .subcommand(lottery::command())
+        .subcommand(raffle::command())
+}
+
+fn main() -> anyhow::Result<()> {
+    let matches = cli().get_matches();
+    let ctx = Context::from_matches(&matches)?;
+    match matches.subcommand() {
+        Some(("lottery", args)) => lottery::run(&ctx, args),
+        Some(("raffle", args)) => raffle::run(&ctx, args),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_is_well_formed() {
+        cli().debug_assert();
+    }
+
+    #[test]
+    fn test_global_args_after_subcommand() {
+        let matches = cli()
+            .try_get_matches_from(["lottery-cli", "lottery", "list", "-o", "json", "--dry-run"])
+            .unwrap();
+        assert_eq!(matches.value_of("output"), Some("json"));
+        assert!(matches.is_present("dry-run"));
+    }
+}
@@ -0,0 +1,219 @@
+//! Human tables and JSON output
+//!
+//! Every command renders its result as a [`Table`] (rows of JSON values) or
+//! a [`Record`] (ordered fields); `--output json` prints the same data as a
+//! JSON array or object instead.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            other => anyhow::bail!("unknown output format {:?}", other),
+        }
+    }
+}
+
+/// Cell text: strings unquoted, null as `-`
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "-".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Rows under fixed column headers
+#[derive(Debug, Default)]
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<Value>) {
+        debug_assert_eq!(cells.len(), self.headers.len());
+        self.rows.push(cells);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Array of objects keyed by header
+    pub fn to_json(&self) -> Value {
+        Value::Array(
+            self.rows
+                .iter()
+                .map(|row| {
+                    let object: Map<String, Value> = self
+                        .headers
+                        .iter()
+                        .map(|header| header.to_string())
+                        .zip(row.iter().cloned())
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(cell).collect())
+            .collect();
+        let mut widths: Vec<usize> = self.headers.iter().map(|header| header.len()).collect();
+        for row in &rows {
+            for (width, text) in widths.iter_mut().zip(row) {
+                *width = (*width).max(text.chars().count());
+            }
+        }
+
+        let line = |f: &mut fmt::Formatter<'_>, cells: &[String]| -> fmt::Result {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(text, width)| format!("{:<width$}", text, width = width))
+                .collect();
+            writeln!(f, "{}", padded.join("  ").trim_end())
+        };
+
+        let headers: Vec<String> = self.headers.iter().map(|h| h.to_uppercase()).collect();
+        line(f, &headers)?;
+        for row in &rows {
+            line(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered fields of a single object
+#[derive(Debug, Default)]
+pub struct Record {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl Record {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        Value::Object(
+            self.fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .fields
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        for (name, value) in &self.fields {
+            writeln!(f, "{:<width$}  {}", name, cell(value), width = width)?;
+        }
+        Ok(())
+    }
+}
+
+/// Print a record, followed by an optional titled table nested under `key`
+/// in JSON output
+pub fn print_record(format: Format, record: &Record, nested: Option<(&str, &Table)>) {
+    match format {
+        Format::Table => {
+            print!("{}", record);
+            if let Some((key, table)) = nested {
+                println!();
+                if table.is_empty() {
+                    println!("no {}", key);
+                } else {
+                    print!("{}", table);
+                }
+            }
+        }
+        Format::Json => {
+            let mut json = record.to_json();
+            if let (Some((key, table)), Value::Object(object)) = (nested, &mut json) {
+                object.insert(key.to_string(), table.to_json());
+            }
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).expect("JSON values serialize")
+            );
+        }
+    }
+}
+
+pub fn print_table(format: Format, table: &Table) {
+    match format {
+        Format::Table => print!("{}", table),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&table.to_json()).expect("JSON values serialize")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_table_aligns_columns() {
+        let mut table = Table::new(&["id", "state"]);
+        table.row(vec![json!("game-1"), json!("Playing")]);
+        table.row(vec![json!(7), Value::Null]);
+        assert_eq!(
+            table.to_string(),
+            "ID      STATE\ngame-1  Playing\n7       -\n"
+        );
+    }
+
+    #[test]
+    fn test_table_json_keys_by_header() {
+        let mut table = Table::new(&["id", "players"]);
+        table.row(vec![json!("g1"), json!(3)]);
+        assert_eq!(table.to_json(), json!([{ "id": "g1", "players": 3 }]));
+    }
+
+    #[test]
+    fn test_record_renders_fields_in_order() {
+        let record = Record::new().field("game_id", "g1").field("round", 2);
+        assert_eq!(record.to_string(), "game_id  g1\nround    2\n");
+        assert_eq!(record.to_json(), json!({ "game_id": "g1", "round": 2 }));
+    }
+}
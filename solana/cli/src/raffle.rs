@@ -0,0 +1,578 @@
+//! `lottery-cli raffle ...`: raffle_v4 operations
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Arg, ArgMatches, Command};
+use raffle_client::instruction::{self, CreateRaffleParams, UpdateConfigParams};
+use raffle_client::state::{ProgramState, RaffleAccount, RaffleStatus, RandomnessProvider};
+use raffle_client::{draw, pda, ClientError, RaffleClient};
+use serde_json::json;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::context::{arg, opt_arg, parse_provider, Context, PROVIDERS};
+use crate::output::{print_record, print_table, Record, Table};
+
+pub fn command() -> Command<'static> {
+    let raffle_id = || {
+        Arg::new("raffle-id")
+            .required(true)
+            .help("Raffle identifier")
+    };
+    Command::new("raffle")
+        .about("Operate raffle_v4 raffles")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("init")
+                .about("Create the program state and role registry (skips existing)")
+                .arg(
+                    Arg::new("fee-rate")
+                        .long("fee-rate")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("treasury")
+                        .long("treasury")
+                        .takes_value(true)
+                        .help("Defaults to the CLI keypair"),
+                ),
+        )
+        .subcommand(
+            Command::new("create")
+                .about("Create a raffle")
+                .arg(raffle_id())
+                .arg(
+                    Arg::new("title")
+                        .long("title")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("description")
+                        .long("description")
+                        .takes_value(true)
+                        .default_value(""),
+                )
+                .arg(
+                    Arg::new("prize")
+                        .long("prize")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("ticket-price")
+                        .long("ticket-price")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("max-tickets")
+                        .long("max-tickets")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("min-tickets")
+                        .long("min-tickets")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("max-per-wallet")
+                        .long("max-per-wallet")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("duration")
+                        .long("duration")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Seconds from the start time"),
+                )
+                .arg(
+                    Arg::new("start-time")
+                        .long("start-time")
+                        .takes_value(true)
+                        .help("Unix timestamp; defaults to now"),
+                )
+                .arg(
+                    Arg::new("provider")
+                        .long("provider")
+                        .takes_value(true)
+                        .possible_values(PROVIDERS)
+                        .default_value("orao"),
+                ),
+        )
+        .subcommand(Command::new("list").about("List every raffle"))
+        .subcommand(
+            Command::new("show")
+                .about("Show a raffle, its tickets and its next step")
+                .arg(raffle_id()),
+        )
+        .subcommand(
+            Command::new("crank")
+                .about("Submit the raffle's next step: draw, bind, distribute or cancel undersold")
+                .arg(raffle_id()),
+        )
+        .subcommand(
+            Command::new("cancel")
+                .about("Cancel a raffle before any tickets sell (creator only)")
+                .arg(raffle_id()),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Show or queue program configuration changes")
+                .subcommand_required(true)
+                .subcommand(Command::new("show"))
+                .subcommand(
+                    Command::new("set")
+                        .arg(Arg::new("fee-rate").long("fee-rate").takes_value(true))
+                        .arg(Arg::new("treasury").long("treasury").takes_value(true))
+                        .arg(Arg::new("authority").long("authority").takes_value(true))
+                        .arg(
+                            Arg::new("pause-flags")
+                                .long("pause-flags")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::new("config-delay")
+                                .long("config-delay")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    Command::new("apply").about("Apply the queued change once its delay passed"),
+                )
+                .subcommand(Command::new("cancel").about("Drop the queued change")),
+        )
+}
+
+pub fn run(ctx: &Context, matches: &ArgMatches) -> Result<()> {
+    let client = RaffleClient::new(ctx.rpc());
+    match matches.subcommand() {
+        Some(("init", args)) => init(ctx, &client, args),
+        Some(("create", args)) => create(ctx, &client, args),
+        Some(("list", _)) => list(ctx, &client),
+        Some(("show", args)) => show(ctx, &client, arg(args, "raffle-id")?),
+        Some(("crank", args)) => crank(ctx, &client, arg(args, "raffle-id")?),
+        Some(("cancel", args)) => {
+            let raffle_id = arg(args, "raffle-id")?;
+            let cancel = instruction::cancel_raffle(&ctx.pubkey(), raffle_id);
+            ctx.submit(client.rpc(), "cancel raffle", &[cancel])
+        }
+        Some(("config", args)) => config(ctx, &client, args),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+/// Cluster time of the latest slot; the program checks deadlines against
+/// it rather than the local clock
+fn cluster_time(rpc: &RpcClient) -> Result<i64> {
+    let slot = rpc.get_slot()?;
+    Ok(rpc.get_block_time(slot)?)
+}
+
+fn init(ctx: &Context, client: &RaffleClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let authority = ctx.pubkey();
+    let mut instructions = Vec::new();
+    if client.program_state()?.is_none() {
+        instructions.push(instruction::initialize_program(
+            &authority,
+            arg(args, "fee-rate")?,
+            opt_arg(args, "treasury")?.unwrap_or(authority),
+        ));
+    }
+    if client.role_registry()?.is_none() {
+        instructions.push(instruction::initialize_roles(&authority));
+    }
+
+    if instructions.is_empty() {
+        println!("already initialized");
+        return Ok(());
+    }
+    ctx.submit(client.rpc(), "initialize", &instructions)
+}
+
+fn create(ctx: &Context, client: &RaffleClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let params = CreateRaffleParams {
+        raffle_id: arg(args, "raffle-id")?,
+        title: arg(args, "title")?,
+        description: arg(args, "description")?,
+        content: None,
+        prize_amount: arg(args, "prize")?,
+        ticket_price: arg(args, "ticket-price")?,
+        price_tiers: vec![],
+        max_tickets: arg(args, "max-tickets")?,
+        min_tickets: opt_arg(args, "min-tickets")?,
+        max_tickets_per_wallet: opt_arg(args, "max-per-wallet")?,
+        start_time: opt_arg(args, "start-time")?,
+        duration: arg(args, "duration")?,
+        draw_timeout: None,
+        randomness_provider: parse_provider(args.value_of("provider").expect("has default"))?,
+    };
+    let create = instruction::create_raffle(&ctx.pubkey(), params);
+    ctx.submit(client.rpc(), "create raffle", &[create])
+}
+
+/// What the operator should do next for a raffle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RaffleStep {
+    RequestDraw,
+    FulfillAndBind,
+    BindWinner,
+    DistributePrize,
+    CancelUndersold,
+    Wait(&'static str),
+    Done,
+}
+
+fn next_step(raffle: &RaffleAccount, now: i64) -> RaffleStep {
+    if raffle.frozen {
+        return RaffleStep::Wait("raffle frozen");
+    }
+    match raffle.status {
+        RaffleStatus::Active if raffle.is_undersold(now) => RaffleStep::CancelUndersold,
+        RaffleStatus::Active if !raffle.can_be_drawn(now) => RaffleStep::Wait("ticket sales open"),
+        RaffleStatus::Active if raffle.randomness_provider == RandomnessProvider::Orao => {
+            RaffleStep::RequestDraw
+        }
+        RaffleStatus::Active => RaffleStep::Wait("awaiting commit"),
+        RaffleStatus::Drawing if raffle.randomness_provider != RandomnessProvider::Orao => {
+            RaffleStep::Wait("awaiting reveal")
+        }
+        RaffleStatus::Drawing if raffle.winning_ticket.is_some() => RaffleStep::BindWinner,
+        RaffleStatus::Drawing => RaffleStep::FulfillAndBind,
+        RaffleStatus::Complete if raffle.distributed_at.is_none() => RaffleStep::DistributePrize,
+        RaffleStatus::Complete | RaffleStatus::Cancelled => RaffleStep::Done,
+    }
+}
+
+impl std::fmt::Display for RaffleStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RaffleStep::RequestDraw => write!(f, "request draw"),
+            RaffleStep::FulfillAndBind => write!(f, "fulfill draw and bind winner"),
+            RaffleStep::BindWinner => write!(f, "bind winner"),
+            RaffleStep::DistributePrize => write!(f, "distribute prize"),
+            RaffleStep::CancelUndersold => write!(f, "cancel undersold raffle"),
+            RaffleStep::Wait(reason) => write!(f, "wait: {}", reason),
+            RaffleStep::Done => write!(f, "done"),
+        }
+    }
+}
+
+fn step_instructions(
+    ctx: &Context,
+    client: &RaffleClient<RpcClient>,
+    raffle: &RaffleAccount,
+    step: RaffleStep,
+) -> Result<Option<Vec<Instruction>>> {
+    let caller = ctx.pubkey();
+    let instructions = match step {
+        RaffleStep::RequestDraw => {
+            let seed = draw::vrf_seed(raffle)?;
+            let vrf_request = pda::orao_randomness(&seed);
+            vec![instruction::request_winner_selection(
+                &caller,
+                raffle.id,
+                &vrf_request,
+                &client.orao_treasury()?,
+            )]
+        }
+        RaffleStep::FulfillAndBind => match client.fulfill_and_bind(&caller, raffle.id) {
+            Ok(instructions) => instructions,
+            Err(ClientError::RandomnessNotFulfilled(_)) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        },
+        RaffleStep::BindWinner => {
+            let winning_ticket = raffle.winning_ticket.expect("checked by next_step");
+            let tickets: Vec<_> = client
+                .tickets(raffle.id, None)?
+                .into_iter()
+                .map(|(_, ticket)| ticket)
+                .collect();
+            let holder = draw::holder_of(&tickets, winning_ticket)
+                .ok_or_else(|| anyhow!("no ticket account holds ticket {}", winning_ticket))?;
+            vec![instruction::bind_winner(
+                &caller,
+                raffle.id,
+                holder.ticket_number,
+            )]
+        }
+        RaffleStep::DistributePrize => {
+            let winner = raffle.winner.expect("complete raffles have a winner");
+            vec![instruction::distribute_prize(
+                &caller,
+                raffle.id,
+                &winner,
+                &raffle.treasury,
+            )]
+        }
+        RaffleStep::CancelUndersold => vec![instruction::cancel_undersold_raffle(
+            &caller,
+            raffle.id,
+            &raffle.creator,
+        )],
+        RaffleStep::Wait(_) | RaffleStep::Done => return Ok(None),
+    };
+    Ok(Some(instructions))
+}
+
+fn fetch_raffle(client: &RaffleClient<RpcClient>, raffle_id: u64) -> Result<RaffleAccount> {
+    client
+        .raffle(raffle_id)?
+        .ok_or_else(|| anyhow!("raffle {} not found", raffle_id))
+}
+
+fn list(ctx: &Context, client: &RaffleClient<RpcClient>) -> Result<()> {
+    let now = cluster_time(client.rpc())?;
+    let mut table = Table::new(&[
+        "id",
+        "title",
+        "status",
+        "sold",
+        "max",
+        "ticket_price",
+        "prize",
+        "ends",
+        "next_step",
+    ]);
+    for (_, raffle) in client.all_raffles()? {
+        table.row(vec![
+            json!(raffle.id),
+            json!(raffle.title),
+            json!(format!("{:?}", raffle.status)),
+            json!(raffle.tickets_sold),
+            json!(raffle.max_tickets),
+            json!(raffle.ticket_price),
+            json!(raffle.prize_amount),
+            json!(raffle.end_time),
+            json!(next_step(&raffle, now).to_string()),
+        ]);
+    }
+    print_table(ctx.format, &table);
+    Ok(())
+}
+
+fn show(ctx: &Context, client: &RaffleClient<RpcClient>, raffle_id: u64) -> Result<()> {
+    let raffle = fetch_raffle(client, raffle_id)?;
+    let now = cluster_time(client.rpc())?;
+
+    let mut tickets = Table::new(&["first", "quantity", "owner", "paid", "refunded"]);
+    for (_, ticket) in client.tickets(raffle_id, None)? {
+        tickets.row(vec![
+            json!(ticket.ticket_number),
+            json!(ticket.quantity),
+            json!(ticket.owner.to_string()),
+            json!(ticket.amount_paid),
+            json!(ticket.refunded),
+        ]);
+    }
+
+    let record = Record::new()
+        .field("id", raffle.id)
+        .field("address", pda::raffle(raffle_id).to_string())
+        .field("title", raffle.title.clone())
+        .field("creator", raffle.creator.to_string())
+        .field("status", format!("{:?}", raffle.status))
+        .field("frozen", raffle.frozen)
+        .field("prize_amount", raffle.prize_amount)
+        .field("ticket_price", raffle.ticket_price)
+        .field("fee_rate", raffle.fee_rate)
+        .field("tickets_sold", raffle.tickets_sold)
+        .field("min_tickets", raffle.min_tickets)
+        .field("max_tickets", raffle.max_tickets)
+        .field("ticket_revenue", raffle.ticket_revenue)
+        .field("start_time", raffle.start_time)
+        .field("end_time", raffle.end_time)
+        .field(
+            "randomness_provider",
+            format!("{:?}", raffle.randomness_provider),
+        )
+        .field("vrf_request", raffle.vrf_request.map(|key| key.to_string()))
+        .field("draw_attempts", raffle.draw_attempts)
+        .field("winning_ticket", raffle.winning_ticket)
+        .field("winner", raffle.winner.map(|key| key.to_string()))
+        .field("distributed_at", raffle.distributed_at)
+        .field("next_step", next_step(&raffle, now).to_string());
+    print_record(ctx.format, &record, Some(("tickets", &tickets)));
+    Ok(())
+}
+
+fn crank(ctx: &Context, client: &RaffleClient<RpcClient>, raffle_id: u64) -> Result<()> {
+    let raffle = fetch_raffle(client, raffle_id)?;
+    let now = cluster_time(client.rpc())?;
+    let step = next_step(&raffle, now);
+
+    match step_instructions(ctx, client, &raffle, step)? {
+        Some(instructions) => ctx.submit(client.rpc(), &step.to_string(), &instructions),
+        None => {
+            let step = match step {
+                RaffleStep::FulfillAndBind => RaffleStep::Wait("awaiting ORAO fulfillment"),
+                other => other,
+            };
+            print_record(
+                ctx.format,
+                &Record::new().field("step", step.to_string()),
+                None,
+            );
+            Ok(())
+        }
+    }
+}
+
+fn program_record(state: &ProgramState) -> Record {
+    let pending = state.pending_config.as_ref();
+    Record::new()
+        .field("program_state", pda::program_state().to_string())
+        .field("authority", state.authority.to_string())
+        .field(
+            "pending_authority",
+            state.pending_authority.map(|key| key.to_string()),
+        )
+        .field("treasury", state.treasury.to_string())
+        .field("fee_rate", state.fee_rate)
+        .field("pause_flags", state.pause_flags)
+        .field("config_delay", state.config_delay)
+        .field("total_raffles", state.total_raffles)
+        .field("total_volume", state.total_volume)
+        .field(
+            "pending_fee_rate",
+            pending.and_then(|change| change.fee_rate),
+        )
+        .field(
+            "pending_treasury",
+            pending
+                .and_then(|change| change.treasury)
+                .map(|key| key.to_string()),
+        )
+        .field(
+            "pending_config_delay",
+            pending.and_then(|change| change.config_delay),
+        )
+        .field("pending_eta", pending.map(|change| change.eta))
+}
+
+fn config(ctx: &Context, client: &RaffleClient<RpcClient>, args: &ArgMatches) -> Result<()> {
+    let caller = ctx.pubkey();
+    let (action, instruction) = match args.subcommand() {
+        Some(("show", _)) => {
+            let state = client
+                .program_state()?
+                .ok_or_else(|| anyhow!("program not initialized"))?;
+            print_record(ctx.format, &program_record(&state), None);
+            return Ok(());
+        }
+        Some(("set", args)) => {
+            let params = UpdateConfigParams {
+                new_fee_rate: opt_arg(args, "fee-rate")?,
+                new_treasury: opt_arg::<Pubkey>(args, "treasury")?,
+                new_authority: opt_arg::<Pubkey>(args, "authority")?,
+                new_pause_flags: opt_arg(args, "pause-flags")?,
+                new_config_delay: opt_arg(args, "config-delay")?,
+            };
+            if params.new_fee_rate.is_none()
+                && params.new_treasury.is_none()
+                && params.new_authority.is_none()
+                && params.new_pause_flags.is_none()
+                && params.new_config_delay.is_none()
+            {
+                bail!("nothing to update");
+            }
+            (
+                "update config",
+                instruction::update_program_config(&caller, params),
+            )
+        }
+        Some(("apply", _)) => (
+            "apply config change",
+            instruction::apply_config_change(&caller),
+        ),
+        Some(("cancel", _)) => (
+            "cancel config change",
+            instruction::cancel_config_change(&caller),
+        ),
+        _ => unreachable!("subcommand required"),
+    };
+    ctx.submit(client.rpc(), action, &[instruction])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raffle(status: RaffleStatus) -> RaffleAccount {
+        RaffleAccount {
+            id: 1,
+            creator: Pubkey::new_unique(),
+            title: "Raffle".to_string(),
+            description: String::new(),
+            content: None,
+            prize_amount: 1_000_000_000,
+            ticket_price: 10_000_000,
+            fee_rate: 250,
+            treasury: Pubkey::new_unique(),
+            price_tiers: vec![],
+            max_tickets: 10,
+            min_tickets: 2,
+            tickets_sold: 0,
+            ticket_revenue: 0,
+            tickets_refunded: 0,
+            open_ticket_accounts: 0,
+            max_tickets_per_wallet: None,
+            start_time: 0,
+            end_time: 1_000,
+            status,
+            frozen: false,
+            escrow_bump: 255,
+            raffle_bump: 255,
+            vrf_request: None,
+            randomness_provider: RandomnessProvider::Orao,
+            randomness_request: None,
+            draw_timeout: RaffleAccount::DEFAULT_DRAW_TIMEOUT,
+            draw_requested_at: None,
+            draw_attempts: 0,
+            winner: None,
+            winning_ticket: None,
+            vrf_proof: None,
+            created_at: 0,
+            drawn_at: None,
+            distributed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_active_raffle_steps() {
+        let mut r = raffle(RaffleStatus::Active);
+        r.tickets_sold = 3;
+        assert_eq!(next_step(&r, 999), RaffleStep::Wait("ticket sales open"));
+        assert_eq!(next_step(&r, 1_000), RaffleStep::RequestDraw);
+
+        r.tickets_sold = 1;
+        assert_eq!(next_step(&r, 1_000), RaffleStep::CancelUndersold);
+
+        r.frozen = true;
+        assert_eq!(next_step(&r, 1_000), RaffleStep::Wait("raffle frozen"));
+    }
+
+    #[test]
+    fn test_draw_and_settlement_steps() {
+        let mut r = raffle(RaffleStatus::Drawing);
+        assert_eq!(next_step(&r, 0), RaffleStep::FulfillAndBind);
+        r.winning_ticket = Some(4);
+        assert_eq!(next_step(&r, 0), RaffleStep::BindWinner);
+
+        r.status = RaffleStatus::Complete;
+        r.winner = Some(Pubkey::new_unique());
+        assert_eq!(next_step(&r, 0), RaffleStep::DistributePrize);
+        r.distributed_at = Some(5);
+        assert_eq!(next_step(&r, 0), RaffleStep::Done);
+    }
+
+    #[test]
+    fn test_non_orao_draws_wait() {
+        let mut r = raffle(RaffleStatus::Drawing);
+        r.randomness_provider = RandomnessProvider::SlotHash;
+        assert_eq!(next_step(&r, 0), RaffleStep::Wait("awaiting reveal"));
+    }
+}
@@ -0,0 +1,403 @@
+//! Account fetching and transaction submission
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use randomness::orao::OraoRandomness;
+use randomness::RandomnessSource;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use telegram_lottery::state::{
+    GameState, LotteryConfig, OracleRegistry, PlayerList, RandomnessProvider, RoleRegistry,
+    TreasuryState, VrfResult, WithdrawalProposal,
+};
+
+use crate::crank::{CrankStep, GameSnapshot};
+use crate::error::{ClientError, Result};
+use crate::events::{parse_logs, LotteryEvent};
+use crate::pda;
+use crate::rpc::LotteryRpc;
+
+/// Offset of the treasury in ORAO's network state: discriminator, authority
+const ORAO_TREASURY_OFFSET: usize = 8 + 32;
+
+/// Lottery client over any [`LotteryRpc`] backend
+pub struct LotteryClient<R> {
+    rpc: R,
+}
+
+impl<R: LotteryRpc> LotteryClient<R> {
+    pub fn new(rpc: R) -> Self {
+        Self { rpc }
+    }
+
+    /// Underlying RPC backend
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    /// Decode an Anchor account, checking its discriminator
+    pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+        T::try_deserialize(&mut &data[..]).map_err(|error| ClientError::Decode {
+            address: *address,
+            reason: error.to_string(),
+        })
+    }
+
+    /// Fetch and decode an account, or `None` if it does not exist
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        self.rpc
+            .get_account_data(address)?
+            .map(|data| Self::decode(address, &data))
+            .transpose()
+    }
+
+    /// Fetch and decode several accounts of one type, in order
+    pub fn fetch_many<T: AccountDeserialize>(&self, addresses: &[Pubkey]) -> Result<Vec<Option<T>>> {
+        let accounts = self.rpc.get_multiple_account_data(addresses)?;
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(address, data)| data.map(|data| Self::decode(address, &data)).transpose())
+            .collect()
+    }
+
+    pub fn game(&self, game_id: &str) -> Result<Option<GameState>> {
+        self.fetch(&pda::game(game_id))
+    }
+
+    /// Several games in one round trip
+    pub fn games(&self, game_ids: &[&str]) -> Result<Vec<Option<GameState>>> {
+        let addresses: Vec<Pubkey> = game_ids.iter().map(|game_id| pda::game(game_id)).collect();
+        self.fetch_many(&addresses)
+    }
+
+    /// Every game account, oldest first
+    pub fn all_games(&self) -> Result<Vec<(Pubkey, GameState)>> {
+        let filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            GameState::DISCRIMINATOR.to_vec(),
+        ))];
+        let mut games = self
+            .rpc
+            .get_program_account_data(filters)?
+            .into_iter()
+            .map(|(address, data)| Ok((address, Self::decode::<GameState>(&address, &data)?)))
+            .collect::<Result<Vec<_>>>()?;
+        games.sort_by(|(_, a), (_, b)| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.game_id.cmp(&b.game_id))
+        });
+        Ok(games)
+    }
+
+    pub fn player_list(&self, game_id: &str) -> Result<Option<PlayerList>> {
+        self.fetch(&pda::player_list(game_id))
+    }
+
+    pub fn vrf_result(&self, game_id: &str, round: u8) -> Result<Option<VrfResult>> {
+        self.fetch(&pda::vrf_result(game_id, round))
+    }
+
+    pub fn treasury(&self) -> Result<Option<TreasuryState>> {
+        self.fetch(&pda::treasury())
+    }
+
+    pub fn config(&self) -> Result<Option<LotteryConfig>> {
+        self.fetch(&pda::config())
+    }
+
+    pub fn role_registry(&self) -> Result<Option<RoleRegistry>> {
+        self.fetch(&pda::roles())
+    }
+
+    pub fn oracle_registry(&self) -> Result<Option<OracleRegistry>> {
+        self.fetch(&pda::oracles())
+    }
+
+    pub fn withdrawal(&self, proposal_id: u64) -> Result<Option<WithdrawalProposal>> {
+        self.fetch(&pda::withdrawal(proposal_id))
+    }
+
+    /// Id the next withdrawal proposal must use
+    pub fn next_proposal_id(&self) -> Result<u64> {
+        let address = pda::treasury();
+        let treasury: TreasuryState = self
+            .fetch(&address)?
+            .ok_or(ClientError::AccountNotFound(address))?;
+        Ok(treasury.proposal_count)
+    }
+
+    /// Fee treasury recorded in ORAO's network state, needed by `request_orao_vrf`
+    pub fn orao_treasury(&self) -> Result<Pubkey> {
+        let address = randomness::orao::network_state_address();
+        let data = self
+            .rpc
+            .get_account_data(&address)?
+            .ok_or(ClientError::AccountNotFound(address))?;
+        let bytes = data
+            .get(ORAO_TREASURY_OFFSET..ORAO_TREASURY_OFFSET + 32)
+            .ok_or_else(|| ClientError::Decode {
+                address,
+                reason: "network state too short".to_string(),
+            })?;
+        Ok(Pubkey::try_from(bytes).expect("slice is 32 bytes"))
+    }
+
+    /// Whether ORAO has fulfilled the randomness request for a game round
+    pub fn randomness_fulfilled(&self, game_id: &str, round: u8) -> Result<bool> {
+        let data = self
+            .rpc
+            .get_account_data(&pda::orao_randomness(game_id, round))?;
+        Ok(data
+            .and_then(|data| OraoRandomness::try_from_data(&data).ok())
+            .and_then(|randomness| randomness.read_64())
+            .is_some())
+    }
+
+    /// Chain state deciding a game's next step, or `None` if the game does
+    /// not exist
+    pub fn snapshot(&self, game_id: &str) -> Result<Option<GameSnapshot>> {
+        let game = match self.game(game_id)? {
+            Some(game) => game,
+            None => return Ok(None),
+        };
+        let players_address = pda::player_list(game_id);
+        let players = self
+            .fetch(&players_address)?
+            .ok_or(ClientError::AccountNotFound(players_address))?;
+        let vrf_result = match game.current_round {
+            0 => None,
+            round => self.vrf_result(game_id, round)?,
+        };
+        let randomness_fulfilled = game.vrf_request_pending
+            && game.randomness_provider == RandomnessProvider::Orao
+            && self.randomness_fulfilled(game_id, game.pending_round)?;
+        let play_paused = self
+            .config()?
+            .is_some_and(|config| config.is_paused(LotteryConfig::PAUSE_PLAY));
+
+        Ok(Some(GameSnapshot {
+            game,
+            players,
+            vrf_result,
+            randomness_fulfilled,
+            play_paused,
+        }))
+    }
+
+    /// Instruction for the snapshot's next step, signed by `operator`, or
+    /// `None` if the game has to wait or is done
+    pub fn crank_instruction(
+        &self,
+        operator: &Pubkey,
+        snapshot: &GameSnapshot,
+    ) -> Result<Option<Instruction>> {
+        let step = snapshot.next_step();
+        let orao_treasury = match step {
+            CrankStep::RequestRandomness { .. } => self.orao_treasury()?,
+            _ => Pubkey::default(),
+        };
+        Ok(step.instruction(operator, &snapshot.game, &orao_treasury))
+    }
+
+    /// Sign with `signers` (the first pays fees) and submit
+    pub fn send(&self, instructions: &[Instruction], signers: &[&dyn Signer]) -> Result<Signature> {
+        let payer = signers.first().map(|signer| signer.pubkey());
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, payer.as_ref(), signers, blockhash);
+        self.rpc.send_and_confirm_transaction(&transaction)
+    }
+
+    /// Lottery events emitted by a confirmed transaction
+    pub fn events(&self, signature: &Signature) -> Result<Vec<LotteryEvent>> {
+        let logs = self
+            .rpc
+            .get_transaction_logs(signature)?
+            .ok_or(ClientError::TransactionNotFound(*signature))?;
+        Ok(parse_logs(&logs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crank::tests::{game, players};
+    use crate::instruction;
+    use crate::rpc::mock::MockRpc;
+    use anchor_lang::{AccountSerialize, Event};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use solana_sdk::signature::Keypair;
+    use telegram_lottery::state::{GameStartedEvent, GameStatus, VrfResult};
+
+    fn vrf_result(round: u8, drawn_number: u8) -> VrfResult {
+        VrfResult {
+            game_id: "g1".to_string(),
+            round,
+            random_value: [7u8; 32],
+            proof: vec![],
+            drawn_number,
+            used: false,
+            timestamp: 1,
+            bump: 255,
+        }
+    }
+
+    fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_fetch_decodes_and_reports_missing() {
+        let rpc = MockRpc::new();
+        rpc.set_account(pda::vrf_result("g1", 1), serialize(&vrf_result(1, 5)));
+        let client = LotteryClient::new(rpc);
+
+        let result = client.vrf_result("g1", 1).unwrap().unwrap();
+        assert_eq!(result.drawn_number, 5);
+        assert!(client.vrf_result("g1", 2).unwrap().is_none());
+        assert!(client.game("g1").unwrap().is_none());
+        assert!(matches!(
+            client.next_proposal_id(),
+            Err(ClientError::AccountNotFound(address)) if address == pda::treasury()
+        ));
+    }
+
+    #[test]
+    fn test_wrong_account_type_is_a_decode_error() {
+        let rpc = MockRpc::new();
+        rpc.set_account(pda::game("g1"), serialize(&vrf_result(1, 5)));
+        let client = LotteryClient::new(rpc);
+        assert!(matches!(client.game("g1"), Err(ClientError::Decode { .. })));
+    }
+
+    #[test]
+    fn test_fetch_many_keeps_order() {
+        let rpc = MockRpc::new();
+        rpc.set_account(pda::vrf_result("g1", 2), serialize(&vrf_result(2, 3)));
+        let client = LotteryClient::new(rpc);
+        let addresses = [pda::vrf_result("g1", 1), pda::vrf_result("g1", 2)];
+        let results: Vec<Option<VrfResult>> = client.fetch_many(&addresses).unwrap();
+        assert!(results[0].is_none());
+        assert_eq!(results[1].as_ref().unwrap().drawn_number, 3);
+    }
+
+    #[test]
+    fn test_orao_treasury_reads_network_state() {
+        let treasury = Pubkey::new_unique();
+        let mut data = vec![0u8; ORAO_TREASURY_OFFSET];
+        data.extend_from_slice(treasury.as_ref());
+        data.extend_from_slice(&[0u8; 16]);
+
+        let rpc = MockRpc::new();
+        let client = LotteryClient::new(rpc);
+        assert!(client.orao_treasury().is_err());
+        client.rpc().set_account(randomness::orao::network_state_address(), data);
+        assert_eq!(client.orao_treasury().unwrap(), treasury);
+    }
+
+    #[test]
+    fn test_all_games_lists_only_games() {
+        let rpc = MockRpc::new();
+        let mut older = game(GameStatus::Playing);
+        older.game_id = "g2".to_string();
+        older.created_at = -1;
+        rpc.set_account(pda::game("g1"), serialize(&game(GameStatus::Joining)));
+        rpc.set_account(pda::game("g2"), serialize(&older));
+        rpc.set_account(pda::vrf_result("g1", 1), serialize(&vrf_result(1, 5)));
+        let client = LotteryClient::new(rpc);
+
+        let games = client.all_games().unwrap();
+        let ids: Vec<&str> = games.iter().map(|(_, g)| g.game_id.as_str()).collect();
+        assert_eq!(ids, vec!["g2", "g1"]);
+        assert_eq!(games[1].0, pda::game("g1"));
+    }
+
+    #[test]
+    fn test_snapshot_drives_crank() {
+        let operator = Pubkey::new_unique();
+        let rpc = MockRpc::new();
+        let mut state = game(GameStatus::Playing);
+        state.vrf_request_pending = true;
+        state.pending_round = 1;
+        rpc.set_account(pda::game("g1"), serialize(&state));
+        rpc.set_account(
+            pda::player_list("g1"),
+            serialize(&players(&[Some(1), Some(2)])),
+        );
+        let client = LotteryClient::new(rpc);
+        assert!(client.snapshot("missing").unwrap().is_none());
+
+        let snapshot = client.snapshot("g1").unwrap().unwrap();
+        assert_eq!(
+            snapshot.next_step(),
+            CrankStep::Wait("awaiting ORAO fulfillment")
+        );
+        assert!(client
+            .crank_instruction(&operator, &snapshot)
+            .unwrap()
+            .is_none());
+
+        // ORAO randomness account: discriminator, seed, randomness
+        let seed = pda::round_seed("g1", 1);
+        let mut data = solana_sdk::hash::hash(b"account:Randomness").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&seed);
+        data.extend_from_slice(&[7u8; 64]);
+        client
+            .rpc()
+            .set_account(pda::orao_randomness("g1", 1), data);
+
+        let snapshot = client.snapshot("g1").unwrap().unwrap();
+        assert_eq!(
+            client.crank_instruction(&operator, &snapshot).unwrap(),
+            Some(instruction::fulfill_orao_vrf(&operator, "g1", 1))
+        );
+    }
+
+    #[test]
+    fn test_send_signs_and_submits() {
+        let operator = Keypair::new();
+        let client = LotteryClient::new(MockRpc::new());
+        let start = instruction::start_game(&operator.pubkey(), "g1");
+
+        let signature = client.send(&[start], &[&operator]).unwrap();
+        let sent = client.rpc().sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].signatures[0], signature);
+        assert_eq!(sent[0].message.account_keys[0], operator.pubkey());
+        sent[0].verify().unwrap();
+    }
+
+    #[test]
+    fn test_events_for_signature() {
+        let client = LotteryClient::new(MockRpc::new());
+        let signature = Signature::new_unique();
+        assert!(matches!(
+            client.events(&signature),
+            Err(ClientError::TransactionNotFound(_))
+        ));
+
+        let event = GameStartedEvent {
+            game_id: "g1".to_string(),
+            player_count: 2,
+            timestamp: 9,
+        };
+        let program = telegram_lottery::ID;
+        client.rpc().set_logs(
+            signature,
+            vec![
+                format!("Program {} invoke [1]", program),
+                format!("Program data: {}", STANDARD.encode(event.data())),
+                format!("Program {} success", program),
+            ],
+        );
+        let events = client.events(&signature).unwrap();
+        assert!(matches!(&events[..], [LotteryEvent::GameStarted(e)] if e.player_count == 2));
+    }
+}
@@ -0,0 +1,340 @@
+//! Next legal operator instruction for a game
+//!
+//! A game advances through `start_game`, then per round `request_orao_vrf`
+//! → fulfillment → `fulfill_orao_vrf` → `process_elimination`, and finally
+//! `complete_game`. [`GameSnapshot::next_step`] reads that position from
+//! chain state alone, so callers can resume after a restart and never submit
+//! a step twice.
+
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use telegram_lottery::state::{GameState, GameStatus, PlayerList, RandomnessProvider, VrfResult};
+
+use crate::instruction;
+
+/// Chain state that decides a game's next step
+#[derive(Clone, Debug)]
+pub struct GameSnapshot {
+    pub game: GameState,
+    pub players: PlayerList,
+    /// Result for `game.current_round`, if one has been recorded
+    pub vrf_result: Option<VrfResult>,
+    /// Whether ORAO has fulfilled the pending round's request
+    pub randomness_fulfilled: bool,
+    /// Whether `LotteryConfig::PAUSE_PLAY` is set
+    pub play_paused: bool,
+}
+
+/// What the operator should do next
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrankStep {
+    StartGame,
+    RequestRandomness {
+        round: u8,
+    },
+    FulfillRandomness {
+        round: u8,
+    },
+    ProcessElimination {
+        round: u8,
+    },
+    CompleteGame,
+    /// Nothing to submit until something outside the operator happens
+    Wait(&'static str),
+    /// The game needs no further operator instructions
+    Done,
+}
+
+impl GameSnapshot {
+    /// Players not yet eliminated
+    pub fn active_players(&self) -> usize {
+        self.players
+            .players
+            .iter()
+            .filter(|player| player.eliminated_round.is_none())
+            .count()
+    }
+
+    pub fn next_step(&self) -> CrankStep {
+        let game = &self.game;
+        match game.state {
+            GameStatus::Created | GameStatus::Joining => return CrankStep::Wait("players joining"),
+            GameStatus::NumberSelection => {
+                let all_selected = self
+                    .players
+                    .players
+                    .iter()
+                    .filter(|player| player.eliminated_round.is_none())
+                    .all(|player| player.selected_number.is_some());
+                return if all_selected {
+                    CrankStep::StartGame
+                } else {
+                    CrankStep::Wait("players selecting numbers")
+                };
+            }
+            GameStatus::Playing => {}
+            GameStatus::Distributing | GameStatus::Completed | GameStatus::Cancelled => {
+                return CrankStep::Done
+            }
+        }
+
+        if self.play_paused {
+            return CrankStep::Wait("play paused");
+        }
+
+        if game.vrf_request_pending {
+            return match game.randomness_provider {
+                RandomnessProvider::Orao if self.randomness_fulfilled => {
+                    CrankStep::FulfillRandomness {
+                        round: game.pending_round,
+                    }
+                }
+                RandomnessProvider::Orao => CrankStep::Wait("awaiting ORAO fulfillment"),
+                _ => CrankStep::Wait("awaiting reveal"),
+            };
+        }
+
+        let unprocessed = self
+            .vrf_result
+            .as_ref()
+            .is_some_and(|result| result.round == game.current_round && !result.used);
+        if game.current_round > 0 && unprocessed {
+            return CrankStep::ProcessElimination {
+                round: game.current_round,
+            };
+        }
+
+        if self.active_players() <= game.winner_count as usize {
+            return CrankStep::CompleteGame;
+        }
+
+        match game.randomness_provider {
+            RandomnessProvider::Orao => match game.current_round.checked_add(1) {
+                Some(round) => CrankStep::RequestRandomness { round },
+                None => CrankStep::Wait("round limit reached"),
+            },
+            _ => CrankStep::Wait("awaiting oracle"),
+        }
+    }
+}
+
+impl CrankStep {
+    /// Whether there is an instruction to submit
+    pub fn is_actionable(&self) -> bool {
+        !matches!(self, CrankStep::Wait(_) | CrankStep::Done)
+    }
+
+    /// Instruction for this step, signed by an operator; `orao_treasury` is
+    /// only read for randomness requests
+    pub fn instruction(
+        &self,
+        operator: &Pubkey,
+        game: &GameState,
+        orao_treasury: &Pubkey,
+    ) -> Option<Instruction> {
+        let game_id = game.game_id.as_str();
+        match *self {
+            CrankStep::StartGame => Some(instruction::start_game(operator, game_id)),
+            CrankStep::RequestRandomness { round } => Some(instruction::request_orao_vrf(
+                operator,
+                game_id,
+                round,
+                orao_treasury,
+            )),
+            CrankStep::FulfillRandomness { round } => {
+                Some(instruction::fulfill_orao_vrf(operator, game_id, round))
+            }
+            CrankStep::ProcessElimination { round } => {
+                Some(instruction::process_elimination(operator, game_id, round))
+            }
+            CrankStep::CompleteGame => Some(instruction::complete_game(
+                operator,
+                game_id,
+                &game.token_mint,
+            )),
+            CrankStep::Wait(_) | CrankStep::Done => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CrankStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrankStep::StartGame => write!(f, "start game"),
+            CrankStep::RequestRandomness { round } => {
+                write!(f, "request randomness for round {}", round)
+            }
+            CrankStep::FulfillRandomness { round } => {
+                write!(f, "fulfill randomness for round {}", round)
+            }
+            CrankStep::ProcessElimination { round } => {
+                write!(f, "process elimination for round {}", round)
+            }
+            CrankStep::CompleteGame => write!(f, "complete game"),
+            CrankStep::Wait(reason) => write!(f, "wait: {}", reason),
+            CrankStep::Done => write!(f, "done"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use telegram_lottery::state::{NumberRange, Player};
+
+    pub(crate) fn game(state: GameStatus) -> GameState {
+        GameState {
+            game_id: "g1".to_string(),
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            entry_fee: 1_000,
+            max_players: 10,
+            winner_count: 1,
+            state,
+            prize_pool: 3_000,
+            treasury_fee: 300,
+            fee_bps: 1_000,
+            number_range: NumberRange { min: 1, max: 10 },
+            created_at: 0,
+            started_at: None,
+            completed_at: None,
+            payment_deadline: 600,
+            current_round: 0,
+            drawn_numbers: vec![],
+            token_mint: Pubkey::new_unique(),
+            escrow_account: Pubkey::new_unique(),
+            vrf_oracle: Pubkey::new_unique(),
+            vrf_request_pending: false,
+            pending_round: 0,
+            randomness_provider: RandomnessProvider::Orao,
+            randomness_request: None,
+            bump: 255,
+        }
+    }
+
+    pub(crate) fn players(numbers: &[Option<u8>]) -> PlayerList {
+        PlayerList {
+            game_id: "g1".to_string(),
+            players: numbers
+                .iter()
+                .map(|number| Player {
+                    wallet: Pubkey::new_unique(),
+                    telegram_id: "tg".to_string(),
+                    selected_number: *number,
+                    eliminated_round: None,
+                    is_winner: false,
+                    prize_claimed: false,
+                    prize_amount: 0,
+                    joined_at: 0,
+                })
+                .collect(),
+            bump: 255,
+        }
+    }
+
+    fn snapshot(game: GameState, players: PlayerList) -> GameSnapshot {
+        GameSnapshot {
+            game,
+            players,
+            vrf_result: None,
+            randomness_fulfilled: false,
+            play_paused: false,
+        }
+    }
+
+    fn vrf_result(round: u8, used: bool) -> VrfResult {
+        VrfResult {
+            game_id: "g1".to_string(),
+            round,
+            random_value: [0u8; 32],
+            proof: vec![],
+            drawn_number: 3,
+            used,
+            timestamp: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_start_waits_for_numbers() {
+        let mut s = snapshot(game(GameStatus::NumberSelection), players(&[Some(1), None]));
+        assert_eq!(s.next_step(), CrankStep::Wait("players selecting numbers"));
+        s.players.players[1].selected_number = Some(2);
+        assert_eq!(s.next_step(), CrankStep::StartGame);
+    }
+
+    #[test]
+    fn test_round_sequence() {
+        let mut s = snapshot(
+            game(GameStatus::Playing),
+            players(&[Some(1), Some(2), Some(3)]),
+        );
+        assert_eq!(s.next_step(), CrankStep::RequestRandomness { round: 1 });
+
+        s.game.vrf_request_pending = true;
+        s.game.pending_round = 1;
+        assert_eq!(s.next_step(), CrankStep::Wait("awaiting ORAO fulfillment"));
+        s.randomness_fulfilled = true;
+        assert_eq!(s.next_step(), CrankStep::FulfillRandomness { round: 1 });
+
+        s.game.vrf_request_pending = false;
+        s.game.pending_round = 0;
+        s.game.current_round = 1;
+        s.randomness_fulfilled = false;
+        s.vrf_result = Some(vrf_result(1, false));
+        assert_eq!(s.next_step(), CrankStep::ProcessElimination { round: 1 });
+
+        s.vrf_result = Some(vrf_result(1, true));
+        assert_eq!(s.next_step(), CrankStep::RequestRandomness { round: 2 });
+
+        for player in &mut s.players.players[1..] {
+            player.eliminated_round = Some(1);
+        }
+        assert_eq!(s.next_step(), CrankStep::CompleteGame);
+    }
+
+    #[test]
+    fn test_pause_and_terminal_states() {
+        let mut s = snapshot(game(GameStatus::Playing), players(&[Some(1), Some(2)]));
+        s.play_paused = true;
+        assert_eq!(s.next_step(), CrankStep::Wait("play paused"));
+
+        for state in [
+            GameStatus::Distributing,
+            GameStatus::Completed,
+            GameStatus::Cancelled,
+        ] {
+            s.game.state = state;
+            assert_eq!(s.next_step(), CrankStep::Done);
+        }
+    }
+
+    #[test]
+    fn test_oracle_games_are_not_requested() {
+        let mut s = snapshot(game(GameStatus::Playing), players(&[Some(1), Some(2)]));
+        s.game.randomness_provider = RandomnessProvider::SignedOracle;
+        assert!(!s.next_step().is_actionable());
+    }
+
+    #[test]
+    fn test_instructions_follow_step() {
+        let s = snapshot(game(GameStatus::Playing), players(&[Some(1), Some(2)]));
+        let operator = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        assert_eq!(
+            CrankStep::RequestRandomness { round: 2 }.instruction(&operator, &s.game, &treasury),
+            Some(instruction::request_orao_vrf(&operator, "g1", 2, &treasury))
+        );
+        assert_eq!(
+            CrankStep::CompleteGame.instruction(&operator, &s.game, &treasury),
+            Some(instruction::complete_game(
+                &operator,
+                "g1",
+                &s.game.token_mint
+            ))
+        );
+        assert!(CrankStep::Wait("x")
+            .instruction(&operator, &s.game, &treasury)
+            .is_none());
+    }
+}
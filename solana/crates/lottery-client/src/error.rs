@@ -0,0 +1,27 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Errors returned by the lottery client
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(String),
+
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("Account {address} could not be decoded: {reason}")]
+    Decode { address: Pubkey, reason: String },
+
+    #[error("Transaction {0} not found")]
+    TransactionNotFound(Signature),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(error.to_string())
+    }
+}
@@ -0,0 +1,247 @@
+//! Decoding of lottery `#[event]`s from transaction logs
+//!
+//! Anchor's `emit!` writes each event as a `Program data: <base64>` log line
+//! holding the event discriminator and its borsh encoding. Only lines logged
+//! while the lottery program is the innermost invocation are decoded, so a
+//! CPI'd program cannot spoof lottery events.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use telegram_lottery::instructions::{VrfFulfilledEvent, VrfRequestedEvent};
+use telegram_lottery::state::*;
+
+const DATA_PREFIX: &str = "Program data: ";
+
+macro_rules! lottery_events {
+    ($($variant:ident => $event:ident),* $(,)?) => {
+        /// Every event emitted by the lottery program
+        #[derive(Clone, Debug)]
+        pub enum LotteryEvent {
+            $($variant($event),)*
+        }
+
+        impl LotteryEvent {
+            /// Struct names of every decoded event
+            pub const NAMES: &[&str] = &[$(stringify!($event),)*];
+
+            /// Decode discriminator-prefixed event data
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                if data.len() < 8 {
+                    return None;
+                }
+                let (discriminator, mut body) = data.split_at(8);
+                $(
+                    if discriminator == $event::DISCRIMINATOR {
+                        return $event::deserialize(&mut body).ok().map(LotteryEvent::$variant);
+                    }
+                )*
+                None
+            }
+
+            /// Event struct name as declared in the program
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(LotteryEvent::$variant(_) => stringify!($event),)*
+                }
+            }
+        }
+    };
+}
+
+lottery_events! {
+    GameCreated => GameCreatedEvent,
+    PlayerJoined => PlayerJoinedEvent,
+    GameCompleted => GameCompletedEvent,
+    PrizeClaimed => PrizeClaimedEvent,
+    GameCancelled => GameCancelledEvent,
+    TreasuryInitialized => TreasuryInitializedEvent,
+    NumberSelected => NumberSelectedEvent,
+    AllNumbersSelected => AllNumbersSelectedEvent,
+    GameStarted => GameStartedEvent,
+    VrfSubmitted => VrfSubmittedEvent,
+    VrfRequested => VrfRequestedEvent,
+    VrfFulfilled => VrfFulfilledEvent,
+    RoundRandomnessCommitted => RoundRandomnessCommittedEvent,
+    RoundRandomnessRevealed => RoundRandomnessRevealedEvent,
+    EliminationProcessed => EliminationProcessedEvent,
+    GameReadyToComplete => GameReadyToCompleteEvent,
+    AllPrizesClaimed => AllPrizesClaimedEvent,
+    RefundProcessed => RefundProcessedEvent,
+    AllRefundsProcessed => AllRefundsProcessedEvent,
+    WithdrawalProposed => WithdrawalProposedEvent,
+    WithdrawalApproved => WithdrawalApprovedEvent,
+    WithdrawalCancelled => WithdrawalCancelledEvent,
+    WithdrawalConfigUpdated => WithdrawalConfigUpdatedEvent,
+    TreasuryWithdrawal => TreasuryWithdrawalEvent,
+    RoleGranted => RoleGrantedEvent,
+    RoleRevoked => RoleRevokedEvent,
+    FeePercentageUpdated => FeePercentageUpdatedEvent,
+    ConfigUpdated => ConfigUpdatedEvent,
+    PauseFlagsUpdated => PauseFlagsUpdatedEvent,
+    OracleRegistered => OracleRegisteredEvent,
+    OracleDeregistered => OracleDeregisteredEvent,
+}
+
+/// Decode every lottery event in a transaction's log messages, in order
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<LotteryEvent> {
+    let program_id = telegram_lottery::ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs.iter().map(AsRef::as_ref) {
+        if let Some(data) = line.strip_prefix(DATA_PREFIX) {
+            if stack.last() == Some(&program_id.as_str()) {
+                if let Some(event) = STANDARD.decode(data).ok().and_then(|d| LotteryEvent::decode(&d)) {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+        if words.next() != Some("Program") {
+            continue;
+        }
+        match (words.next(), words.next()) {
+            (Some(id), Some("invoke")) => stack.push(id),
+            (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::Event;
+    use std::fs;
+    use std::path::Path;
+
+    fn data_line(event: &impl Event) -> String {
+        format!("{}{}", DATA_PREFIX, STANDARD.encode(event.data()))
+    }
+
+    fn started(game_id: &str) -> GameStartedEvent {
+        GameStartedEvent {
+            game_id: game_id.to_string(),
+            player_count: 4,
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let event = RoleGrantedEvent {
+            role: Role::Operator,
+            member: Pubkey::new_unique(),
+            granted_by: Pubkey::new_unique(),
+            timestamp: 5,
+        };
+        match LotteryEvent::decode(&event.data()).unwrap() {
+            LotteryEvent::RoleGranted(decoded) => {
+                assert_eq!(decoded.member, event.member);
+                assert_eq!(decoded.role, Role::Operator);
+            }
+            other => panic!("unexpected {}", other.name()),
+        }
+        assert!(LotteryEvent::decode(&[0u8; 4]).is_none());
+        assert!(LotteryEvent::decode(&[0u8; 16]).is_none());
+    }
+
+    #[test]
+    fn test_parse_logs_tracks_invocations() {
+        let lottery = telegram_lottery::ID.to_string();
+        let other = Pubkey::new_unique().to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", lottery),
+            "Program log: Instruction: StartGame".to_string(),
+            format!("Program {} invoke [2]", other),
+            // Emitted by the inner program; must be ignored
+            data_line(&started("spoofed")),
+            format!("Program {} success", other),
+            data_line(&started("real")),
+            "Program data: not-base64!".to_string(),
+            format!("Program {} consumed 5000 of 200000 compute units", lottery),
+            format!("Program {} success", lottery),
+            data_line(&started("outside")),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            LotteryEvent::GameStarted(event) => assert_eq!(event.game_id, "real"),
+            other => panic!("unexpected {}", other.name()),
+        }
+    }
+
+    #[test]
+    fn test_events_from_failed_invocation_unwind() {
+        let lottery = telegram_lottery::ID.to_string();
+        let other = Pubkey::new_unique().to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", other),
+            format!("Program {} failed: custom program error: 0x1", other),
+            data_line(&started("after-failure")),
+        ];
+        assert!(parse_logs(&logs).is_empty());
+
+        let logs = vec![
+            format!("Program {} invoke [1]", lottery),
+            data_line(&started("g1")),
+            format!("Program {} success", lottery),
+            format!("Program {} invoke [1]", lottery),
+            data_line(&started("g2")),
+            format!("Program {} success", lottery),
+        ];
+        assert_eq!(parse_logs(&logs).len(), 2);
+    }
+
+    /// Names of the structs marked `#[event]` in every source file under `dir`
+    fn program_events(dir: &Path, names: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                program_events(&path, names);
+                continue;
+            }
+            if path.extension() != Some("rs".as_ref()) {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            let mut lines = source.lines().map(str::trim);
+            while let Some(line) = lines.next() {
+                if line != "#[event]" {
+                    continue;
+                }
+                let declaration = lines.find(|line| line.starts_with("pub struct ")).unwrap();
+                let name = declaration["pub struct ".len()..]
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .next()
+                    .unwrap();
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_program_event_is_decoded() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../programs/lottery/src");
+        let mut names = Vec::new();
+        program_events(&src, &mut names);
+        assert!(names.iter().any(|name| name == "GameCreatedEvent"));
+
+        for name in &names {
+            assert!(
+                LotteryEvent::NAMES.contains(&name.as_str()),
+                "{} is emitted by the program but missing from lottery_events!",
+                name
+            );
+        }
+    }
+}
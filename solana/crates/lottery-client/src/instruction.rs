@@ -0,0 +1,622 @@
+//! Typed builders for every `telegram_lottery` instruction
+//!
+//! Builders derive all program accounts from their seeds; callers pass only
+//! signers, external accounts (token accounts, mints, oracles) and arguments.
+//! Account lists are encoded through the program's own `accounts::*` structs,
+//! so they stay in sync with the program.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token;
+use anchor_spl::token::spl_token;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{system_program, sysvar};
+use telegram_lottery::state::{RandomnessProvider, Role};
+use telegram_lottery::{accounts, instruction as ix};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: telegram_lottery::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Arguments for [`create_game`]
+#[derive(Clone, Debug)]
+pub struct CreateGameArgs {
+    pub game_id: String,
+    /// Entry fee in base units of `token_mint`
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub winner_count: u8,
+    pub payment_deadline_minutes: u16,
+    pub randomness_provider: RandomnessProvider,
+    pub token_mint: Pubkey,
+    /// Oracle for signed-oracle games; must be in the approved registry
+    pub vrf_oracle: Pubkey,
+}
+
+// ----- admin -----
+
+/// Create the treasury and its token account for `token_mint`
+pub fn initialize(
+    authority: &Pubkey,
+    treasury_authority: &Pubkey,
+    token_mint: &Pubkey,
+    fee_percentage: u8,
+) -> Instruction {
+    build(
+        accounts::Initialize {
+            authority: *authority,
+            treasury_state: pda::treasury(),
+            treasury_token_account: pda::treasury_token_account(token_mint),
+            token_mint: *token_mint,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::Initialize {
+            treasury_authority: *treasury_authority,
+            fee_percentage,
+        },
+    )
+}
+
+pub fn initialize_roles(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeRoles {
+            authority: *authority,
+            treasury_state: pda::treasury(),
+            role_registry: pda::roles(),
+            system_program: system_program::ID,
+        },
+        ix::InitializeRoles {},
+    )
+}
+
+pub fn grant_role(admin: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+    build(
+        accounts::GrantRole {
+            admin: *admin,
+            role_registry: pda::roles(),
+            clock: sysvar::clock::ID,
+        },
+        ix::GrantRole { role, member },
+    )
+}
+
+pub fn revoke_role(admin: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+    build(
+        accounts::RevokeRole {
+            admin: *admin,
+            role_registry: pda::roles(),
+            clock: sysvar::clock::ID,
+        },
+        ix::RevokeRole { role, member },
+    )
+}
+
+pub fn update_fee_percentage(fee_manager: &Pubkey, fee_percentage: u8) -> Instruction {
+    build(
+        accounts::UpdateFeePercentage {
+            fee_manager: *fee_manager,
+            role_registry: pda::roles(),
+            treasury_state: pda::treasury(),
+            clock: sysvar::clock::ID,
+        },
+        ix::UpdateFeePercentage { fee_percentage },
+    )
+}
+
+pub fn initialize_config(
+    admin: &Pubkey,
+    allowed_mints: Vec<Pubkey>,
+    max_entry_fee: u64,
+) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            admin: *admin,
+            role_registry: pda::roles(),
+            config: pda::config(),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::InitializeConfig {
+            allowed_mints,
+            max_entry_fee,
+        },
+    )
+}
+
+/// Update the config; `None` leaves a field unchanged
+pub fn update_config(
+    admin: &Pubkey,
+    allowed_mints: Option<Vec<Pubkey>>,
+    max_entry_fee: Option<u64>,
+) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: pda::config(),
+            clock: sysvar::clock::ID,
+        },
+        ix::UpdateConfig {
+            allowed_mints,
+            max_entry_fee,
+        },
+    )
+}
+
+pub fn set_pause_flags(pauser: &Pubkey, pause_flags: u8) -> Instruction {
+    build(
+        accounts::SetPauseFlags {
+            pauser: *pauser,
+            role_registry: pda::roles(),
+            config: pda::config(),
+            clock: sysvar::clock::ID,
+        },
+        ix::SetPauseFlags { pause_flags },
+    )
+}
+
+pub fn initialize_oracle_registry(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeOracleRegistry {
+            admin: *admin,
+            config: pda::config(),
+            oracle_registry: pda::oracles(),
+            system_program: system_program::ID,
+        },
+        ix::InitializeOracleRegistry {},
+    )
+}
+
+pub fn register_oracle(admin: &Pubkey, oracle: Pubkey) -> Instruction {
+    build(
+        accounts::RegisterOracle {
+            admin: *admin,
+            config: pda::config(),
+            oracle_registry: pda::oracles(),
+            clock: sysvar::clock::ID,
+        },
+        ix::RegisterOracle { oracle },
+    )
+}
+
+pub fn deregister_oracle(admin: &Pubkey, oracle: Pubkey) -> Instruction {
+    build(
+        accounts::DeregisterOracle {
+            admin: *admin,
+            config: pda::config(),
+            oracle_registry: pda::oracles(),
+            clock: sysvar::clock::ID,
+        },
+        ix::DeregisterOracle { oracle },
+    )
+}
+
+// ----- game -----
+
+pub fn create_game(authority: &Pubkey, args: &CreateGameArgs) -> Instruction {
+    build(
+        accounts::CreateGame {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(&args.game_id),
+            config: pda::config(),
+            player_list: pda::player_list(&args.game_id),
+            treasury_state: pda::treasury(),
+            token_mint: args.token_mint,
+            escrow_account: pda::escrow(&args.game_id),
+            oracle_registry: pda::oracles(),
+            vrf_oracle: args.vrf_oracle,
+            system_program: system_program::ID,
+            token_program: spl_token::ID,
+            rent: sysvar::rent::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::CreateGame {
+            game_id: args.game_id.clone(),
+            entry_fee: args.entry_fee,
+            max_players: args.max_players,
+            winner_count: args.winner_count,
+            payment_deadline_minutes: args.payment_deadline_minutes,
+            randomness_provider: args.randomness_provider,
+        },
+    )
+}
+
+/// Join a game, paying the entry fee from `player_token_account`
+pub fn join_game(
+    player: &Pubkey,
+    player_token_account: &Pubkey,
+    game_id: &str,
+    telegram_id: &str,
+) -> Instruction {
+    build(
+        accounts::JoinGame {
+            player: *player,
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            player_list: pda::player_list(game_id),
+            player_token_account: *player_token_account,
+            escrow_account: pda::escrow(game_id),
+            token_program: spl_token::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::JoinGame {
+            game_id: game_id.to_string(),
+            telegram_id: telegram_id.to_string(),
+        },
+    )
+}
+
+pub fn select_number(player: &Pubkey, game_id: &str, number: u8) -> Instruction {
+    build(
+        accounts::SelectNumber {
+            player: *player,
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            player_list: pda::player_list(game_id),
+            clock: sysvar::clock::ID,
+        },
+        ix::SelectNumber {
+            game_id: game_id.to_string(),
+            number,
+        },
+    )
+}
+
+pub fn start_game(authority: &Pubkey, game_id: &str) -> Instruction {
+    build(
+        accounts::StartGame {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            player_list: pda::player_list(game_id),
+            clock: sysvar::clock::ID,
+        },
+        ix::StartGame {
+            game_id: game_id.to_string(),
+        },
+    )
+}
+
+/// Request ORAO randomness for `round`; `orao_treasury` comes from the ORAO
+/// network state (see [`crate::LotteryClient::orao_treasury`])
+pub fn request_orao_vrf(
+    payer: &Pubkey,
+    game_id: &str,
+    round: u8,
+    orao_treasury: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RequestOraoVrf {
+            player: *payer,
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            network_state: randomness::orao::network_state_address(),
+            treasury: *orao_treasury,
+            randomness: pda::orao_randomness(game_id, round),
+            orao_vrf: randomness::orao::ID,
+            system_program: system_program::ID,
+        },
+        ix::RequestOraoVrf {
+            game_id: game_id.to_string(),
+            round,
+        },
+    )
+}
+
+pub fn fulfill_orao_vrf(authority: &Pubkey, game_id: &str, round: u8) -> Instruction {
+    build(
+        accounts::FulfillOraoVrf {
+            authority: *authority,
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            randomness: pda::orao_randomness(game_id, round),
+            vrf_result: pda::vrf_result(game_id, round),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::FulfillOraoVrf {
+            game_id: game_id.to_string(),
+            round,
+        },
+    )
+}
+
+/// Record signed-oracle randomness for `round`
+///
+/// Must be preceded in the same transaction by an Ed25519 program
+/// instruction in which `oracle` signs [`pda::round_seed`].
+pub fn submit_vrf(oracle: &Pubkey, game_id: &str, round: u8) -> Instruction {
+    build(
+        accounts::SubmitVrf {
+            vrf_oracle: *oracle,
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            oracle_registry: pda::oracles(),
+            vrf_result: pda::vrf_result(game_id, round),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::SubmitVrf {
+            game_id: game_id.to_string(),
+            round,
+        },
+    )
+}
+
+/// Commit to `randomness::slot_hash::commitment(secret)` for a slot-hash round
+pub fn commit_round_randomness(
+    authority: &Pubkey,
+    game_id: &str,
+    round: u8,
+    commitment: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::CommitRoundRandomness {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            clock: sysvar::clock::ID,
+        },
+        ix::CommitRoundRandomness {
+            game_id: game_id.to_string(),
+            round,
+            commitment,
+        },
+    )
+}
+
+pub fn reveal_round_randomness(
+    authority: &Pubkey,
+    game_id: &str,
+    round: u8,
+    secret: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::RevealRoundRandomness {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            vrf_result: pda::vrf_result(game_id, round),
+            slot_hashes: sysvar::slot_hashes::ID,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::RevealRoundRandomness {
+            game_id: game_id.to_string(),
+            round,
+            secret,
+        },
+    )
+}
+
+pub fn process_elimination(authority: &Pubkey, game_id: &str, round: u8) -> Instruction {
+    build(
+        accounts::ProcessElimination {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            player_list: pda::player_list(game_id),
+            vrf_result: pda::vrf_result(game_id, round),
+            clock: sysvar::clock::ID,
+        },
+        ix::ProcessElimination {
+            game_id: game_id.to_string(),
+            round,
+        },
+    )
+}
+
+/// Settle a game; the treasury fee goes to the treasury's `token_mint` account
+pub fn complete_game(authority: &Pubkey, game_id: &str, token_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::CompleteGame {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(game_id),
+            config: pda::config(),
+            player_list: pda::player_list(game_id),
+            treasury_state: pda::treasury(),
+            escrow_account: pda::escrow(game_id),
+            treasury_token_account: pda::treasury_token_account(token_mint),
+            token_program: spl_token::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::CompleteGame {
+            game_id: game_id.to_string(),
+        },
+    )
+}
+
+pub fn claim_prize(winner: &Pubkey, winner_token_account: &Pubkey, game_id: &str) -> Instruction {
+    build(
+        accounts::ClaimPrize {
+            winner: *winner,
+            game_state: pda::game(game_id),
+            player_list: pda::player_list(game_id),
+            escrow_account: pda::escrow(game_id),
+            winner_token_account: *winner_token_account,
+            token_program: spl_token::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::ClaimPrize {
+            game_id: game_id.to_string(),
+        },
+    )
+}
+
+pub fn request_refund(
+    player: &Pubkey,
+    player_token_account: &Pubkey,
+    game_id: &str,
+) -> Instruction {
+    build(
+        accounts::RequestRefund {
+            player: *player,
+            game_state: pda::game(game_id),
+            player_list: pda::player_list(game_id),
+            escrow_account: pda::escrow(game_id),
+            player_token_account: *player_token_account,
+            token_program: spl_token::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::RequestRefund {
+            game_id: game_id.to_string(),
+        },
+    )
+}
+
+pub fn cancel_game(authority: &Pubkey, game_id: &str, reason: &str) -> Instruction {
+    build(
+        accounts::CancelGame {
+            authority: *authority,
+            role_registry: pda::roles(),
+            game_state: pda::game(game_id),
+            player_list: pda::player_list(game_id),
+            clock: sysvar::clock::ID,
+        },
+        ix::CancelGame {
+            game_id: game_id.to_string(),
+            reason: reason.to_string(),
+        },
+    )
+}
+
+// ----- treasury -----
+
+pub fn configure_withdrawals(
+    admin: &Pubkey,
+    approval_threshold: u8,
+    withdrawal_delay: i64,
+) -> Instruction {
+    build(
+        accounts::ConfigureWithdrawals {
+            admin: *admin,
+            role_registry: pda::roles(),
+            treasury_state: pda::treasury(),
+            clock: sysvar::clock::ID,
+        },
+        ix::ConfigureWithdrawals {
+            approval_threshold,
+            withdrawal_delay,
+        },
+    )
+}
+
+/// Propose a withdrawal; `proposal_id` must be the treasury's `proposal_count`
+pub fn propose_withdrawal(
+    proposer: &Pubkey,
+    proposal_id: u64,
+    amount: u64,
+    destination_token_account: &Pubkey,
+    token_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ProposeWithdrawal {
+            proposer: *proposer,
+            role_registry: pda::roles(),
+            treasury_state: pda::treasury(),
+            proposal: pda::withdrawal(proposal_id),
+            destination_token_account: *destination_token_account,
+            treasury_token_account: pda::treasury_token_account(token_mint),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::ProposeWithdrawal { amount },
+    )
+}
+
+pub fn approve_withdrawal(approver: &Pubkey, proposal_id: u64) -> Instruction {
+    build(
+        accounts::ApproveWithdrawal {
+            approver: *approver,
+            role_registry: pda::roles(),
+            proposal: pda::withdrawal(proposal_id),
+            clock: sysvar::clock::ID,
+        },
+        ix::ApproveWithdrawal { proposal_id },
+    )
+}
+
+pub fn cancel_withdrawal(authority: &Pubkey, proposal_id: u64) -> Instruction {
+    build(
+        accounts::CancelWithdrawal {
+            authority: *authority,
+            role_registry: pda::roles(),
+            proposal: pda::withdrawal(proposal_id),
+            clock: sysvar::clock::ID,
+        },
+        ix::CancelWithdrawal { proposal_id },
+    )
+}
+
+pub fn withdraw_treasury(
+    authority: &Pubkey,
+    proposal_id: u64,
+    destination_token_account: &Pubkey,
+    token_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawTreasury {
+            authority: *authority,
+            role_registry: pda::roles(),
+            treasury_state: pda::treasury(),
+            proposal: pda::withdrawal(proposal_id),
+            treasury_token_account: pda::treasury_token_account(token_mint),
+            destination_token_account: *destination_token_account,
+            token_program: spl_token::ID,
+            clock: sysvar::clock::ID,
+        },
+        ix::WithdrawTreasury { proposal_id },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn test_data_starts_with_discriminator() {
+        let instruction = start_game(&Pubkey::new_unique(), "g1");
+        assert_eq!(instruction.program_id, telegram_lottery::ID);
+        assert_eq!(instruction.data[..8], ix::StartGame::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn test_join_game_accounts() {
+        let player = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let instruction = join_game(&player, &token_account, "g1", "tg-1");
+
+        let keys: Vec<Pubkey> = instruction.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys[0], player);
+        assert!(instruction.accounts[0].is_signer);
+        assert!(keys.contains(&pda::game("g1")));
+        assert!(keys.contains(&pda::escrow("g1")));
+        assert!(keys.contains(&token_account));
+    }
+
+    #[test]
+    fn test_round_accounts_follow_round() {
+        let authority = Pubkey::new_unique();
+        let instruction = fulfill_orao_vrf(&authority, "g1", 4);
+        let keys: Vec<Pubkey> = instruction.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert!(keys.contains(&pda::vrf_result("g1", 4)));
+        assert!(keys.contains(&pda::orao_randomness("g1", 4)));
+        assert!(!keys.contains(&pda::vrf_result("g1", 3)));
+    }
+}
@@ -0,0 +1,27 @@
+//! Rust client for the `telegram_lottery` program
+//!
+//! - [`pda`]: addresses of every program-derived account
+//! - [`instruction`]: typed builders for every program instruction
+//! - [`LotteryClient`]: account fetching and transaction submission over a
+//!   [`LotteryRpc`] backend
+//! - [`events`]: decoding of `#[event]`s from transaction logs
+//! - [`crank`]: the next operator instruction a game needs
+//!
+//! [`LotteryRpc`] is implemented for `solana_client`'s blocking `RpcClient`;
+//! enable the `mock` feature for an in-memory implementation to test
+//! services against.
+
+pub mod client;
+pub mod crank;
+pub mod error;
+pub mod events;
+pub mod instruction;
+pub mod pda;
+pub mod rpc;
+
+pub use client::LotteryClient;
+pub use crank::{CrankStep, GameSnapshot};
+pub use error::{ClientError, Result};
+pub use events::LotteryEvent;
+pub use rpc::LotteryRpc;
+pub use telegram_lottery::{state, ID};
@@ -0,0 +1,113 @@
+//! Program-derived addresses used by the lottery
+//!
+//! Seeds mirror the `#[account(seeds = ...)]` constraints in the program.
+
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_sdk::pubkey::Pubkey;
+use telegram_lottery::state::GameState;
+
+pub const GAME_SEED: &[u8] = b"game";
+pub const PLAYERS_SEED: &[u8] = b"players";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const VRF_SEED: &[u8] = b"vrf";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const ROLES_SEED: &[u8] = b"roles";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const ORACLES_SEED: &[u8] = b"oracles";
+pub const WITHDRAWAL_SEED: &[u8] = b"withdrawal";
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &telegram_lottery::ID).0
+}
+
+/// Game state account
+pub fn game(game_id: &str) -> Pubkey {
+    find(&[GAME_SEED, game_id.as_bytes()])
+}
+
+/// Player list account
+pub fn player_list(game_id: &str) -> Pubkey {
+    find(&[PLAYERS_SEED, game_id.as_bytes()])
+}
+
+/// Escrow token account holding a game's entry fees
+pub fn escrow(game_id: &str) -> Pubkey {
+    find(&[ESCROW_SEED, game_id.as_bytes()])
+}
+
+/// Randomness result for a game round
+pub fn vrf_result(game_id: &str, round: u8) -> Pubkey {
+    find(&[VRF_SEED, game_id.as_bytes(), &[round]])
+}
+
+/// Treasury state account
+pub fn treasury() -> Pubkey {
+    find(&[TREASURY_SEED])
+}
+
+/// Treasury's associated token account for `mint`
+pub fn treasury_token_account(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&treasury(), mint)
+}
+
+/// Role registry
+pub fn roles() -> Pubkey {
+    find(&[ROLES_SEED])
+}
+
+/// Lottery config
+pub fn config() -> Pubkey {
+    find(&[CONFIG_SEED])
+}
+
+/// Approved oracle registry
+pub fn oracles() -> Pubkey {
+    find(&[ORACLES_SEED])
+}
+
+/// Treasury withdrawal proposal
+pub fn withdrawal(proposal_id: u64) -> Pubkey {
+    find(&[WITHDRAWAL_SEED, proposal_id.to_le_bytes().as_ref()])
+}
+
+/// Randomness seed a game round's request is bound to
+pub fn round_seed(game_id: &str, round: u8) -> [u8; 32] {
+    GameState::round_seed(&game(game_id), round)
+}
+
+/// ORAO randomness account for a game round
+pub fn orao_randomness(game_id: &str, round: u8) -> Pubkey {
+    randomness::orao::randomness_account_address(&round_seed(game_id, round))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_addresses_are_distinct() {
+        let addresses = [
+            game("g1"),
+            player_list("g1"),
+            escrow("g1"),
+            vrf_result("g1", 1),
+            vrf_result("g1", 2),
+            game("g2"),
+        ];
+        for (i, a) in addresses.iter().enumerate() {
+            for b in &addresses[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_addresses_match_program_seeds() {
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"vrf", b"g1", &[3]], &telegram_lottery::ID);
+        assert_eq!(vrf_result("g1", 3), expected);
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"withdrawal", &7u64.to_le_bytes()], &telegram_lottery::ID);
+        assert_eq!(withdrawal(7), expected);
+    }
+}
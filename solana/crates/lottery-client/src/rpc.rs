@@ -0,0 +1,206 @@
+//! RPC backend abstraction
+//!
+//! The client only needs a handful of calls, so it talks to the cluster
+//! through [`LotteryRpc`] rather than `RpcClient` directly. Services can swap
+//! in [`mock::MockRpc`] (feature `mock`) to run without a validator.
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig,
+};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::error::{ClientError, Result};
+
+/// Cluster access needed by [`crate::LotteryClient`]
+pub trait LotteryRpc {
+    /// Raw data of an account, or `None` if it does not exist
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>>;
+
+    /// Raw data of several accounts, in order
+    fn get_multiple_account_data(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        addresses
+            .iter()
+            .map(|address| self.get_account_data(address))
+            .collect()
+    }
+
+    /// Addresses and data of the lottery program's accounts matching every filter
+    fn get_program_account_data(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>>;
+
+    /// Blockhash to sign new transactions with
+    fn get_latest_blockhash(&self) -> Result<Hash>;
+
+    /// Submit a signed transaction and wait for confirmation
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature>;
+
+    /// Log messages of a confirmed transaction, or `None` if it is unknown
+    fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>>;
+}
+
+impl LotteryRpc for RpcClient {
+    fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let response = self.get_account_with_commitment(address, self.commitment())?;
+        Ok(response.value.map(|account| account.data))
+    }
+
+    fn get_multiple_account_data(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        // The RPC caps getMultipleAccounts at 100 keys
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(100) {
+            let response = self.get_multiple_accounts_with_commitment(chunk, self.commitment())?;
+            accounts.extend(response.value.into_iter().map(|account| account.map(|a| a.data)));
+        }
+        Ok(accounts)
+    }
+
+    fn get_program_account_data(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self.get_program_accounts_with_config(&telegram_lottery::ID, config)?;
+        Ok(accounts
+            .into_iter()
+            .map(|(address, account)| (address, account.data))
+            .collect())
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        Ok(RpcClient::get_latest_blockhash(self)?)
+    }
+
+    fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        Ok(RpcClient::send_and_confirm_transaction(self, transaction)?)
+    }
+
+    fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let transaction = match self.get_transaction_with_config(signature, config) {
+            Ok(transaction) => transaction,
+            Err(error) if error.to_string().contains("not found") => return Ok(None),
+            Err(error) => return Err(ClientError::from(error)),
+        };
+        let logs = transaction
+            .transaction
+            .meta
+            .and_then(|meta| match meta.log_messages {
+                OptionSerializer::Some(logs) => Some(logs),
+                _ => None,
+            })
+            .unwrap_or_default();
+        Ok(Some(logs))
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+pub mod mock {
+    //! In-memory [`LotteryRpc`] for tests
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Accounts and transaction logs held in memory, all treated as owned by
+    /// the lottery program
+    ///
+    /// Sent transactions are recorded rather than executed; tests inspect
+    /// them with [`MockRpc::sent`] and seed state with [`MockRpc::set_account`].
+    #[derive(Default)]
+    pub struct MockRpc {
+        accounts: RefCell<HashMap<Pubkey, Vec<u8>>>,
+        logs: RefCell<HashMap<Signature, Vec<String>>>,
+        sent: RefCell<Vec<Transaction>>,
+        blockhash: Hash,
+    }
+
+    impl MockRpc {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Store raw account data at `address`
+        pub fn set_account(&self, address: Pubkey, data: Vec<u8>) {
+            self.accounts.borrow_mut().insert(address, data);
+        }
+
+        /// Remove the account at `address`
+        pub fn remove_account(&self, address: &Pubkey) {
+            self.accounts.borrow_mut().remove(address);
+        }
+
+        /// Log messages returned for `signature`
+        pub fn set_logs(&self, signature: Signature, logs: Vec<String>) {
+            self.logs.borrow_mut().insert(signature, logs);
+        }
+
+        /// Transactions submitted so far
+        pub fn sent(&self) -> Vec<Transaction> {
+            self.sent.borrow().clone()
+        }
+    }
+
+    fn matches(filter: &RpcFilterType, data: &[u8]) -> bool {
+        match filter {
+            RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+            RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+            RpcFilterType::TokenAccountState => false,
+        }
+    }
+
+    impl LotteryRpc for MockRpc {
+        fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+            Ok(self.accounts.borrow().get(address).cloned())
+        }
+
+        fn get_program_account_data(
+            &self,
+            filters: Vec<RpcFilterType>,
+        ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+            Ok(self
+                .accounts
+                .borrow()
+                .iter()
+                .filter(|(_, data)| filters.iter().all(|filter| matches(filter, data)))
+                .map(|(address, data)| (*address, data.clone()))
+                .collect())
+        }
+
+        fn get_latest_blockhash(&self) -> Result<Hash> {
+            Ok(self.blockhash)
+        }
+
+        fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+            self.sent.borrow_mut().push(transaction.clone());
+            Ok(transaction.signatures.first().copied().unwrap_or_default())
+        }
+
+        fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>> {
+            Ok(self.logs.borrow().get(signature).cloned())
+        }
+    }
+}
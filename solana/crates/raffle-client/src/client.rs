@@ -0,0 +1,382 @@
+//! Account fetching, ticket enumeration and transaction submission
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use raffle_v4::state::{
+    ProgramState, RaffleAccount, RoleRegistry, TicketAccount, UserTicketsAccount,
+};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::draw;
+use crate::error::{ClientError, Result};
+use crate::instruction;
+use crate::pda;
+use crate::rpc::RaffleRpc;
+
+/// Offset of `TicketAccount::raffle_id`: discriminator
+const TICKET_RAFFLE_ID_OFFSET: usize = 8;
+/// Offset of `TicketAccount::owner`: discriminator, raffle_id
+const TICKET_OWNER_OFFSET: usize = 8 + 8;
+
+/// Offset of the treasury in ORAO's network state: discriminator, authority
+const ORAO_TREASURY_OFFSET: usize = 8 + 32;
+
+/// Raffle client over any [`RaffleRpc`] backend
+pub struct RaffleClient<R> {
+    rpc: R,
+}
+
+impl<R: RaffleRpc> RaffleClient<R> {
+    pub fn new(rpc: R) -> Self {
+        Self { rpc }
+    }
+
+    /// Underlying RPC backend
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    /// Decode an Anchor account, checking its discriminator
+    pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+        T::try_deserialize(&mut &data[..]).map_err(|error| ClientError::Decode {
+            address: *address,
+            reason: error.to_string(),
+        })
+    }
+
+    /// Fetch and decode an account, or `None` if it does not exist
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        self.rpc
+            .get_account_data(address)?
+            .map(|data| Self::decode(address, &data))
+            .transpose()
+    }
+
+    /// Fetch and decode several accounts of one type, in order
+    pub fn fetch_many<T: AccountDeserialize>(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<T>>> {
+        let accounts = self.rpc.get_multiple_account_data(addresses)?;
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(address, data)| data.map(|data| Self::decode(address, &data)).transpose())
+            .collect()
+    }
+
+    /// Fetch an account that must exist
+    fn require<T: AccountDeserialize>(&self, address: Pubkey) -> Result<T> {
+        self.fetch(&address)?
+            .ok_or(ClientError::AccountNotFound(address))
+    }
+
+    pub fn program_state(&self) -> Result<Option<ProgramState>> {
+        self.fetch(&pda::program_state())
+    }
+
+    pub fn role_registry(&self) -> Result<Option<RoleRegistry>> {
+        self.fetch(&pda::roles())
+    }
+
+    pub fn raffle(&self, raffle_id: u64) -> Result<Option<RaffleAccount>> {
+        self.fetch(&pda::raffle(raffle_id))
+    }
+
+    /// Several raffles in one round trip
+    pub fn raffles(&self, raffle_ids: &[u64]) -> Result<Vec<Option<RaffleAccount>>> {
+        let addresses: Vec<Pubkey> = raffle_ids.iter().map(|id| pda::raffle(*id)).collect();
+        self.fetch_many(&addresses)
+    }
+
+    /// Every raffle account, by id
+    pub fn all_raffles(&self) -> Result<Vec<(Pubkey, RaffleAccount)>> {
+        let filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            RaffleAccount::DISCRIMINATOR.to_vec(),
+        ))];
+        let mut raffles = self
+            .rpc
+            .get_program_account_data(filters)?
+            .into_iter()
+            .map(|(address, data)| Ok((address, Self::decode::<RaffleAccount>(&address, &data)?)))
+            .collect::<Result<Vec<_>>>()?;
+        raffles.sort_by_key(|(_, raffle)| raffle.id);
+        Ok(raffles)
+    }
+
+    /// Ticket account whose range starts at `ticket_number`
+    pub fn ticket(&self, raffle_id: u64, ticket_number: u32) -> Result<Option<TicketAccount>> {
+        self.fetch(&pda::ticket(raffle_id, ticket_number))
+    }
+
+    pub fn user_tickets(
+        &self,
+        raffle_id: u64,
+        owner: &Pubkey,
+    ) -> Result<Option<UserTicketsAccount>> {
+        self.fetch(&pda::user_tickets(raffle_id, owner))
+    }
+
+    /// Every open ticket account of a raffle, optionally only `owner`'s,
+    /// ordered by first ticket number
+    pub fn tickets(
+        &self,
+        raffle_id: u64,
+        owner: Option<&Pubkey>,
+    ) -> Result<Vec<(Pubkey, TicketAccount)>> {
+        let mut filters = vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                TicketAccount::DISCRIMINATOR.to_vec(),
+            )),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                TICKET_RAFFLE_ID_OFFSET,
+                raffle_id.to_le_bytes().to_vec(),
+            )),
+        ];
+        if let Some(owner) = owner {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                TICKET_OWNER_OFFSET,
+                owner.to_bytes().to_vec(),
+            )));
+        }
+
+        let mut tickets = self
+            .rpc
+            .get_program_account_data(filters)?
+            .into_iter()
+            .map(|(address, data)| Ok((address, Self::decode::<TicketAccount>(&address, &data)?)))
+            .collect::<Result<Vec<_>>>()?;
+        tickets.sort_by_key(|(_, ticket)| ticket.ticket_number);
+        Ok(tickets)
+    }
+
+    /// Fee treasury recorded in ORAO's network state, needed by
+    /// `request_winner_selection`
+    pub fn orao_treasury(&self) -> Result<Pubkey> {
+        let address = randomness::orao::network_state_address();
+        let data = self
+            .rpc
+            .get_account_data(&address)?
+            .ok_or(ClientError::AccountNotFound(address))?;
+        let bytes = data
+            .get(ORAO_TREASURY_OFFSET..ORAO_TREASURY_OFFSET + 32)
+            .ok_or_else(|| ClientError::Decode {
+                address,
+                reason: "network state too short".to_string(),
+            })?;
+        Ok(Pubkey::try_from(bytes).expect("slice is 32 bytes"))
+    }
+
+    /// Winning ticket the raffle's fulfilled ORAO request will draw
+    pub fn winning_ticket(&self, raffle: &RaffleAccount) -> Result<u32> {
+        let address = draw::vrf_request(raffle)?;
+        let data = self
+            .rpc
+            .get_account_data(&address)?
+            .ok_or(ClientError::RandomnessNotFulfilled(address))?;
+        draw::winning_ticket(raffle, &data)
+    }
+
+    /// `fulfill_winner_selection` followed by `bind_winner` on the ticket
+    /// range holding the drawn number, for an ORAO raffle whose randomness
+    /// has been fulfilled
+    pub fn fulfill_and_bind(&self, caller: &Pubkey, raffle_id: u64) -> Result<Vec<Instruction>> {
+        let raffle: RaffleAccount = self.require(pda::raffle(raffle_id))?;
+        let vrf_request = draw::vrf_request(&raffle)?;
+        let winning_ticket = self.winning_ticket(&raffle)?;
+
+        let tickets: Vec<TicketAccount> = self
+            .tickets(raffle_id, None)?
+            .into_iter()
+            .map(|(_, ticket)| ticket)
+            .collect();
+        let holder =
+            draw::holder_of(&tickets, winning_ticket).ok_or(ClientError::TicketNotFound {
+                raffle_id,
+                ticket: winning_ticket,
+            })?;
+
+        Ok(vec![
+            instruction::fulfill_winner_selection(caller, raffle_id, &vrf_request),
+            instruction::bind_winner(caller, raffle_id, holder.ticket_number),
+        ])
+    }
+
+    /// Sign with `signers` (the first pays fees) and submit
+    pub fn send(&self, instructions: &[Instruction], signers: &[&dyn Signer]) -> Result<Signature> {
+        let payer = signers.first().map(|signer| signer.pubkey());
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, payer.as_ref(), signers, blockhash);
+        self.rpc.send_and_confirm_transaction(&transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::tests::{drawing_raffle, randomness_data};
+    use crate::rpc::mock::MockRpc;
+    use anchor_lang::AccountSerialize;
+    use raffle_v4::instructions::fulfill_winner_selection::calculate_winning_ticket;
+    use solana_sdk::signature::Keypair;
+
+    fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn ticket(raffle_id: u64, owner: Pubkey, ticket_number: u32, quantity: u32) -> TicketAccount {
+        TicketAccount {
+            raffle_id,
+            owner,
+            ticket_number,
+            quantity,
+            amount_paid: 10_000_000 * quantity as u64,
+            refunded: false,
+            purchase_time: 500,
+            bump: 255,
+        }
+    }
+
+    /// Store ticket ranges, one account each, at their PDAs
+    fn seed_tickets(rpc: &MockRpc, raffle_id: u64, ranges: &[(Pubkey, u32, u32)]) {
+        for (owner, start, quantity) in ranges {
+            rpc.set_account(
+                pda::ticket(raffle_id, *start),
+                serialize(&ticket(raffle_id, *owner, *start, *quantity)),
+            );
+        }
+    }
+
+    #[test]
+    fn test_tickets_filters_by_raffle_and_owner() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let rpc = MockRpc::new();
+        seed_tickets(&rpc, 1, &[(bob, 5, 2), (alice, 0, 5), (alice, 7, 1)]);
+        seed_tickets(&rpc, 2, &[(alice, 0, 3)]);
+        // A raffle account must not be mistaken for a ticket
+        rpc.set_account(pda::raffle(1), serialize(&drawing_raffle(1, 8, [0u8; 32])));
+        let client = RaffleClient::new(rpc);
+
+        let all = client.tickets(1, None).unwrap();
+        let starts: Vec<u32> = all.iter().map(|(_, t)| t.ticket_number).collect();
+        assert_eq!(starts, vec![0, 5, 7]);
+        assert_eq!(all[1].0, pda::ticket(1, 5));
+
+        let alices = client.tickets(1, Some(&alice)).unwrap();
+        assert_eq!(alices.len(), 2);
+        assert!(alices
+            .iter()
+            .all(|(_, t)| t.owner == alice && t.raffle_id == 1));
+        assert!(client.tickets(3, None).unwrap().is_empty());
+
+        let raffles = client.all_raffles().unwrap();
+        assert_eq!(raffles.len(), 1);
+        assert_eq!(raffles[0].0, pda::raffle(1));
+    }
+
+    #[test]
+    fn test_fetch_decodes_and_reports_missing() {
+        let rpc = MockRpc::new();
+        rpc.set_account(pda::raffle(1), serialize(&drawing_raffle(1, 8, [0u8; 32])));
+        rpc.set_account(
+            pda::raffle(2),
+            serialize(&ticket(2, Pubkey::new_unique(), 0, 1)),
+        );
+        let client = RaffleClient::new(rpc);
+
+        assert_eq!(client.raffle(1).unwrap().unwrap().tickets_sold, 8);
+        assert!(client.program_state().unwrap().is_none());
+        assert!(matches!(client.raffle(2), Err(ClientError::Decode { .. })));
+        let raffles = client.raffles(&[3, 1]).unwrap();
+        assert!(raffles[0].is_none() && raffles[1].is_some());
+    }
+
+    #[test]
+    fn test_fulfill_and_bind_targets_holder_of_drawn_ticket() {
+        let seed = [6u8; 32];
+        let randomness = [42u8; 64];
+        let raffle = drawing_raffle(1, 20, seed);
+        let expected = calculate_winning_ticket(&randomness, 20).unwrap();
+
+        let rpc = MockRpc::new();
+        rpc.set_account(pda::raffle(1), serialize(&raffle));
+        let ranges = [
+            (Pubkey::new_unique(), 0, 8),
+            (Pubkey::new_unique(), 8, 4),
+            (Pubkey::new_unique(), 12, 8),
+        ];
+        seed_tickets(&rpc, 1, &ranges);
+        let client = RaffleClient::new(rpc);
+        let caller = Pubkey::new_unique();
+
+        // Not fulfilled yet
+        assert!(matches!(
+            client.fulfill_and_bind(&caller, 1),
+            Err(ClientError::RandomnessNotFulfilled(_))
+        ));
+
+        let vrf_request = raffle.vrf_request.unwrap();
+        client
+            .rpc()
+            .set_account(vrf_request, randomness_data(seed, randomness));
+        let instructions = client.fulfill_and_bind(&caller, 1).unwrap();
+
+        let holder_start = ranges
+            .iter()
+            .map(|(_, start, _)| *start)
+            .filter(|start| *start <= expected)
+            .max()
+            .unwrap();
+        assert_eq!(
+            instructions,
+            vec![
+                instruction::fulfill_winner_selection(&caller, 1, &vrf_request),
+                instruction::bind_winner(&caller, 1, holder_start),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fulfill_and_bind_needs_holder() {
+        let seed = [6u8; 32];
+        let raffle = drawing_raffle(1, 20, seed);
+        let rpc = MockRpc::new();
+        rpc.set_account(pda::raffle(1), serialize(&raffle));
+        rpc.set_account(
+            raffle.vrf_request.unwrap(),
+            randomness_data(seed, [42u8; 64]),
+        );
+        let client = RaffleClient::new(rpc);
+
+        assert!(matches!(
+            client.fulfill_and_bind(&Pubkey::new_unique(), 1),
+            Err(ClientError::TicketNotFound { raffle_id: 1, .. })
+        ));
+        assert!(matches!(
+            client.fulfill_and_bind(&Pubkey::new_unique(), 2),
+            Err(ClientError::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_send_signs_and_submits() {
+        let pauser = Keypair::new();
+        let client = RaffleClient::new(MockRpc::new());
+        let freeze = instruction::set_raffle_frozen(&pauser.pubkey(), 1, true);
+
+        let signature = client.send(&[freeze], &[&pauser]).unwrap();
+        let sent = client.rpc().sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].signatures[0], signature);
+        sent[0].verify().unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+//! Off-chain mirror of the raffle's ORAO draw
+//!
+//! `fulfill_winner_selection` maps the ORAO randomness to a ticket number and
+//! `bind_winner` then needs the ticket account whose range holds it. Running
+//! the same mapping here lets both instructions go out in one transaction.
+
+use raffle_v4::instructions::fulfill_winner_selection::calculate_winning_ticket;
+use raffle_v4::instructions::request_winner_selection::generate_vrf_seed;
+use raffle_v4::state::{RaffleAccount, RaffleStatus, TicketAccount};
+use randomness::orao::OraoRandomness;
+use randomness::RandomnessSource;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::{ClientError, Result};
+
+fn not_drawable(raffle: &RaffleAccount, reason: &str) -> ClientError {
+    ClientError::NotDrawable {
+        raffle_id: raffle.id,
+        reason: reason.to_string(),
+    }
+}
+
+/// Seed the program derives for the raffle's next ORAO request
+///
+/// The seed depends only on raffle state, so the request account named here
+/// stays valid until the raffle's draw attempt counter moves.
+pub fn vrf_seed(raffle: &RaffleAccount) -> Result<[u8; 32]> {
+    generate_vrf_seed(raffle).map_err(|error| not_drawable(raffle, &error.to_string()))
+}
+
+/// ORAO randomness account recorded by the raffle's open request
+pub fn vrf_request(raffle: &RaffleAccount) -> Result<Pubkey> {
+    raffle
+        .vrf_request
+        .ok_or_else(|| not_drawable(raffle, "no randomness requested"))
+}
+
+/// Winning ticket number `fulfill_winner_selection` will draw from the
+/// randomness account data
+pub fn winning_ticket(raffle: &RaffleAccount, randomness_data: &[u8]) -> Result<u32> {
+    if raffle.status != RaffleStatus::Drawing || raffle.winning_ticket.is_some() {
+        return Err(not_drawable(raffle, "not awaiting a draw"));
+    }
+    let address = vrf_request(raffle)?;
+    let request = raffle
+        .randomness_request
+        .ok_or_else(|| not_drawable(raffle, "no randomness requested"))?;
+
+    let account =
+        OraoRandomness::try_from_data(randomness_data).map_err(|error| ClientError::Decode {
+            address,
+            reason: error.to_string(),
+        })?;
+    if account.seed != request.seed {
+        return Err(ClientError::Decode {
+            address,
+            reason: "seed does not match the raffle's request".to_string(),
+        });
+    }
+    let randomness = account
+        .read_64()
+        .ok_or(ClientError::RandomnessNotFulfilled(address))?;
+
+    calculate_winning_ticket(&randomness, raffle.tickets_sold)
+        .map_err(|error| not_drawable(raffle, &error.to_string()))
+}
+
+/// Ticket account whose range holds `ticket_number`
+pub fn holder_of(tickets: &[TicketAccount], ticket_number: u32) -> Option<&TicketAccount> {
+    tickets.iter().find(|ticket| ticket.contains(ticket_number))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use randomness::RandomnessRequest;
+    use solana_sdk::hash::hash;
+
+    /// Raw ORAO randomness account for `seed`
+    pub(crate) fn randomness_data(seed: [u8; 32], randomness: [u8; 64]) -> Vec<u8> {
+        let mut data = hash(b"account:Randomness").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&seed);
+        data.extend_from_slice(&randomness);
+        data
+    }
+
+    pub(crate) fn drawing_raffle(
+        raffle_id: u64,
+        tickets_sold: u32,
+        seed: [u8; 32],
+    ) -> RaffleAccount {
+        RaffleAccount {
+            id: raffle_id,
+            creator: Pubkey::new_unique(),
+            title: "Raffle".to_string(),
+            description: String::new(),
+            content: None,
+            prize_amount: 1_000_000_000,
+            ticket_price: 10_000_000,
+            fee_rate: 250,
+            treasury: Pubkey::new_unique(),
+            price_tiers: vec![],
+            max_tickets: 100,
+            min_tickets: 1,
+            tickets_sold,
+            ticket_revenue: 10_000_000 * tickets_sold as u64,
+            tickets_refunded: 0,
+            open_ticket_accounts: 0,
+            max_tickets_per_wallet: None,
+            start_time: 0,
+            end_time: 1_000,
+            status: RaffleStatus::Drawing,
+            frozen: false,
+            escrow_bump: 255,
+            raffle_bump: 255,
+            vrf_request: Some(randomness::orao::randomness_account_address(&seed)),
+            randomness_provider: randomness::RandomnessProvider::Orao,
+            randomness_request: Some(RandomnessRequest {
+                seed,
+                ..RandomnessRequest::default()
+            }),
+            draw_timeout: RaffleAccount::DEFAULT_DRAW_TIMEOUT,
+            draw_requested_at: Some(1_000),
+            draw_attempts: 1,
+            winner: None,
+            winning_ticket: None,
+            vrf_proof: None,
+            created_at: 0,
+            drawn_at: None,
+            distributed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_winning_ticket_matches_program() {
+        let seed = [4u8; 32];
+        let raffle = drawing_raffle(1, 37, seed);
+        let randomness = [9u8; 64];
+
+        let ticket = winning_ticket(&raffle, &randomness_data(seed, randomness)).unwrap();
+        assert_eq!(ticket, calculate_winning_ticket(&randomness, 37).unwrap());
+        assert!(ticket < 37);
+    }
+
+    #[test]
+    fn test_winning_ticket_rejects_bad_accounts() {
+        let seed = [4u8; 32];
+        let raffle = drawing_raffle(1, 10, seed);
+
+        assert!(matches!(
+            winning_ticket(&raffle, &randomness_data(seed, [0u8; 64])),
+            Err(ClientError::RandomnessNotFulfilled(_))
+        ));
+        assert!(matches!(
+            winning_ticket(&raffle, &randomness_data([5u8; 32], [1u8; 64])),
+            Err(ClientError::Decode { .. })
+        ));
+        assert!(matches!(
+            winning_ticket(&raffle, &[0u8; 16]),
+            Err(ClientError::Decode { .. })
+        ));
+
+        let mut drawn = raffle.clone();
+        drawn.winning_ticket = Some(3);
+        assert!(matches!(
+            winning_ticket(&drawn, &randomness_data(seed, [1u8; 64])),
+            Err(ClientError::NotDrawable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_vrf_seed_follows_draw_attempts() {
+        let raffle = drawing_raffle(1, 10, [0u8; 32]);
+        assert_eq!(vrf_seed(&raffle).unwrap(), vrf_seed(&raffle).unwrap());
+        let retried = RaffleAccount {
+            draw_attempts: raffle.draw_attempts + 1,
+            ..raffle.clone()
+        };
+        assert_ne!(vrf_seed(&raffle).unwrap(), vrf_seed(&retried).unwrap());
+    }
+}
@@ -0,0 +1,32 @@
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Errors returned by the raffle client
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(String),
+
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("Account {address} could not be decoded: {reason}")]
+    Decode { address: Pubkey, reason: String },
+
+    #[error("Raffle {raffle_id} cannot be drawn: {reason}")]
+    NotDrawable { raffle_id: u64, reason: String },
+
+    #[error("Randomness account {0} has not been fulfilled")]
+    RandomnessNotFulfilled(Pubkey),
+
+    #[error("No ticket account of raffle {raffle_id} covers ticket {ticket}")]
+    TicketNotFound { raffle_id: u64, ticket: u32 },
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(error.to_string())
+    }
+}
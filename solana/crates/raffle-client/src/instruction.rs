@@ -0,0 +1,515 @@
+//! Typed builders for every `raffle_v4` instruction
+//!
+//! Builders derive all program accounts from their seeds; callers pass only
+//! signers, external accounts (winner, treasury, ORAO accounts) and the
+//! raffle state the seeds depend on. Account lists are encoded through the
+//! program's own `accounts::*` structs, so they stay in sync with the program.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+pub use raffle_v4::instructions::{CreateRaffleParams, UpdateConfigParams};
+use raffle_v4::state::Role;
+use raffle_v4::{accounts, instruction as ix};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{system_program, sysvar};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: raffle_v4::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// ----- admin -----
+
+pub fn initialize_program(authority: &Pubkey, fee_rate: u16, treasury: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeProgram {
+            program_state: pda::program_state(),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        ix::InitializeProgram { fee_rate, treasury },
+    )
+}
+
+/// Queue, apply immediately or propose config changes; see [`UpdateConfigParams`]
+pub fn update_program_config(caller: &Pubkey, params: UpdateConfigParams) -> Instruction {
+    build(
+        accounts::UpdateProgramConfig {
+            program_state: pda::program_state(),
+            role_registry: pda::roles(),
+            caller: *caller,
+        },
+        ix::UpdateProgramConfig { params },
+    )
+}
+
+pub fn accept_authority(new_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAuthority {
+            program_state: pda::program_state(),
+            new_authority: *new_authority,
+        },
+        ix::AcceptAuthority {},
+    )
+}
+
+pub fn apply_config_change(caller: &Pubkey) -> Instruction {
+    build(
+        accounts::ApplyConfigChange {
+            program_state: pda::program_state(),
+            caller: *caller,
+        },
+        ix::ApplyConfigChange {},
+    )
+}
+
+pub fn cancel_config_change(fee_manager: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelConfigChange {
+            program_state: pda::program_state(),
+            role_registry: pda::roles(),
+            fee_manager: *fee_manager,
+        },
+        ix::CancelConfigChange {},
+    )
+}
+
+pub fn set_raffle_frozen(pauser: &Pubkey, raffle_id: u64, frozen: bool) -> Instruction {
+    build(
+        accounts::SetRaffleFrozen {
+            raffle_account: pda::raffle(raffle_id),
+            role_registry: pda::roles(),
+            pauser: *pauser,
+        },
+        ix::SetRaffleFrozen { raffle_id, frozen },
+    )
+}
+
+pub fn initialize_roles(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeRoles {
+            role_registry: pda::roles(),
+            program_state: pda::program_state(),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        ix::InitializeRoles {},
+    )
+}
+
+pub fn grant_role(authority: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+    build(
+        accounts::GrantRole {
+            role_registry: pda::roles(),
+            program_state: pda::program_state(),
+            authority: *authority,
+        },
+        ix::GrantRole { role, member },
+    )
+}
+
+pub fn revoke_role(authority: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+    build(
+        accounts::RevokeRole {
+            role_registry: pda::roles(),
+            program_state: pda::program_state(),
+            authority: *authority,
+        },
+        ix::RevokeRole { role, member },
+    )
+}
+
+// ----- raffle -----
+
+pub fn create_raffle(creator: &Pubkey, params: CreateRaffleParams) -> Instruction {
+    build(
+        accounts::CreateRaffle {
+            raffle_account: pda::raffle(params.raffle_id),
+            escrow_account: pda::escrow(params.raffle_id),
+            program_state: pda::program_state(),
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        ix::CreateRaffle { params },
+    )
+}
+
+fn purchase_accounts(
+    buyer: &Pubkey,
+    raffle_id: u64,
+    tickets_sold: u32,
+) -> accounts::PurchaseTicket {
+    accounts::PurchaseTicket {
+        raffle_account: pda::raffle(raffle_id),
+        ticket_account: pda::ticket(raffle_id, tickets_sold),
+        user_tickets: pda::user_tickets(raffle_id, buyer),
+        escrow_account: pda::escrow(raffle_id),
+        program_state: pda::program_state(),
+        buyer: *buyer,
+        system_program: system_program::ID,
+    }
+}
+
+/// Buy one ticket; `tickets_sold` is the raffle's current count, which
+/// becomes the new ticket's number
+pub fn purchase_ticket(buyer: &Pubkey, raffle_id: u64, tickets_sold: u32) -> Instruction {
+    build(
+        purchase_accounts(buyer, raffle_id, tickets_sold),
+        ix::PurchaseTicket { raffle_id },
+    )
+}
+
+/// Buy `quantity` consecutive tickets starting at `tickets_sold`
+pub fn purchase_tickets(
+    buyer: &Pubkey,
+    raffle_id: u64,
+    tickets_sold: u32,
+    quantity: u32,
+) -> Instruction {
+    build(
+        purchase_accounts(buyer, raffle_id, tickets_sold),
+        ix::PurchaseTickets {
+            raffle_id,
+            quantity,
+        },
+    )
+}
+
+pub fn cancel_raffle(creator: &Pubkey, raffle_id: u64) -> Instruction {
+    build(
+        accounts::CancelRaffle {
+            raffle_account: pda::raffle(raffle_id),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        ix::CancelRaffle { raffle_id },
+    )
+}
+
+/// Cancel a timed-out draw; ORAO raffles must pass their pending request
+/// (`RaffleAccount::vrf_request`), other providers pass `None`
+pub fn cancel_stalled_raffle(
+    caller: &Pubkey,
+    raffle_id: u64,
+    creator: &Pubkey,
+    vrf_request: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CancelStalledRaffle {
+            raffle_account: pda::raffle(raffle_id),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            creator: *creator,
+            vrf_request: vrf_request.copied(),
+            caller: *caller,
+            system_program: system_program::ID,
+        },
+        ix::CancelStalledRaffle { raffle_id },
+    )
+}
+
+pub fn cancel_undersold_raffle(caller: &Pubkey, raffle_id: u64, creator: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelUndersoldRaffle {
+            raffle_account: pda::raffle(raffle_id),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            creator: *creator,
+            caller: *caller,
+            system_program: system_program::ID,
+        },
+        ix::CancelUndersoldRaffle { raffle_id },
+    )
+}
+
+// ----- draw -----
+
+/// Request ORAO randomness; `vrf_request` is the ORAO account for the seed
+/// the program will derive (see [`crate::draw::vrf_seed`])
+pub fn request_winner_selection(
+    payer: &Pubkey,
+    raffle_id: u64,
+    vrf_request: &Pubkey,
+    orao_treasury: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RequestWinnerSelection {
+            raffle_account: pda::raffle(raffle_id),
+            program_state: pda::program_state(),
+            vrf_request: *vrf_request,
+            network_state: randomness::orao::network_state_address(),
+            treasury: *orao_treasury,
+            payer: *payer,
+            vrf_program: randomness::orao::ID,
+            system_program: system_program::ID,
+        },
+        ix::RequestWinnerSelection { raffle_id },
+    )
+}
+
+/// Request fresh ORAO randomness for a stalled draw; `previous_request` is
+/// the unfulfilled request being replaced
+pub fn rerequest_winner_selection(
+    payer: &Pubkey,
+    raffle_id: u64,
+    previous_request: &Pubkey,
+    vrf_request: &Pubkey,
+    orao_treasury: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RerequestWinnerSelection {
+            raffle_account: pda::raffle(raffle_id),
+            program_state: pda::program_state(),
+            previous_request: *previous_request,
+            vrf_request: *vrf_request,
+            network_state: randomness::orao::network_state_address(),
+            treasury: *orao_treasury,
+            payer: *payer,
+            vrf_program: randomness::orao::ID,
+            system_program: system_program::ID,
+        },
+        ix::RerequestWinnerSelection { raffle_id },
+    )
+}
+
+/// Draw the winning ticket from the raffle's fulfilled ORAO request
+pub fn fulfill_winner_selection(
+    caller: &Pubkey,
+    raffle_id: u64,
+    vrf_request: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::FulfillWinnerSelection {
+            raffle_account: pda::raffle(raffle_id),
+            vrf_request: *vrf_request,
+            program_state: pda::program_state(),
+            caller: *caller,
+        },
+        ix::FulfillWinnerSelection { raffle_id },
+    )
+}
+
+/// Bind the winner; `ticket_start` is the first ticket of the range holding
+/// the winning ticket
+pub fn bind_winner(caller: &Pubkey, raffle_id: u64, ticket_start: u32) -> Instruction {
+    build(
+        accounts::BindWinner {
+            raffle_account: pda::raffle(raffle_id),
+            program_state: pda::program_state(),
+            winning_ticket: pda::ticket(raffle_id, ticket_start),
+            caller: *caller,
+        },
+        ix::BindWinner { raffle_id },
+    )
+}
+
+pub fn commit_winner_selection(
+    caller: &Pubkey,
+    raffle_id: u64,
+    commitment: Option<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::CommitWinnerSelection {
+            raffle_account: pda::raffle(raffle_id),
+            program_state: pda::program_state(),
+            caller: *caller,
+        },
+        ix::CommitWinnerSelection {
+            raffle_id,
+            commitment,
+        },
+    )
+}
+
+/// Reveal a signed-oracle or slot-hash draw; signed-oracle reveals must be
+/// preceded by the oracle's Ed25519 instruction in the same transaction
+pub fn reveal_winner_selection(
+    caller: &Pubkey,
+    raffle_id: u64,
+    secret: Option<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::RevealWinnerSelection {
+            raffle_account: pda::raffle(raffle_id),
+            program_state: pda::program_state(),
+            role_registry: pda::roles(),
+            instructions: sysvar::instructions::ID,
+            slot_hashes: sysvar::slot_hashes::ID,
+            caller: *caller,
+        },
+        ix::RevealWinnerSelection { raffle_id, secret },
+    )
+}
+
+// ----- settlement -----
+
+/// Pay the winner and the treasury snapshotted in the raffle
+pub fn distribute_prize(
+    caller: &Pubkey,
+    raffle_id: u64,
+    winner: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::DistributePrize {
+            raffle_account: pda::raffle(raffle_id),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            winner: *winner,
+            treasury: *treasury,
+            caller: *caller,
+            system_program: system_program::ID,
+        },
+        ix::DistributePrize { raffle_id },
+    )
+}
+
+pub fn claim_refund(ticket_holder: &Pubkey, raffle_id: u64, ticket_number: u32) -> Instruction {
+    build(
+        accounts::ClaimRefund {
+            raffle_account: pda::raffle(raffle_id),
+            ticket_account: pda::ticket(raffle_id, ticket_number),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            ticket_holder: *ticket_holder,
+            user_tickets: pda::user_tickets(raffle_id, ticket_holder),
+            system_program: system_program::ID,
+        },
+        ix::ClaimRefund {
+            raffle_id,
+            ticket_number,
+        },
+    )
+}
+
+/// Refund several ranges; `tickets` pairs each range's first ticket number
+/// with its owner, whose per-wallet tracking account is appended as well
+pub fn refund_batch(caller: &Pubkey, raffle_id: u64, tickets: &[(u32, Pubkey)]) -> Instruction {
+    let mut instruction = build(
+        accounts::RefundBatch {
+            raffle_account: pda::raffle(raffle_id),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            caller: *caller,
+        },
+        ix::RefundBatch { raffle_id },
+    );
+    for (ticket_number, owner) in tickets {
+        instruction.accounts.push(AccountMeta::new(
+            pda::ticket(raffle_id, *ticket_number),
+            false,
+        ));
+        instruction.accounts.push(AccountMeta::new(*owner, false));
+        instruction.accounts.push(AccountMeta::new(
+            pda::user_tickets(raffle_id, owner),
+            false,
+        ));
+    }
+    instruction
+}
+
+pub fn close_ticket(
+    caller: &Pubkey,
+    raffle_id: u64,
+    ticket_number: u32,
+    owner: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CloseTicket {
+            raffle_account: pda::raffle(raffle_id),
+            ticket_account: pda::ticket(raffle_id, ticket_number),
+            owner: *owner,
+            user_tickets: pda::user_tickets(raffle_id, owner),
+            program_state: pda::program_state(),
+            caller: *caller,
+        },
+        ix::CloseTicket {
+            raffle_id,
+            ticket_number,
+        },
+    )
+}
+
+/// Close a settled raffle; `treasury` is the treasury snapshotted on the raffle
+pub fn close_raffle(
+    caller: &Pubkey,
+    raffle_id: u64,
+    creator: &Pubkey,
+    treasury: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CloseRaffle {
+            raffle_account: pda::raffle(raffle_id),
+            escrow_account: pda::escrow(raffle_id),
+            program_state: pda::program_state(),
+            creator: *creator,
+            treasury: *treasury,
+            caller: *caller,
+        },
+        ix::CloseRaffle { raffle_id },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    fn keys(instruction: &Instruction) -> Vec<Pubkey> {
+        instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect()
+    }
+
+    #[test]
+    fn test_data_starts_with_discriminator() {
+        let instruction = bind_winner(&Pubkey::new_unique(), 9, 4);
+        assert_eq!(instruction.program_id, raffle_v4::ID);
+        assert_eq!(instruction.data[..8], ix::BindWinner::DISCRIMINATOR);
+        assert_eq!(instruction.data[8..], 9u64.to_le_bytes());
+        assert!(keys(&instruction).contains(&pda::ticket(9, 4)));
+    }
+
+    #[test]
+    fn test_purchase_uses_next_ticket_number() {
+        let buyer = Pubkey::new_unique();
+        let instruction = purchase_tickets(&buyer, 3, 17, 5);
+        let keys = keys(&instruction);
+        assert!(keys.contains(&pda::ticket(3, 17)));
+        assert!(keys.contains(&pda::user_tickets(3, &buyer)));
+        let buyer_meta = instruction
+            .accounts
+            .iter()
+            .find(|meta| meta.pubkey == buyer)
+            .unwrap();
+        assert!(buyer_meta.is_signer && buyer_meta.is_writable);
+    }
+
+    #[test]
+    fn test_refund_batch_appends_writable_triples() {
+        let owners = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let instruction = refund_batch(&Pubkey::new_unique(), 2, &[(0, owners[0]), (4, owners[1])]);
+        let tail = &instruction.accounts[instruction.accounts.len() - 6..];
+        let expected = [
+            pda::ticket(2, 0),
+            owners[0],
+            pda::user_tickets(2, &owners[0]),
+            pda::ticket(2, 4),
+            owners[1],
+            pda::user_tickets(2, &owners[1]),
+        ];
+        for (meta, key) in tail.iter().zip(expected) {
+            assert_eq!(meta.pubkey, key);
+            assert!(meta.is_writable && !meta.is_signer);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Rust client for the `raffle_v4` program
+//!
+//! - [`pda`]: addresses of every program-derived account
+//! - [`instruction`]: typed builders for every program instruction
+//! - [`RaffleClient`]: account fetching, ticket enumeration and transaction
+//!   submission over a [`RaffleRpc`] backend
+//! - [`draw`]: recomputing the winning ticket from an ORAO randomness account
+//!
+//! [`RaffleRpc`] is implemented for `solana_client`'s blocking `RpcClient`;
+//! enable the `mock` feature for an in-memory implementation to test
+//! services against.
+
+pub mod client;
+pub mod draw;
+pub mod error;
+pub mod instruction;
+pub mod pda;
+pub mod rpc;
+
+pub use client::RaffleClient;
+pub use error::{ClientError, Result};
+pub use raffle_v4::{state, ID};
+pub use rpc::RaffleRpc;
@@ -0,0 +1,74 @@
+//! Program-derived addresses used by the raffle
+//!
+//! Thin wrappers over the program's own `find_pda` helpers, dropping the bump.
+
+use raffle_v4::state::{
+    EscrowAccount, ProgramState, RaffleAccount, RoleRegistry, TicketAccount, UserTicketsAccount,
+};
+use solana_sdk::pubkey::Pubkey;
+
+/// Global program state
+pub fn program_state() -> Pubkey {
+    ProgramState::find_pda().0
+}
+
+/// Role registry
+pub fn roles() -> Pubkey {
+    RoleRegistry::find_pda().0
+}
+
+/// Raffle account
+pub fn raffle(raffle_id: u64) -> Pubkey {
+    RaffleAccount::find_pda(raffle_id).0
+}
+
+/// Escrow holding a raffle's prize and ticket revenue
+pub fn escrow(raffle_id: u64) -> Pubkey {
+    EscrowAccount::find_pda(raffle_id).0
+}
+
+/// Ticket account whose range starts at `ticket_number`
+pub fn ticket(raffle_id: u64, ticket_number: u32) -> Pubkey {
+    TicketAccount::find_pda(raffle_id, ticket_number).0
+}
+
+/// Per-wallet ticket tracking account
+pub fn user_tickets(raffle_id: u64, owner: &Pubkey) -> Pubkey {
+    UserTicketsAccount::find_pda(raffle_id, owner).0
+}
+
+/// ORAO randomness account for a draw seed
+pub fn orao_randomness(seed: &[u8; 32]) -> Pubkey {
+    randomness::orao::randomness_account_address(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addresses_match_program_seeds() {
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"ticket", &7u64.to_le_bytes(), &3u32.to_le_bytes()],
+            &raffle_v4::ID,
+        );
+        assert_eq!(ticket(7, 3), expected);
+
+        let owner = Pubkey::new_unique();
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"user_tickets", &7u64.to_le_bytes(), owner.as_ref()],
+            &raffle_v4::ID,
+        );
+        assert_eq!(user_tickets(7, &owner), expected);
+    }
+
+    #[test]
+    fn test_raffle_addresses_are_distinct() {
+        let addresses = [raffle(1), escrow(1), ticket(1, 0), ticket(1, 1), raffle(2)];
+        for (i, a) in addresses.iter().enumerate() {
+            for b in &addresses[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+}
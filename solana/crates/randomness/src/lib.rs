@@ -0,0 +1,177 @@
+//! Randomness providers shared by the lottery and raffle programs
+//!
+//! A game or raffle records the [`RandomnessProvider`] it was created with and
+//! draws through the matching [`RandomnessSource`] implementation. Providers
+//! are enabled by cargo feature: `orao`, `signed-oracle` and `slot-hash`.
+//! Disabled providers still compile, because Anchor programs cannot drop
+//! instructions by feature, but refuse every request and load.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub mod orao;
+pub mod signed_oracle;
+pub mod slot_hash;
+
+/// Randomness provider a game or raffle draws from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RandomnessProvider {
+    /// ORAO VRF network
+    #[default]
+    Orao,
+    /// Registered oracle signing the request seed with ed25519
+    SignedOracle,
+    /// Operator commit-reveal mixed with a later slot hash (low stakes only)
+    SlotHash,
+}
+
+impl RandomnessProvider {
+    pub const LEN: usize = 1;
+
+    /// Whether this provider was compiled into the program
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            RandomnessProvider::Orao => cfg!(feature = "orao"),
+            RandomnessProvider::SignedOracle => cfg!(feature = "signed-oracle"),
+            RandomnessProvider::SlotHash => cfg!(feature = "slot-hash"),
+        }
+    }
+
+    /// Fail unless this provider was compiled into the program
+    pub fn require_enabled(&self) -> Result<()> {
+        require!(self.is_enabled(), RandomnessError::ProviderDisabled);
+        Ok(())
+    }
+}
+
+/// Open randomness request recorded by the consuming program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RandomnessRequest {
+    /// Seed the randomness is bound to
+    pub seed: [u8; 32],
+    /// Hash of the operator's secret (slot-hash provider only)
+    pub commitment: [u8; 32],
+    /// Slot the request was opened in
+    pub slot: u64,
+}
+
+impl RandomnessRequest {
+    pub const LEN: usize = 32 + // seed
+        32 + // commitment
+        8; // slot
+}
+
+/// Interface implemented by every randomness provider
+pub trait RandomnessSource<'info>: Sized {
+    /// Provider-specific accounts and arguments needed to open a request
+    type Request;
+
+    /// Open a request bound to `seed`; the caller stores the returned record
+    fn request(request: Self::Request, seed: [u8; 32]) -> Result<RandomnessRequest>;
+
+    /// Whether the randomness can be read
+    fn is_fulfilled(&self) -> bool;
+
+    /// 64 bytes of randomness, once fulfilled
+    fn read_64(&self) -> Option<[u8; 64]>;
+
+    /// 32 bytes of randomness, once fulfilled
+    fn read_32(&self) -> Option<[u8; 32]> {
+        self.read_64().map(|randomness| {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&randomness[..32]);
+            bytes
+        })
+    }
+}
+
+#[error_code(offset = 7000)]
+pub enum RandomnessError {
+    #[msg("Randomness provider is not enabled in this build")]
+    ProviderDisabled,
+
+    #[msg("Randomness account does not match the request")]
+    RandomnessAccountMismatch,
+
+    #[msg("Randomness account could not be decoded")]
+    InvalidRandomnessAccount,
+
+    #[msg("Randomness has not been fulfilled")]
+    NotFulfilled,
+
+    #[msg("No oracle signature over the request seed in this transaction")]
+    MissingSignature,
+
+    #[msg("Commitment must be non-zero")]
+    InvalidCommitment,
+
+    #[msg("Revealed secret does not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("No slot hash recorded after the commit slot yet")]
+    RevealTooEarly,
+
+    #[msg("Commit slot is no longer covered by the slot hashes sysvar")]
+    RevealExpired,
+}
+
+/// Stretch hashed inputs into 64 bytes of output
+pub fn expand_64(parts: &[&[u8]]) -> [u8; 64] {
+    let mut randomness = [0u8; 64];
+    for (lane, chunk) in randomness.chunks_exact_mut(32).enumerate() {
+        let mut inputs = parts.to_vec();
+        let domain = [lane as u8];
+        inputs.push(&domain);
+        chunk.copy_from_slice(&hashv(&inputs).to_bytes());
+    }
+    randomness
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(Option<[u8; 64]>);
+
+    impl<'info> RandomnessSource<'info> for Fixed {
+        type Request = ();
+
+        fn request(_request: (), seed: [u8; 32]) -> Result<RandomnessRequest> {
+            Ok(RandomnessRequest { seed, ..Default::default() })
+        }
+
+        fn is_fulfilled(&self) -> bool {
+            self.0.is_some()
+        }
+
+        fn read_64(&self) -> Option<[u8; 64]> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_read_32_takes_prefix() {
+        let mut bytes = [0u8; 64];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let source = Fixed(Some(bytes));
+        assert_eq!(source.read_32().unwrap(), bytes[..32]);
+        assert!(Fixed(None).read_32().is_none());
+    }
+
+    #[test]
+    fn test_expand_64_lanes_differ() {
+        let out = expand_64(&[b"seed"]);
+        assert_ne!(out[..32], out[32..]);
+        assert_eq!(out, expand_64(&[b"seed"]));
+        assert_ne!(out, expand_64(&[b"other"]));
+    }
+
+    #[test]
+    fn test_provider_enabled_matches_features() {
+        assert_eq!(RandomnessProvider::Orao.is_enabled(), cfg!(feature = "orao"));
+        assert_eq!(RandomnessProvider::SignedOracle.is_enabled(), cfg!(feature = "signed-oracle"));
+        assert_eq!(RandomnessProvider::SlotHash.is_enabled(), cfg!(feature = "slot-hash"));
+    }
+}
@@ -0,0 +1,273 @@
+//! ORAO VRF provider
+//!
+//! Talks to ORAO through its account layout and instruction encoding rather
+//! than the `orao-solana-vrf` crate, so both programs share one integration
+//! regardless of the ORAO SDK version they were written against.
+//!
+//! Only ORAO's legacy v1 interface is used: the `request` instruction
+//! (`global:request` discriminator followed by the 32-byte seed) and the
+//! `Randomness` account (`account:Randomness` discriminator, seed at bytes
+//! 8..40, randomness at 40..104, then the responses). `request_v2` and
+//! `RandomnessV2` accounts are not supported.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use crate::{RandomnessError, RandomnessProvider, RandomnessRequest, RandomnessSource};
+
+// ORAO VRF program id
+declare_id!("VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y");
+
+/// Seed of the ORAO network state PDA
+pub const CONFIG_ACCOUNT_SEED: &[u8] = b"orao-vrf-network-configuration";
+
+/// Seed prefix of ORAO randomness request PDAs
+pub const RANDOMNESS_ACCOUNT_SEED: &[u8] = b"orao-vrf-randomness-request";
+
+/// Size of the fixed part of a randomness account: discriminator, seed, randomness
+const RANDOMNESS_HEADER_LEN: usize = 8 + 32 + 64;
+
+/// ORAO network state address
+pub fn network_state_address() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &ID).0
+}
+
+/// ORAO randomness account address for `seed`
+pub fn randomness_account_address(seed: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[RANDOMNESS_ACCOUNT_SEED, seed], &ID).0
+}
+
+/// Anchor discriminator of ORAO's legacy v1 `request` instruction
+fn request_discriminator() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(b"global:request").to_bytes()[..8]);
+    discriminator
+}
+
+/// Anchor discriminator of ORAO's legacy v1 `Randomness` account
+fn randomness_discriminator() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(b"account:Randomness").to_bytes()[..8]);
+    discriminator
+}
+
+/// ORAO's legacy v1 `request` instruction
+fn request_instruction(
+    payer: Pubkey,
+    network_state: Pubkey,
+    treasury: Pubkey,
+    request: Pubkey,
+    system_program: Pubkey,
+    seed: [u8; 32],
+) -> Instruction {
+    let mut data = request_discriminator().to_vec();
+    data.extend_from_slice(&seed);
+
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(network_state, false),
+            AccountMeta::new(treasury, false),
+            AccountMeta::new(request, false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+        data,
+    }
+}
+
+/// Accounts for ORAO's `request` instruction
+pub struct OraoRequest<'info> {
+    /// Pays the ORAO fee and the randomness account rent
+    pub payer: AccountInfo<'info>,
+    /// ORAO network state
+    pub network_state: AccountInfo<'info>,
+    /// ORAO fee treasury
+    pub treasury: AccountInfo<'info>,
+    /// Randomness account derived from the request seed
+    pub request: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub orao_program: AccountInfo<'info>,
+}
+
+/// Randomness account written by ORAO
+#[derive(Clone, Debug)]
+pub struct OraoRandomness {
+    /// Seed the request was made with
+    pub seed: [u8; 32],
+    /// Aggregated randomness (all zero until fulfilled)
+    pub randomness: [u8; 64],
+}
+
+impl OraoRandomness {
+    /// Load the ORAO randomness account for `request`
+    pub fn load(account: &AccountInfo, request: &RandomnessRequest) -> Result<Self> {
+        RandomnessProvider::Orao.require_enabled()?;
+        require_keys_eq!(*account.owner, ID, RandomnessError::RandomnessAccountMismatch);
+        require_keys_eq!(
+            account.key(),
+            randomness_account_address(&request.seed),
+            RandomnessError::RandomnessAccountMismatch
+        );
+
+        let randomness = Self::try_from_data(&account.try_borrow_data()?)?;
+        require!(
+            randomness.seed == request.seed,
+            RandomnessError::RandomnessAccountMismatch
+        );
+
+        Ok(randomness)
+    }
+
+    /// Decode the fixed part of a randomness account
+    pub fn try_from_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= RANDOMNESS_HEADER_LEN && data[..8] == randomness_discriminator(),
+            RandomnessError::InvalidRandomnessAccount
+        );
+
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&data[8..40]);
+        let mut randomness = [0u8; 64];
+        randomness.copy_from_slice(&data[40..RANDOMNESS_HEADER_LEN]);
+
+        Ok(Self { seed, randomness })
+    }
+}
+
+impl<'info> RandomnessSource<'info> for OraoRandomness {
+    type Request = OraoRequest<'info>;
+
+    fn request(request: OraoRequest<'info>, seed: [u8; 32]) -> Result<RandomnessRequest> {
+        RandomnessProvider::Orao.require_enabled()?;
+        require_keys_eq!(request.orao_program.key(), ID, RandomnessError::RandomnessAccountMismatch);
+        require_keys_eq!(
+            request.network_state.key(),
+            network_state_address(),
+            RandomnessError::RandomnessAccountMismatch
+        );
+        require_keys_eq!(
+            request.request.key(),
+            randomness_account_address(&seed),
+            RandomnessError::RandomnessAccountMismatch
+        );
+
+        let instruction = request_instruction(
+            request.payer.key(),
+            request.network_state.key(),
+            request.treasury.key(),
+            request.request.key(),
+            request.system_program.key(),
+            seed,
+        );
+
+        invoke(
+            &instruction,
+            &[
+                request.payer,
+                request.network_state,
+                request.treasury,
+                request.request,
+                request.system_program,
+                request.orao_program,
+            ],
+        )?;
+
+        Ok(RandomnessRequest {
+            seed,
+            commitment: [0u8; 32],
+            slot: Clock::get()?.slot,
+        })
+    }
+
+    fn is_fulfilled(&self) -> bool {
+        self.randomness != [0u8; 64]
+    }
+
+    fn read_64(&self) -> Option<[u8; 64]> {
+        if self.is_fulfilled() {
+            Some(self.randomness)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_data(seed: [u8; 32], randomness: [u8; 64]) -> Vec<u8> {
+        let mut data = randomness_discriminator().to_vec();
+        data.extend_from_slice(&seed);
+        data.extend_from_slice(&randomness);
+        data.extend_from_slice(&0u32.to_le_bytes()); // empty responses
+        data
+    }
+
+    #[test]
+    fn test_decode_pending_and_fulfilled() {
+        let pending = OraoRandomness::try_from_data(&account_data([7u8; 32], [0u8; 64])).unwrap();
+        assert_eq!(pending.seed, [7u8; 32]);
+        assert!(!pending.is_fulfilled());
+        assert!(pending.read_64().is_none());
+
+        let fulfilled = OraoRandomness::try_from_data(&account_data([7u8; 32], [9u8; 64])).unwrap();
+        assert!(fulfilled.is_fulfilled());
+        assert_eq!(fulfilled.read_64().unwrap(), [9u8; 64]);
+        assert_eq!(fulfilled.read_32().unwrap(), [9u8; 32]);
+    }
+
+    #[test]
+    fn test_decode_rejects_foreign_data() {
+        let mut data = account_data([7u8; 32], [9u8; 64]);
+        data[0] ^= 1;
+        assert!(OraoRandomness::try_from_data(&data).is_err());
+
+        let short = account_data([7u8; 32], [9u8; 64])[..RANDOMNESS_HEADER_LEN - 1].to_vec();
+        assert!(OraoRandomness::try_from_data(&short).is_err());
+    }
+
+    #[test]
+    fn test_matches_orao_crate_encoding() {
+        use anchor_lang::solana_program::system_program;
+        use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+
+        assert_eq!(orao_solana_vrf::ID, ID);
+        assert_eq!(orao_solana_vrf::CONFIG_ACCOUNT_SEED, CONFIG_ACCOUNT_SEED);
+        assert_eq!(orao_solana_vrf::RANDOMNESS_ACCOUNT_SEED, RANDOMNESS_ACCOUNT_SEED);
+
+        let account = orao_solana_vrf::state::Randomness {
+            seed: [7u8; 32],
+            randomness: [9u8; 64],
+            responses: Vec::new(),
+        };
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        let decoded = OraoRandomness::try_from_data(&data).unwrap();
+        assert_eq!(decoded.seed, account.seed);
+        assert_eq!(decoded.randomness, account.randomness);
+
+        let seed = [5u8; 32];
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let instruction = request_instruction(keys[0], keys[1], keys[2], keys[3], system_program::ID, seed);
+        let accounts = orao_solana_vrf::accounts::Request {
+            payer: keys[0],
+            network_state: keys[1],
+            treasury: keys[2],
+            request: keys[3],
+            system_program: system_program::ID,
+        };
+        assert_eq!(instruction.accounts, accounts.to_account_metas(None));
+        assert_eq!(instruction.data, orao_solana_vrf::instruction::Request { seed }.data());
+    }
+
+    #[test]
+    fn test_randomness_address_depends_on_seed() {
+        assert_ne!(
+            randomness_account_address(&[1u8; 32]),
+            randomness_account_address(&[2u8; 32])
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Signed-oracle provider
+//!
+//! The oracle signs the request seed with ed25519 and submits the signature
+//! through a native Ed25519 program instruction in the same transaction.
+//! Ed25519 signatures are deterministic, so the oracle cannot grind for a
+//! favourable value; the randomness is derived from the signature. Whether the
+//! signing oracle is trusted is left to the consuming program.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::{expand_64, RandomnessError, RandomnessProvider, RandomnessRequest, RandomnessSource};
+
+/// Ed25519 instruction header: signature count and padding
+const HEADER_LEN: usize = 2;
+
+/// Ed25519 signature offsets entry: seven little-endian u16 values
+const OFFSETS_LEN: usize = 14;
+
+/// Instruction index meaning "data lives in this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Oracle signature over a request seed
+#[derive(Clone, Debug)]
+pub struct SignedOracleRandomness {
+    /// Oracle that signed the seed
+    pub oracle: Pubkey,
+    /// Ed25519 signature over the seed
+    pub signature: [u8; 64],
+}
+
+impl SignedOracleRandomness {
+    /// Find an Ed25519 instruction earlier in this transaction that verified a
+    /// signature over the request seed
+    pub fn load(instructions: &AccountInfo, request: &RandomnessRequest) -> Result<Self> {
+        RandomnessProvider::SignedOracle.require_enabled()?;
+        let current_index = load_current_index_checked(instructions)?;
+
+        for index in 0..current_index {
+            let instruction = load_instruction_at_checked(index as usize, instructions)?;
+            if instruction.program_id != ed25519_program::ID {
+                continue;
+            }
+            if let Some(signed) = Self::from_ed25519_data(&instruction.data, &request.seed) {
+                return Ok(signed);
+            }
+        }
+
+        err!(RandomnessError::MissingSignature)
+    }
+
+    /// Parse a single-signature Ed25519 instruction whose message is `seed`
+    pub fn from_ed25519_data(data: &[u8], seed: &[u8; 32]) -> Option<Self> {
+        if data.len() < HEADER_LEN + OFFSETS_LEN || data[0] != 1 {
+            return None;
+        }
+
+        let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+        let offsets = HEADER_LEN;
+        let signature_offset = read_u16(offsets) as usize;
+        let signature_instruction = read_u16(offsets + 2);
+        let public_key_offset = read_u16(offsets + 4) as usize;
+        let public_key_instruction = read_u16(offsets + 6);
+        let message_offset = read_u16(offsets + 8) as usize;
+        let message_size = read_u16(offsets + 10) as usize;
+        let message_instruction = read_u16(offsets + 12);
+
+        // Everything must be read from this instruction, not a neighbour
+        if signature_instruction != CURRENT_INSTRUCTION
+            || public_key_instruction != CURRENT_INSTRUCTION
+            || message_instruction != CURRENT_INSTRUCTION
+        {
+            return None;
+        }
+
+        let message = data.get(message_offset..message_offset.checked_add(message_size)?)?;
+        if message != seed.as_ref() {
+            return None;
+        }
+
+        let public_key = data.get(public_key_offset..public_key_offset.checked_add(32)?)?;
+        let signature_bytes = data.get(signature_offset..signature_offset.checked_add(64)?)?;
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(signature_bytes);
+
+        Some(Self {
+            oracle: Pubkey::try_from(public_key).ok()?,
+            signature,
+        })
+    }
+}
+
+impl<'info> RandomnessSource<'info> for SignedOracleRandomness {
+    /// The oracle answers off-chain from the emitted seed
+    type Request = ();
+
+    fn request(_request: (), seed: [u8; 32]) -> Result<RandomnessRequest> {
+        RandomnessProvider::SignedOracle.require_enabled()?;
+        Ok(RandomnessRequest {
+            seed,
+            commitment: [0u8; 32],
+            slot: Clock::get()?.slot,
+        })
+    }
+
+    fn is_fulfilled(&self) -> bool {
+        true
+    }
+
+    fn read_64(&self) -> Option<[u8; 64]> {
+        Some(expand_64(&[self.oracle.as_ref(), &self.signature]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lay out an Ed25519 instruction the way the Solana SDK does
+    fn ed25519_data(oracle: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+        let public_key_offset = HEADER_LEN + OFFSETS_LEN;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1u8, 0u8];
+        for value in [
+            signature_offset as u16,
+            CURRENT_INSTRUCTION,
+            public_key_offset as u16,
+            CURRENT_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(oracle.as_ref());
+        data.extend_from_slice(signature);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_signature_over_seed() {
+        let oracle = Pubkey::new_unique();
+        let seed = [3u8; 32];
+        let data = ed25519_data(&oracle, &[5u8; 64], &seed);
+
+        let signed = SignedOracleRandomness::from_ed25519_data(&data, &seed).unwrap();
+        assert_eq!(signed.oracle, oracle);
+        assert_eq!(signed.signature, [5u8; 64]);
+        assert!(signed.is_fulfilled());
+    }
+
+    #[test]
+    fn test_rejects_other_message() {
+        let data = ed25519_data(&Pubkey::new_unique(), &[5u8; 64], &[4u8; 32]);
+        assert!(SignedOracleRandomness::from_ed25519_data(&data, &[3u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_rejects_data_from_other_instruction() {
+        let seed = [3u8; 32];
+        let mut data = ed25519_data(&Pubkey::new_unique(), &[5u8; 64], &seed);
+        // Point the signature at instruction 0
+        data[HEADER_LEN + 2..HEADER_LEN + 4].copy_from_slice(&0u16.to_le_bytes());
+        assert!(SignedOracleRandomness::from_ed25519_data(&data, &seed).is_none());
+    }
+
+    #[test]
+    fn test_rejects_truncated_data() {
+        let seed = [3u8; 32];
+        let data = ed25519_data(&Pubkey::new_unique(), &[5u8; 64], &seed);
+        assert!(SignedOracleRandomness::from_ed25519_data(&data[..data.len() - 1], &seed).is_none());
+    }
+
+    #[test]
+    fn test_randomness_depends_on_signature() {
+        let oracle = Pubkey::new_unique();
+        let first = SignedOracleRandomness { oracle, signature: [1u8; 64] };
+        let second = SignedOracleRandomness { oracle, signature: [2u8; 64] };
+        assert_ne!(first.read_64(), second.read_64());
+    }
+}
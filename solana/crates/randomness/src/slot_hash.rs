@@ -0,0 +1,164 @@
+//! Slot-hash commit-reveal provider
+//!
+//! The operator commits to a secret when the request is opened and reveals it
+//! later; the randomness mixes the secret with the hash of the first slot after
+//! the commit. Neither side controls the result alone, but the operator can
+//! withhold a reveal and a leader can influence its slot hash, so this is only
+//! suitable for low-stakes draws.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar;
+use crate::{expand_64, RandomnessError, RandomnessProvider, RandomnessRequest, RandomnessSource};
+
+/// SlotHashes sysvar entry: slot followed by its bank hash
+const ENTRY_LEN: usize = 8 + 32;
+
+/// Commitment to a reveal secret
+pub fn commitment(secret: &[u8; 32]) -> [u8; 32] {
+    hash(secret).to_bytes()
+}
+
+/// Revealed slot-hash randomness
+#[derive(Clone, Debug)]
+pub struct SlotHashRandomness {
+    /// Slot whose hash was mixed in
+    pub slot: u64,
+    /// Derived randomness
+    pub randomness: [u8; 64],
+}
+
+impl SlotHashRandomness {
+    /// Check `secret` against the request commitment and mix it with the
+    /// first recorded slot hash after the commit slot
+    pub fn reveal(
+        slot_hashes: &AccountInfo,
+        request: &RandomnessRequest,
+        secret: &[u8; 32],
+    ) -> Result<Self> {
+        RandomnessProvider::SlotHash.require_enabled()?;
+        require_keys_eq!(
+            slot_hashes.key(),
+            sysvar::slot_hashes::ID,
+            RandomnessError::RandomnessAccountMismatch
+        );
+        require!(
+            commitment(secret) == request.commitment,
+            RandomnessError::CommitmentMismatch
+        );
+
+        let (slot, slot_hash) = Self::first_hash_after(&slot_hashes.try_borrow_data()?, request.slot)?;
+
+        Ok(Self {
+            slot,
+            randomness: expand_64(&[&request.seed, secret, &slot_hash]),
+        })
+    }
+
+    /// Earliest slot hash after `commit_slot` in raw SlotHashes data
+    ///
+    /// Entries are stored newest first. The history must still reach back to
+    /// `commit_slot`, otherwise the first slot after it may have been dropped.
+    pub fn first_hash_after(data: &[u8], commit_slot: u64) -> Result<(u64, [u8; 32])> {
+        require!(data.len() >= 8, RandomnessError::InvalidRandomnessAccount);
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(&data[..8]);
+        let entries = u64::from_le_bytes(len_bytes) as usize;
+        require!(
+            data.len() >= 8 + entries.saturating_mul(ENTRY_LEN),
+            RandomnessError::InvalidRandomnessAccount
+        );
+
+        let mut first_after = None;
+        let mut covers_commit = false;
+        for entry in data[8..8 + entries * ENTRY_LEN].chunks_exact(ENTRY_LEN) {
+            let mut slot_bytes = [0u8; 8];
+            slot_bytes.copy_from_slice(&entry[..8]);
+            let slot = u64::from_le_bytes(slot_bytes);
+
+            if slot <= commit_slot {
+                covers_commit = true;
+                break;
+            }
+
+            let mut slot_hash = [0u8; 32];
+            slot_hash.copy_from_slice(&entry[8..]);
+            first_after = Some((slot, slot_hash));
+        }
+
+        let found = first_after.ok_or(RandomnessError::RevealTooEarly)?;
+        require!(covers_commit, RandomnessError::RevealExpired);
+
+        Ok(found)
+    }
+}
+
+impl<'info> RandomnessSource<'info> for SlotHashRandomness {
+    /// Commitment to the operator's secret
+    type Request = [u8; 32];
+
+    fn request(commitment: [u8; 32], seed: [u8; 32]) -> Result<RandomnessRequest> {
+        RandomnessProvider::SlotHash.require_enabled()?;
+        require!(commitment != [0u8; 32], RandomnessError::InvalidCommitment);
+
+        Ok(RandomnessRequest {
+            seed,
+            commitment,
+            slot: Clock::get()?.slot,
+        })
+    }
+
+    fn is_fulfilled(&self) -> bool {
+        true
+    }
+
+    fn read_64(&self) -> Option<[u8; 64]> {
+        Some(self.randomness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Raw SlotHashes data for the given slots, newest first
+    fn slot_hashes_data(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_first_hash_after_commit() {
+        // Slot 103 was skipped
+        let data = slot_hashes_data(&[105, 104, 102, 101, 100]);
+        let (slot, slot_hash) = SlotHashRandomness::first_hash_after(&data, 101).unwrap();
+        assert_eq!(slot, 102);
+        assert_eq!(slot_hash, [102u8; 32]);
+
+        let (slot, _) = SlotHashRandomness::first_hash_after(&data, 102).unwrap();
+        assert_eq!(slot, 104);
+    }
+
+    #[test]
+    fn test_reveal_too_early() {
+        let data = slot_hashes_data(&[105, 104]);
+        assert!(SlotHashRandomness::first_hash_after(&data, 105).is_err());
+    }
+
+    #[test]
+    fn test_reveal_expired() {
+        // History no longer reaches back to the commit slot
+        let data = slot_hashes_data(&[105, 104, 103]);
+        assert!(SlotHashRandomness::first_hash_after(&data, 100).is_err());
+    }
+
+    #[test]
+    fn test_commitment_binds_secret() {
+        assert_eq!(commitment(&[1u8; 32]), commitment(&[1u8; 32]));
+        assert_ne!(commitment(&[1u8; 32]), commitment(&[2u8; 32]));
+    }
+}
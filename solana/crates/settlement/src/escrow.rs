@@ -0,0 +1,131 @@
+//! Transfers in and out of program-owned escrow
+//!
+//! Lamport escrows are PDAs that carry data, so the system program cannot
+//! debit them; lamports are moved directly instead. SPL escrows are token
+//! accounts whose authority is a PDA, so withdrawals sign with its seeds.
+//! Callers are responsible for validating every account they pass in.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+
+use crate::SettlementError;
+
+/// Move lamports out of a PDA owned by the calling program
+pub fn transfer_lamports_from_pda<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let from_balance = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(error!(SettlementError::InsufficientLamports))?;
+    let to_balance = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(error!(SettlementError::Overflow))?;
+
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+
+    Ok(())
+}
+
+/// Move SPL tokens out of an escrow token account whose authority is a PDA
+pub fn transfer_tokens_from_pda<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let accounts = Transfer {
+        from: from.clone(),
+        to: to.clone(),
+        authority: authority.clone(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.clone(), accounts, signer_seeds),
+        amount,
+    )
+}
+
+/// Move SPL tokens from a signer's token account into escrow
+pub fn transfer_tokens_to_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let accounts = Transfer {
+        from: from.clone(),
+        to: to.clone(),
+        authority: authority.clone(),
+    };
+    token::transfer(CpiContext::new(token_program.clone(), accounts), amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn move_lamports(from_start: u64, to_start: u64, amount: u64) -> (Result<()>, u64, u64) {
+        let owner = Pubkey::new_unique();
+        let (from_key, to_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut from_lamports, mut to_lamports) = (from_start, to_start);
+        let (mut from_data, mut to_data) = (vec![0u8; 8], vec![]);
+        let from = AccountInfo::new(&from_key, false, true, &mut from_lamports, &mut from_data, &owner, false, 0);
+        let to = AccountInfo::new(&to_key, false, true, &mut to_lamports, &mut to_data, &owner, false, 0);
+
+        let result = transfer_lamports_from_pda(&from, &to, amount);
+        let balances = (from.lamports(), to.lamports());
+        (result, balances.0, balances.1)
+    }
+
+    #[test]
+    fn test_transfer_lamports_from_pda() {
+        let (result, from, to) = move_lamports(1_000, 50, 400);
+        assert!(result.is_ok());
+        assert_eq!((from, to), (600, 450));
+
+        // Zero is a no-op
+        let (result, from, to) = move_lamports(1_000, 50, 0);
+        assert!(result.is_ok());
+        assert_eq!((from, to), (1_000, 50));
+    }
+
+    #[test]
+    fn test_failed_transfer_leaves_balances() {
+        let (result, from, to) = move_lamports(600, 50, 601);
+        assert_eq!(result.unwrap_err(), SettlementError::InsufficientLamports.into());
+        assert_eq!((from, to), (600, 50));
+
+        let (result, from, to) = move_lamports(600, u64::MAX, 1);
+        assert_eq!(result.unwrap_err(), SettlementError::Overflow.into());
+        assert_eq!((from, to), (600, u64::MAX));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_transfer_conserves_lamports(from in any::<u32>(), to in any::<u32>(), amount in any::<u32>()) {
+            let (result, from_after, to_after) = move_lamports(from as u64, to as u64, amount as u64);
+            prop_assert_eq!(result.is_ok(), amount <= from);
+            prop_assert_eq!(from_after + to_after, from as u64 + to as u64);
+        }
+    }
+}
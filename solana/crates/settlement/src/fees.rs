@@ -0,0 +1,152 @@
+//! Basis-point fee math
+//!
+//! Products are taken in `u128`, so `amount * bps` cannot overflow for any
+//! `u64` amount. Callers pick the rounding direction explicitly; the
+//! platform-side split ([`split_fee`]) always rounds the fee down so players
+//! are never charged more than the advertised rate.
+
+use anchor_lang::prelude::*;
+
+use crate::SettlementError;
+
+/// One hundred percent in basis points
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Direction to round a fractional fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Rounding {
+    /// Truncate towards zero (in the payer's favour)
+    #[default]
+    Down,
+    /// Round any remainder up (in the collector's favour)
+    Up,
+}
+
+/// Fee and remaining amount after a basis-point split
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct FeeSplit {
+    /// Amount owed to the fee collector
+    pub fee: u64,
+    /// Amount left for the recipient; `fee + net` equals the input
+    pub net: u64,
+}
+
+/// Fail unless `bps` is at most [`BPS_DENOMINATOR`]
+pub fn validate_bps(bps: u16) -> Result<()> {
+    require!(bps <= BPS_DENOMINATOR, SettlementError::InvalidBasisPoints);
+    Ok(())
+}
+
+/// `amount * bps / 10_000`, rounded as requested
+pub fn bps_fee(amount: u64, bps: u16, rounding: Rounding) -> Result<u64> {
+    validate_bps(bps)?;
+
+    let product = amount as u128 * bps as u128;
+    let denominator = BPS_DENOMINATOR as u128;
+    let fee = match rounding {
+        Rounding::Down => product / denominator,
+        Rounding::Up => product.div_ceil(denominator),
+    };
+
+    // bps <= 10_000 keeps the fee at or below `amount`
+    u64::try_from(fee).map_err(|_| error!(SettlementError::Overflow))
+}
+
+/// Split `amount` into a rounded-down fee and the remainder
+pub fn split_fee(amount: u64, bps: u16) -> Result<FeeSplit> {
+    let fee = bps_fee(amount, bps, Rounding::Down)?;
+    let net = amount
+        .checked_sub(fee)
+        .ok_or(error!(SettlementError::Overflow))?;
+    Ok(FeeSplit { fee, net })
+}
+
+/// Convert a whole percentage into basis points
+pub fn percent_to_bps(percent: u8) -> Result<u16> {
+    let bps = percent as u16 * 100;
+    validate_bps(bps)?;
+    Ok(bps)
+}
+
+/// Divide `amount` evenly between `shares` recipients
+///
+/// Returns the per-share amount and the undistributed remainder, which is
+/// always less than `shares`. The caller decides where the dust goes.
+pub fn split_evenly(amount: u64, shares: u64) -> Result<(u64, u64)> {
+    require!(shares > 0, SettlementError::EmptyRange);
+    Ok((amount / shares, amount % shares))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_bps_fee_rounding() {
+        assert_eq!(bps_fee(1_000, 250, Rounding::Down).unwrap(), 25);
+        assert_eq!(bps_fee(1_000, 250, Rounding::Up).unwrap(), 25);
+        assert_eq!(bps_fee(999, 250, Rounding::Down).unwrap(), 24);
+        assert_eq!(bps_fee(999, 250, Rounding::Up).unwrap(), 25);
+        assert_eq!(bps_fee(1, 1, Rounding::Down).unwrap(), 0);
+        assert_eq!(bps_fee(1, 1, Rounding::Up).unwrap(), 1);
+        assert_eq!(bps_fee(0, 500, Rounding::Up).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_bps_fee_does_not_overflow() {
+        assert_eq!(bps_fee(u64::MAX, BPS_DENOMINATOR, Rounding::Down).unwrap(), u64::MAX);
+        assert_eq!(bps_fee(u64::MAX, 5_000, Rounding::Down).unwrap(), u64::MAX / 2);
+        assert_eq!(bps_fee(u64::MAX, 5_000, Rounding::Up).unwrap(), u64::MAX / 2 + 1);
+    }
+
+    #[test]
+    fn test_invalid_bps_rejected() {
+        assert!(bps_fee(100, 10_001, Rounding::Down).is_err());
+        assert!(split_fee(100, u16::MAX).is_err());
+        assert!(percent_to_bps(101).is_err());
+        assert_eq!(percent_to_bps(10).unwrap(), 1_000);
+        assert_eq!(percent_to_bps(100).unwrap(), BPS_DENOMINATOR);
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        assert_eq!(split_evenly(100, 3).unwrap(), (33, 1));
+        assert_eq!(split_evenly(2, 5).unwrap(), (0, 2));
+        assert!(split_evenly(100, 0).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_split_fee_conserves_amount(amount in any::<u64>(), bps in 0u16..=BPS_DENOMINATOR) {
+            let split = split_fee(amount, bps).unwrap();
+            prop_assert_eq!(split.fee as u128 + split.net as u128, amount as u128);
+            prop_assert!(split.fee <= amount);
+        }
+
+        #[test]
+        fn prop_fee_matches_exact_rate(amount in any::<u64>(), bps in 0u16..=BPS_DENOMINATOR) {
+            let down = bps_fee(amount, bps, Rounding::Down).unwrap() as u128;
+            let up = bps_fee(amount, bps, Rounding::Up).unwrap() as u128;
+            let exact = amount as u128 * bps as u128;
+            prop_assert!(down * 10_000 <= exact);
+            prop_assert!(up * 10_000 >= exact);
+            prop_assert!(up - down <= 1);
+        }
+
+        #[test]
+        fn prop_fee_monotonic_in_bps(amount in any::<u64>(), a in 0u16..=BPS_DENOMINATOR, b in 0u16..=BPS_DENOMINATOR) {
+            let (lo, hi) = (a.min(b), a.max(b));
+            prop_assert!(
+                bps_fee(amount, lo, Rounding::Down).unwrap() <= bps_fee(amount, hi, Rounding::Down).unwrap()
+            );
+        }
+
+        #[test]
+        fn prop_split_evenly_conserves_amount(amount in any::<u64>(), shares in 1u64..=1_000) {
+            let (each, remainder) = split_evenly(amount, shares).unwrap();
+            prop_assert!(remainder < shares);
+            prop_assert_eq!(each as u128 * shares as u128 + remainder as u128, amount as u128);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Settlement helpers shared by the lottery and raffle programs
+//!
+//! - [`fees`]: basis-point fee math in `u128` with an explicit [`fees::Rounding`]
+//! - [`range`]: unbiased mapping of VRF output onto ticket and number ranges
+//! - [`escrow`]: lamport and SPL transfers out of program-owned escrow PDAs
+//!
+//! Every helper returns [`SettlementError`] instead of panicking, so handlers
+//! can propagate failures with `?`.
+
+use anchor_lang::prelude::*;
+
+pub mod escrow;
+pub mod fees;
+pub mod range;
+
+#[error_code(offset = 7100)]
+pub enum SettlementError {
+    #[msg("Settlement arithmetic overflowed")]
+    Overflow,
+
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBasisPoints,
+
+    #[msg("Range must contain at least one value")]
+    EmptyRange,
+
+    #[msg("At least 8 bytes of randomness are required")]
+    NotEnoughRandomness,
+
+    #[msg("Escrow does not hold enough lamports")]
+    InsufficientLamports,
+}
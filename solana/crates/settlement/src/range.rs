@@ -0,0 +1,128 @@
+//! Unbiased mapping of randomness onto integer ranges
+//!
+//! Each 8-byte little-endian lane is mapped into `[0, range)` with a 128-bit
+//! widening multiply (Lemire). Lanes landing in the over-represented zone are
+//! rejected and the next lane is tried, so every value is equally likely.
+//! Taking `word % range` instead would favour the low values whenever `range`
+//! does not divide 2^64.
+
+use anchor_lang::prelude::*;
+
+use crate::SettlementError;
+
+/// Draw an index in `[0, range)` from `randomness`
+///
+/// Uses as many 8-byte lanes as needed; trailing bytes shorter than a lane are
+/// ignored. If every lane is rejected (probability below `(range / 2^64)^lanes`)
+/// the last lane's value is used.
+pub fn uniform_index(randomness: &[u8], range: u64) -> Result<u64> {
+    require!(range > 0, SettlementError::EmptyRange);
+    require!(randomness.len() >= 8, SettlementError::NotEnoughRandomness);
+
+    // 2^64 mod range: products whose low half falls below this are biased
+    let threshold = range.wrapping_neg() % range;
+
+    let mut index = 0u64;
+    for lane in randomness.chunks_exact(8) {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(lane);
+        let product = u64::from_le_bytes(bytes) as u128 * range as u128;
+
+        index = (product >> 64) as u64;
+        if product as u64 >= threshold {
+            return Ok(index);
+        }
+    }
+
+    Ok(index)
+}
+
+/// Draw a value in the inclusive range `[min, max]` from `randomness`
+pub fn uniform_in_range(randomness: &[u8], min: u64, max: u64) -> Result<u64> {
+    require!(min <= max, SettlementError::EmptyRange);
+
+    match (max - min).checked_add(1) {
+        Some(range) => Ok(min + uniform_index(randomness, range)?),
+        // Full u64 domain: every word is already uniform
+        None => {
+            require!(randomness.len() >= 8, SettlementError::NotEnoughRandomness);
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&randomness[..8]);
+            Ok(u64::from_le_bytes(bytes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn lanes(words: &[u64]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_uniform_index_edges() {
+        assert_eq!(uniform_index(&lanes(&[0]), 10).unwrap(), 0);
+        assert_eq!(uniform_index(&lanes(&[u64::MAX]), 10).unwrap(), 9);
+        assert_eq!(uniform_index(&lanes(&[1 << 63]), 10).unwrap(), 5);
+        assert_eq!(uniform_index(&lanes(&[u64::MAX]), 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_uniform_index_rejects_biased_lane() {
+        // range 3: threshold = 2^64 mod 3 = 1, so word 0 is rejected
+        assert_eq!(uniform_index(&lanes(&[0, u64::MAX]), 3).unwrap(), 2);
+        // When every lane is rejected the last one is used
+        assert_eq!(uniform_index(&lanes(&[0, 0]), 3).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(uniform_index(&lanes(&[5]), 0).is_err());
+        assert!(uniform_index(&[0u8; 7], 10).is_err());
+        assert!(uniform_in_range(&lanes(&[5]), 6, 5).is_err());
+    }
+
+    #[test]
+    fn test_uniform_in_range_full_domain() {
+        assert_eq!(uniform_in_range(&lanes(&[42]), 0, u64::MAX).unwrap(), 42);
+        assert_eq!(uniform_in_range(&lanes(&[u64::MAX]), 1, 6).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_uniform_index_exact_distribution() {
+        // Sweep the top byte of the word; each value in a range dividing 256
+        // is hit equally often
+        let mut counts = [0u32; 8];
+        for top in 0..=255u64 {
+            let word = (top << 56) | 0x00A5_A5A5_A5A5_A5A5;
+            counts[uniform_index(&lanes(&[word]), 8).unwrap() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| count == 32));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_uniform_index_in_bounds(bytes in proptest::collection::vec(any::<u8>(), 8..=64), range in 1u64..) {
+            prop_assert!(uniform_index(&bytes, range).unwrap() < range);
+        }
+
+        #[test]
+        fn prop_uniform_in_range_in_bounds(bytes in any::<[u8; 64]>(), min in 0u64..1_000, span in 0u64..1_000) {
+            let value = uniform_in_range(&bytes, min, min + span).unwrap();
+            prop_assert!((min..=min + span).contains(&value));
+        }
+
+        #[test]
+        fn prop_uniform_index_monotonic_in_word(a in any::<u64>(), b in any::<u64>(), range in 1u64..=1_000_000) {
+            // Accepted lanes map larger words to equal or larger indices
+            let (lo, hi) = (a.min(b), a.max(b));
+            let threshold = range.wrapping_neg() % range;
+            let accepted = |word: u64| (word as u128 * range as u128) as u64 >= threshold;
+            prop_assume!(accepted(lo) && accepted(hi));
+            prop_assert!(uniform_index(&lanes(&[lo]), range).unwrap() <= uniform_index(&lanes(&[hi]), range).unwrap());
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! One keeper pass over every watched game
+//!
+//! The keeper holds no state of its own: each pass lists the games, reads
+//! each game's [`GameSnapshot`] and submits the single instruction its
+//! [`CrankStep`] calls for. A restarted keeper therefore resumes exactly where
+//! the chain is. Before every retry the snapshot is read again, and the retry
+//! is dropped if the step changed, so a send that timed out but landed is
+//! never submitted twice.
+
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, warn};
+use lottery_client::state::{GameStatus, RandomnessProvider};
+use lottery_client::{pda, CrankStep, GameSnapshot, LotteryClient, LotteryRpc, Result};
+use rand::Rng;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+
+#[derive(Clone, Debug)]
+pub struct KeeperConfig {
+    /// Delay between passes
+    pub poll_interval: Duration,
+    /// Priority fee in micro-lamports per compute unit (0 = none)
+    pub compute_unit_price: u64,
+    /// Compute unit limit requested per transaction
+    pub compute_unit_limit: Option<u32>,
+    /// Resends of a failed step within one pass
+    pub max_retries: u32,
+    /// Backoff before the first resend; grows linearly per attempt
+    pub retry_delay: Duration,
+    /// Game ids to drive; empty drives every game
+    pub games: Vec<String>,
+    /// Fulfill ORAO requests through the mock VRF program (localnet only)
+    pub mock_vrf: bool,
+}
+
+impl Default for KeeperConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(2),
+            compute_unit_price: 0,
+            compute_unit_limit: None,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            games: Vec::new(),
+            mock_vrf: false,
+        }
+    }
+}
+
+/// What the keeper submits for a game
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Crank(CrankStep),
+    /// Fulfill the pending round's request through the mock VRF
+    MockFulfill {
+        round: u8,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Crank(step) => write!(f, "{}", step),
+            Action::MockFulfill { round } => write!(f, "mock fulfill round {}", round),
+        }
+    }
+}
+
+/// Result of driving one game for one pass
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Submitted {
+        action: Action,
+        signature: Signature,
+        attempts: u32,
+    },
+    /// Nothing to submit
+    Idle(Action),
+    /// The step changed between attempts; the next pass picks it up
+    Superseded { expected: Action, found: Action },
+    Failed {
+        action: Action,
+        attempts: u32,
+        error: String,
+    },
+    /// Reading the game's state failed
+    Error(String),
+}
+
+pub struct Keeper<R> {
+    client: LotteryClient<R>,
+    operator: Keypair,
+    config: KeeperConfig,
+}
+
+impl<R: LotteryRpc> Keeper<R> {
+    pub fn new(client: LotteryClient<R>, operator: Keypair, config: KeeperConfig) -> Self {
+        Self {
+            client,
+            operator,
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &KeeperConfig {
+        &self.config
+    }
+
+    /// Create the mock VRF network state with the operator as treasury and
+    /// sole fulfillment authority, unless it exists
+    pub fn init_mock_vrf(&self) -> Result<Option<Signature>> {
+        let network_state = mock_orao::client::network_state_address();
+        if self
+            .client
+            .rpc()
+            .get_account_data(&network_state)?
+            .is_some()
+        {
+            return Ok(None);
+        }
+        let operator = self.operator.pubkey();
+        let initialize = mock_orao::client::initialize(operator, operator, 0, vec![operator]);
+        self.client.send(&[initialize], &[&self.operator]).map(Some)
+    }
+
+    /// Ids of the games to drive this pass, skipping settled games
+    fn game_ids(&self) -> Result<Vec<String>> {
+        if !self.config.games.is_empty() {
+            return Ok(self.config.games.clone());
+        }
+        Ok(self
+            .client
+            .all_games()?
+            .into_iter()
+            .filter(|(_, game)| {
+                // Terminal states; `next_step` would return `Done`
+                !matches!(
+                    game.state,
+                    GameStatus::Distributing | GameStatus::Completed | GameStatus::Cancelled
+                )
+            })
+            .map(|(_, game)| game.game_id)
+            .collect())
+    }
+
+    /// Advance every watched game by at most one step
+    ///
+    /// A failure on one game is reported in its outcome and does not stop
+    /// the others; only failing to list the games fails the pass.
+    pub fn tick(&self) -> Result<Vec<(String, Outcome)>> {
+        let mut outcomes = Vec::new();
+        for game_id in self.game_ids()? {
+            let outcome = self
+                .advance(&game_id)
+                .unwrap_or_else(|error| Outcome::Error(error.to_string()));
+            outcomes.push((game_id, outcome));
+        }
+        Ok(outcomes)
+    }
+
+    /// Submit the game's next step, retrying while the chain still calls for it
+    pub fn advance(&self, game_id: &str) -> Result<Outcome> {
+        let mut attempts = 0;
+        let mut expected = None;
+        loop {
+            let snapshot = match self.client.snapshot(game_id)? {
+                Some(snapshot) => snapshot,
+                None => return Ok(Outcome::Idle(Action::Crank(CrankStep::Done))),
+            };
+            let action = self.action(&snapshot);
+            if let Some(expected) = expected.filter(|expected| *expected != action) {
+                return Ok(Outcome::Superseded {
+                    expected,
+                    found: action,
+                });
+            }
+            let instruction = match self.instruction(&snapshot, action)? {
+                Some(instruction) => instruction,
+                None => return Ok(Outcome::Idle(action)),
+            };
+
+            attempts += 1;
+            match self.submit(instruction) {
+                Ok(signature) => {
+                    return Ok(Outcome::Submitted {
+                        action,
+                        signature,
+                        attempts,
+                    })
+                }
+                Err(error) if attempts > self.config.max_retries => {
+                    return Ok(Outcome::Failed {
+                        action,
+                        attempts,
+                        error: error.to_string(),
+                    })
+                }
+                Err(error) => {
+                    warn!(
+                        "{}: {} failed (attempt {}): {}",
+                        game_id, action, attempts, error
+                    );
+                    thread::sleep(self.config.retry_delay * attempts);
+                    expected = Some(action);
+                }
+            }
+        }
+    }
+
+    fn action(&self, snapshot: &GameSnapshot) -> Action {
+        let game = &snapshot.game;
+        let awaiting_orao = game.state == GameStatus::Playing
+            && !snapshot.play_paused
+            && game.vrf_request_pending
+            && game.randomness_provider == RandomnessProvider::Orao
+            && !snapshot.randomness_fulfilled;
+        if self.config.mock_vrf && awaiting_orao {
+            return Action::MockFulfill {
+                round: game.pending_round,
+            };
+        }
+        Action::Crank(snapshot.next_step())
+    }
+
+    fn instruction(&self, snapshot: &GameSnapshot, action: Action) -> Result<Option<Instruction>> {
+        let operator = self.operator.pubkey();
+        match action {
+            Action::Crank(_) => self.client.crank_instruction(&operator, snapshot),
+            Action::MockFulfill { round } => {
+                let seed = pda::round_seed(&snapshot.game.game_id, round);
+                let mut randomness = [0u8; 64];
+                rand::thread_rng().fill(&mut randomness[..]);
+                // The mock rejects all-zero randomness
+                randomness[0] |= 1;
+                Ok(Some(mock_orao::client::fulfill(operator, seed, randomness)))
+            }
+        }
+    }
+
+    /// Send with the configured compute budget prepended
+    fn submit(&self, instruction: Instruction) -> Result<Signature> {
+        let mut instructions = Vec::with_capacity(3);
+        if let Some(units) = self.config.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if self.config.compute_unit_price > 0 {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                self.config.compute_unit_price,
+            ));
+        }
+        instructions.push(instruction);
+        debug!("submitting {} instruction(s)", instructions.len());
+        self.client.send(&instructions, &[&self.operator])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    use anchor_lang::AccountSerialize;
+    use lottery_client::instruction;
+    use lottery_client::rpc::mock::MockRpc;
+    use lottery_client::state::{GameState, NumberRange, Player, PlayerList};
+    use lottery_client::ClientError;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::transaction::Transaction;
+
+    fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn game(game_id: &str, state: GameStatus) -> GameState {
+        GameState {
+            game_id: game_id.to_string(),
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            entry_fee: 1_000,
+            max_players: 10,
+            winner_count: 1,
+            state,
+            prize_pool: 3_000,
+            treasury_fee: 300,
+            fee_bps: 1_000,
+            number_range: NumberRange { min: 1, max: 10 },
+            created_at: 0,
+            started_at: None,
+            completed_at: None,
+            payment_deadline: 600,
+            current_round: 0,
+            drawn_numbers: vec![],
+            token_mint: Pubkey::new_unique(),
+            escrow_account: Pubkey::new_unique(),
+            vrf_oracle: Pubkey::new_unique(),
+            vrf_request_pending: false,
+            pending_round: 0,
+            randomness_provider: RandomnessProvider::Orao,
+            randomness_request: None,
+            bump: 255,
+        }
+    }
+
+    fn players(game_id: &str, numbers: &[u8]) -> PlayerList {
+        PlayerList {
+            game_id: game_id.to_string(),
+            players: numbers
+                .iter()
+                .map(|number| Player {
+                    wallet: Pubkey::new_unique(),
+                    telegram_id: "tg".to_string(),
+                    selected_number: Some(*number),
+                    eliminated_round: None,
+                    is_winner: false,
+                    prize_claimed: false,
+                    prize_amount: 0,
+                    joined_at: 0,
+                })
+                .collect(),
+            bump: 255,
+        }
+    }
+
+    fn set_game(rpc: &MockRpc, state: &GameState) {
+        rpc.set_account(pda::game(&state.game_id), serialize(state));
+        rpc.set_account(
+            pda::player_list(&state.game_id),
+            serialize(&players(&state.game_id, &[1, 2, 3])),
+        );
+    }
+
+    /// Game waiting on ORAO for round 1
+    fn pending_round(game_id: &str) -> GameState {
+        let mut state = game(game_id, GameStatus::Playing);
+        state.vrf_request_pending = true;
+        state.pending_round = 1;
+        state
+    }
+
+    fn new_keeper<R: LotteryRpc>(rpc: R, config: KeeperConfig) -> Keeper<R> {
+        Keeper::new(LotteryClient::new(rpc), Keypair::new(), config)
+    }
+
+    /// Fails the first `failures` sends, running `on_failure` on the state
+    /// each time
+    struct FlakyRpc {
+        inner: MockRpc,
+        failures: Cell<u32>,
+        on_failure: fn(&MockRpc),
+    }
+
+    impl LotteryRpc for FlakyRpc {
+        fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+            self.inner.get_account_data(address)
+        }
+
+        fn get_program_account_data(
+            &self,
+            filters: Vec<solana_client::rpc_filter::RpcFilterType>,
+        ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+            self.inner.get_program_account_data(filters)
+        }
+
+        fn get_latest_blockhash(&self) -> Result<Hash> {
+            self.inner.get_latest_blockhash()
+        }
+
+        fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+            if self.failures.get() > 0 {
+                self.failures.set(self.failures.get() - 1);
+                (self.on_failure)(&self.inner);
+                return Err(ClientError::Rpc("blockhash expired".to_string()));
+            }
+            self.inner.send_and_confirm_transaction(transaction)
+        }
+
+        fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>> {
+            self.inner.get_transaction_logs(signature)
+        }
+    }
+
+    fn flaky(failures: u32, on_failure: fn(&MockRpc)) -> FlakyRpc {
+        let inner = MockRpc::new();
+        set_game(&inner, &game("g1", GameStatus::NumberSelection));
+        FlakyRpc {
+            inner,
+            failures: Cell::new(failures),
+            on_failure,
+        }
+    }
+
+    fn no_delay() -> KeeperConfig {
+        KeeperConfig {
+            retry_delay: Duration::ZERO,
+            ..KeeperConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_tick_submits_next_step_with_priority_fee() {
+        let rpc = MockRpc::new();
+        set_game(&rpc, &game("g1", GameStatus::NumberSelection));
+        set_game(&rpc, &game("done", GameStatus::Completed));
+        set_game(&rpc, &game("joining", GameStatus::Joining));
+        let config = KeeperConfig {
+            compute_unit_price: 5_000,
+            compute_unit_limit: Some(200_000),
+            ..no_delay()
+        };
+        let keeper = new_keeper(rpc, config);
+
+        let outcomes = keeper.tick().unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].0, "g1");
+        assert!(matches!(
+            outcomes[0].1,
+            Outcome::Submitted {
+                action: Action::Crank(CrankStep::StartGame),
+                attempts: 1,
+                ..
+            }
+        ));
+        assert_eq!(
+            outcomes[1].1,
+            Outcome::Idle(Action::Crank(CrankStep::Wait("players joining")))
+        );
+
+        let sent = keeper.client.rpc().sent();
+        assert_eq!(sent.len(), 1);
+        let message = &sent[0].message;
+        assert_eq!(message.instructions.len(), 3);
+        let operator = keeper.operator.pubkey();
+        let start = instruction::start_game(&operator, "g1");
+        let program = message.program_id(2).unwrap();
+        assert_eq!(*program, start.program_id);
+        assert_eq!(message.instructions[2].data, start.data);
+        assert_eq!(
+            message.instructions[1].data,
+            ComputeBudgetInstruction::set_compute_unit_price(5_000).data
+        );
+    }
+
+    #[test]
+    fn test_resumes_from_chain_state() {
+        let rpc = MockRpc::new();
+        set_game(&rpc, &pending_round("g1"));
+        let keeper = new_keeper(rpc, no_delay());
+        assert_eq!(
+            keeper.advance("g1").unwrap(),
+            Outcome::Idle(Action::Crank(CrankStep::Wait("awaiting ORAO fulfillment")))
+        );
+
+        // A fresh keeper over a fulfilled request picks up at fulfillment
+        let seed = pda::round_seed("g1", 1);
+        let mut data = solana_sdk::hash::hash(b"account:Randomness").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&seed);
+        data.extend_from_slice(&[7u8; 64]);
+        let rpc = MockRpc::new();
+        set_game(&rpc, &pending_round("g1"));
+        rpc.set_account(pda::orao_randomness("g1", 1), data);
+        let keeper = new_keeper(rpc, no_delay());
+        assert!(matches!(
+            keeper.advance("g1").unwrap(),
+            Outcome::Submitted {
+                action: Action::Crank(CrankStep::FulfillRandomness { round: 1 }),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_mock_vrf_fulfills_pending_request() {
+        let rpc = MockRpc::new();
+        set_game(&rpc, &pending_round("g1"));
+        let config = KeeperConfig {
+            mock_vrf: true,
+            ..no_delay()
+        };
+        let keeper = new_keeper(rpc, config);
+
+        assert!(matches!(
+            keeper.advance("g1").unwrap(),
+            Outcome::Submitted {
+                action: Action::MockFulfill { round: 1 },
+                ..
+            }
+        ));
+        let sent = keeper.client.rpc().sent();
+        let message = &sent[0].message;
+        assert_eq!(*message.program_id(0).unwrap(), mock_orao::ID);
+        let request = mock_orao::client::randomness_address(&pda::round_seed("g1", 1));
+        assert!(message.account_keys.contains(&request));
+    }
+
+    #[test]
+    fn test_init_mock_vrf_is_idempotent() {
+        let keeper = new_keeper(MockRpc::new(), no_delay());
+        assert!(keeper.init_mock_vrf().unwrap().is_some());
+        keeper
+            .client
+            .rpc()
+            .set_account(mock_orao::client::network_state_address(), vec![0u8; 8]);
+        assert!(keeper.init_mock_vrf().unwrap().is_none());
+        assert_eq!(keeper.client.rpc().sent().len(), 1);
+    }
+
+    #[test]
+    fn test_retries_until_sent() {
+        let keeper = new_keeper(flaky(2, |_| {}), no_delay());
+        assert!(matches!(
+            keeper.advance("g1").unwrap(),
+            Outcome::Submitted { attempts: 3, .. }
+        ));
+        assert_eq!(keeper.client.rpc().inner.sent().len(), 1);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_retries() {
+        let config = KeeperConfig {
+            max_retries: 1,
+            ..no_delay()
+        };
+        let keeper = new_keeper(flaky(5, |_| {}), config);
+        assert!(matches!(
+            keeper.advance("g1").unwrap(),
+            Outcome::Failed { attempts: 2, .. }
+        ));
+        assert!(keeper.client.rpc().inner.sent().is_empty());
+    }
+
+    #[test]
+    fn test_retry_dropped_when_step_landed() {
+        // The failed send actually landed: the game moved on to round 1
+        let keeper = new_keeper(
+            flaky(1, |rpc| set_game(rpc, &game("g1", GameStatus::Playing))),
+            no_delay(),
+        );
+        assert_eq!(
+            keeper.advance("g1").unwrap(),
+            Outcome::Superseded {
+                expected: Action::Crank(CrankStep::StartGame),
+                found: Action::Crank(CrankStep::RequestRandomness { round: 1 }),
+            }
+        );
+        assert!(keeper.client.rpc().inner.sent().is_empty());
+    }
+}
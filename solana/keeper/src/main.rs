@@ -0,0 +1,254 @@
+//! Keeper daemon for `telegram_lottery` games
+//!
+//! Polls every game (or those given with `--game`) and submits the next legal
+//! operator instruction: `start_game`, then per round `request_orao_vrf` →
+//! `fulfill_orao_vrf` → `process_elimination`, and finally `complete_game`.
+//! Progress is read from chain state on every pass, so the keeper can be
+//! stopped and restarted at any point, and several keepers can run side by
+//! side. The keypair must hold the lottery's game operator role.
+//!
+//! For a local validator, load the mock VRF program at the ORAO id and pass
+//! `--mock-vrf`; the keeper then creates the mock network state if missing
+//! and fulfills its own randomness requests:
+//!
+//! ```text
+//! solana-test-validator \
+//!     --bpf-program EP4Tr2vPKcQU5WD7mng8kM56pttU97igHep5Y23ZFEuJ target/deploy/telegram_lottery.so \
+//!     --bpf-program VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y target/deploy/mock_orao.so
+//! lottery-keeper --url localhost --mock-vrf
+//! ```
+//!
+//! Set `RUST_LOG=debug` to log idle games as well.
+
+mod keeper;
+
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as _, Result};
+use clap::{Arg, ArgMatches, Command};
+use log::{debug, error, info, warn};
+use lottery_client::LotteryClient;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::read_keypair_file;
+
+use crate::keeper::{Keeper, KeeperConfig, Outcome};
+
+fn cli() -> Command<'static> {
+    Command::new("lottery-keeper")
+        .about("Drive telegram_lottery games to completion from chain state")
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .short('u')
+                .takes_value(true)
+                .default_value("localhost")
+                .help("RPC URL or moniker: localhost, devnet, testnet, mainnet-beta"),
+        )
+        .arg(
+            Arg::new("keypair")
+                .long("keypair")
+                .short('k')
+                .takes_value(true)
+                .help("Game operator keypair [default: ~/.config/solana/id.json]"),
+        )
+        .arg(
+            Arg::new("game")
+                .long("game")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .help("Only drive this game; repeat for several [default: every game]"),
+        )
+        .arg(
+            Arg::new("poll-interval")
+                .long("poll-interval")
+                .takes_value(true)
+                .default_value("2")
+                .help("Seconds between passes"),
+        )
+        .arg(
+            Arg::new("priority-fee")
+                .long("priority-fee")
+                .takes_value(true)
+                .default_value("0")
+                .help("Compute unit price in micro-lamports"),
+        )
+        .arg(
+            Arg::new("compute-unit-limit")
+                .long("compute-unit-limit")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("max-retries")
+                .long("max-retries")
+                .takes_value(true)
+                .default_value("3")
+                .help("Resends of a failed step before waiting for the next pass"),
+        )
+        .arg(
+            Arg::new("retry-delay-ms")
+                .long("retry-delay-ms")
+                .takes_value(true)
+                .default_value("500"),
+        )
+        .arg(
+            Arg::new("mock-vrf")
+                .long("mock-vrf")
+                .help("Fulfill randomness through the mock VRF program (local validator only)"),
+        )
+        .arg(
+            Arg::new("once")
+                .long("once")
+                .help("Run a single pass and exit"),
+        )
+}
+
+/// Expand cluster monikers the way the Solana CLI does
+fn resolve_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://localhost:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        other => other,
+    }
+    .to_string()
+}
+
+fn parse<T>(matches: &ArgMatches, name: &str) -> Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    matches
+        .value_of(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|error| anyhow!("invalid --{} {:?}: {}", name, value, error))
+        })
+        .transpose()
+}
+
+fn config(matches: &ArgMatches) -> Result<KeeperConfig> {
+    let required = |name| parse::<u64>(matches, name).map(|value| value.expect("has default"));
+    Ok(KeeperConfig {
+        poll_interval: Duration::from_secs(required("poll-interval")?),
+        compute_unit_price: required("priority-fee")?,
+        compute_unit_limit: parse(matches, "compute-unit-limit")?,
+        max_retries: parse(matches, "max-retries")?.expect("has default"),
+        retry_delay: Duration::from_millis(required("retry-delay-ms")?),
+        games: matches
+            .values_of("game")
+            .map(|values| values.map(str::to_string).collect())
+            .unwrap_or_default(),
+        mock_vrf: matches.is_present("mock-vrf"),
+    })
+}
+
+fn log_outcome(game_id: &str, outcome: &Outcome) {
+    match outcome {
+        Outcome::Submitted {
+            action,
+            signature,
+            attempts,
+        } => info!(
+            "{}: {} ({}, attempt {})",
+            game_id, action, signature, attempts
+        ),
+        Outcome::Idle(action) => debug!("{}: {}", game_id, action),
+        Outcome::Superseded { expected, found } => {
+            info!("{}: {} already landed, next {}", game_id, expected, found)
+        }
+        Outcome::Failed {
+            action,
+            attempts,
+            error,
+        } => warn!(
+            "{}: {} failed after {} attempts: {}",
+            game_id, action, attempts, error
+        ),
+        Outcome::Error(error) => warn!("{}: {}", game_id, error),
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let matches = cli().get_matches();
+    let keypair_path = matches
+        .value_of("keypair")
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            format!("{}/.config/solana/id.json", home)
+        });
+    let operator = read_keypair_file(&keypair_path)
+        .map_err(|error| anyhow!("failed to read keypair {}: {}", keypair_path, error))?;
+    let rpc = RpcClient::new_with_commitment(
+        resolve_url(matches.value_of("url").expect("has default")),
+        CommitmentConfig::confirmed(),
+    );
+    let keeper = Keeper::new(LotteryClient::new(rpc), operator, config(&matches)?);
+
+    if keeper.config().mock_vrf {
+        if let Some(signature) = keeper
+            .init_mock_vrf()
+            .context("failed to initialize the mock VRF")?
+        {
+            info!("initialized mock VRF network state ({})", signature);
+        }
+    }
+
+    loop {
+        match keeper.tick() {
+            Ok(outcomes) => {
+                for (game_id, outcome) in &outcomes {
+                    log_outcome(game_id, outcome);
+                }
+            }
+            Err(error) => error!("pass failed: {}", error),
+        }
+        if matches.is_present("once") {
+            return Ok(());
+        }
+        thread::sleep(keeper.config().poll_interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_args() {
+        let matches = cli()
+            .try_get_matches_from([
+                "lottery-keeper",
+                "--priority-fee",
+                "1000",
+                "--game",
+                "g1",
+                "--game",
+                "g2",
+                "--mock-vrf",
+            ])
+            .unwrap();
+        let config = config(&matches).unwrap();
+        assert_eq!(config.compute_unit_price, 1_000);
+        assert_eq!(config.compute_unit_limit, None);
+        assert_eq!(config.games, vec!["g1", "g2"]);
+        assert_eq!(config.poll_interval, Duration::from_secs(2));
+        assert!(config.mock_vrf);
+    }
+
+    #[test]
+    fn test_invalid_number_is_rejected() {
+        let matches = cli()
+            .try_get_matches_from(["lottery-keeper", "--max-retries", "many"])
+            .unwrap();
+        assert!(config(&matches).is_err());
+    }
+}
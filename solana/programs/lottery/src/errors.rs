@@ -110,9 +110,6 @@ pub enum LotteryError {
     #[msg("Player not in game")]
     PlayerNotInGame,
     
-    #[msg("Number already selected by player")]
-    NumberAlreadySelected,
-    
     #[msg("Player has been eliminated")]
     PlayerEliminated,
     
@@ -154,4 +151,70 @@ pub enum LotteryError {
     
     #[msg("Invalid ORAO VRF program")]
     InvalidOraoVrfProgram,
+    
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+    
+    #[msg("Role already granted to this key")]
+    RoleAlreadyGranted,
+    
+    #[msg("Role not granted to this key")]
+    RoleNotGranted,
+    
+    #[msg("Role member limit reached")]
+    RoleMemberLimitReached,
+    
+    #[msg("Invalid approval threshold")]
+    InvalidApprovalThreshold,
+    
+    #[msg("Invalid withdrawal delay")]
+    InvalidWithdrawalDelay,
+    
+    #[msg("Withdrawal proposal already executed or cancelled")]
+    ProposalClosed,
+    
+    #[msg("Withdrawal already approved by this key")]
+    AlreadyApproved,
+    
+    #[msg("Not enough approvals for this withdrawal")]
+    InsufficientApprovals,
+    
+    #[msg("Withdrawal delay has not elapsed")]
+    WithdrawalDelayNotElapsed,
+    
+    #[msg("Destination does not match the proposal")]
+    DestinationMismatch,
+    
+    #[msg("Operation is paused")]
+    OperationPaused,
+    
+    #[msg("Too many allowed token mints")]
+    TooManyAllowedMints,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Oracle already registered")]
+    OracleAlreadyRegistered,
+    
+    #[msg("Oracle not registered")]
+    OracleNotRegistered,
+    
+    #[msg("Oracle registry is full")]
+    OracleRegistryFull,
+    
+    #[msg("Randomness provider is not enabled in this build")]
+    RandomnessProviderDisabled,
+    
+    #[msg("Game uses a different randomness provider")]
+    WrongRandomnessProvider,
+    
+    #[msg("Not every player has selected a number")]
+    NumbersNotSelected,
+    
+    #[msg("Withdrawal approval threshold and delay can only be raised")]
+    WithdrawalSafeguardLowered,
+    
+    #[msg("Max players must be between 2 and 100")]
+    InvalidMaxPlayers,
 }
\ No newline at end of file
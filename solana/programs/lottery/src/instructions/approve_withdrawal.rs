@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveWithdrawal<'info> {
+    #[account(
+        constraint = role_registry.has_role(Role::TreasuryWithdrawer, &approver.key()) @ LotteryError::MissingRole
+    )]
+    pub approver: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.is_open() @ LotteryError::ProposalClosed
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ApproveWithdrawal>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let approver = ctx.accounts.approver.key();
+    
+    require!(
+        !proposal.approvals.contains(&approver),
+        LotteryError::AlreadyApproved
+    );
+    
+    // Approvers are role members, so the vector is bounded by the role limit;
+    // drop approvals from revoked members first to keep room
+    let role_registry = &ctx.accounts.role_registry;
+    proposal.approvals.retain(|key| role_registry.has_role(Role::TreasuryWithdrawer, key));
+    proposal.approvals.push(approver);
+    
+    emit!(WithdrawalApprovedEvent {
+        proposal_id,
+        approver,
+        approvals: proposal.approvals.len() as u8,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -6,10 +6,17 @@ use crate::{state::*, errors::*};
 pub struct CancelGame<'info> {
     #[account(
         mut,
-        constraint = authority.key() == game_state.authority @ LotteryError::Unauthorized
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
     )]
     pub authority: Signer<'info>,
     
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
     #[account(
         mut,
         seeds = [b"game", game_id.as_bytes()],
@@ -75,7 +82,7 @@ pub fn handler(
     let total_funds = game_state.entry_fee * player_list.players.len() as u64;
     
     // Update game state to cancelled
-    let previous_state = game_state.state.clone();
+    let previous_state = game_state.state;
     game_state.state = GameStatus::Cancelled;
     game_state.completed_at = Some(clock.unix_timestamp);
     
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        constraint = authority.key() == proposal.proposer
+            || authority.key() == role_registry.admin @ LotteryError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.is_open() @ LotteryError::ProposalClosed
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CancelWithdrawal>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.cancelled = true;
+    
+    emit!(WithdrawalCancelledEvent {
+        proposal_id,
+        cancelled_by: ctx.accounts.authority.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use randomness::slot_hash::SlotHashRandomness;
+use randomness::{RandomnessProvider, RandomnessSource};
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+#[instruction(game_id: String, round: u8)]
+pub struct CommitRoundRandomness<'info> {
+    #[account(
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"game", game_id.as_bytes()],
+        bump = game_state.bump,
+        constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState,
+        constraint = game_state.randomness_provider == RandomnessProvider::SlotHash @ LotteryError::WrongRandomnessProvider,
+        constraint = !game_state.vrf_request_pending @ LotteryError::VrfRequestAlreadyPending
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<CommitRoundRandomness>,
+    game_id: String,
+    round: u8,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
+    let game_state = &mut ctx.accounts.game_state;
+    
+    // Validate round number
+    require!(
+        round == game_state.current_round + 1,
+        LotteryError::InvalidRound
+    );
+    
+    // Record the commitment; the reveal mixes in a slot hash from after this slot
+    let request = SlotHashRandomness::request(commitment, GameState::round_seed(&game_key, round))?;
+    
+    game_state.vrf_request_pending = true;
+    game_state.pending_round = round;
+    game_state.randomness_request = Some(request);
+    
+    emit!(RoundRandomnessCommittedEvent {
+        game_id,
+        round,
+        commitment,
+        slot: request.slot,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
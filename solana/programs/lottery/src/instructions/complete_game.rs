@@ -1,16 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::{state::*, errors::*};
+use anchor_spl::token::{Token, TokenAccount};
+use settlement::escrow::transfer_tokens_from_pda;
+use crate::{state::*, errors::*, utils::calculate_prize_distribution};
 
 #[derive(Accounts)]
 #[instruction(game_id: String)]
 pub struct CompleteGame<'info> {
     #[account(
         mut,
-        constraint = authority.key() == game_state.authority @ LotteryError::Unauthorized
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
     )]
     pub authority: Signer<'info>,
     
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
     #[account(
         mut,
         seeds = [b"game", game_id.as_bytes()],
@@ -19,6 +27,14 @@ pub struct CompleteGame<'info> {
     )]
     pub game_state: Account<'info, GameState>,
     
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_SETTLE) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
     #[account(
         mut,
         seeds = [b"players", game_id.as_bytes()],
@@ -60,7 +76,7 @@ pub fn handler(ctx: Context<CompleteGame>, game_id: String) -> Result<()> {
     let clock = &ctx.accounts.clock;
     
     // Count winners (players not eliminated)
-    let winners: Vec<&mut Player> = player_list.players
+    let mut winners: Vec<&mut Player> = player_list.players
         .iter_mut()
         .filter(|p| p.eliminated_round.is_none())
         .collect();
@@ -75,17 +91,18 @@ pub fn handler(ctx: Context<CompleteGame>, game_id: String) -> Result<()> {
     
     // Calculate prize distribution
     let total_prize_pool = game_state.prize_pool;
-    let treasury_fee = game_state.treasury_fee;
-    
-    // Distributable amount (90% of total)
-    let distributable_amount = total_prize_pool
-        .checked_sub(treasury_fee)
-        .ok_or(LotteryError::ArithmeticOverflow)?;
-    
-    // Prize per winner
-    let prize_per_winner = distributable_amount
-        .checked_div(actual_winner_count as u64)
+    let (prize_per_winner, remainder) = calculate_prize_distribution(
+        total_prize_pool,
+        game_state.treasury_fee,
+        actual_winner_count,
+    )?;
+    
+    // Dust that cannot be split evenly goes to the treasury instead of
+    // being stranded in escrow
+    let treasury_fee = game_state.treasury_fee
+        .checked_add(remainder)
         .ok_or(LotteryError::ArithmeticOverflow)?;
+    game_state.treasury_fee = treasury_fee;
     
     // Mark winners and set prize amounts
     for (i, winner) in winners.iter_mut().enumerate() {
@@ -103,16 +120,14 @@ pub fn handler(ctx: Context<CompleteGame>, game_id: String) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_account.to_account_info(),
-        to: ctx.accounts.treasury_token_account.to_account_info(),
-        authority: game_state.to_account_info(),
-    };
-    
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    
-    token::transfer(cpi_ctx, treasury_fee)?;
+    transfer_tokens_from_pda(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.treasury_token_account.to_account_info(),
+        &game_state.to_account_info(),
+        signer_seeds,
+        treasury_fee,
+    )?;
     
     // Update treasury state
     treasury_state.total_collected = treasury_state.total_collected
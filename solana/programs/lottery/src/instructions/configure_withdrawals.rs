@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct ConfigureWithdrawals<'info> {
+    #[account(
+        constraint = admin.key() == role_registry.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<ConfigureWithdrawals>,
+    approval_threshold: u8,
+    withdrawal_delay: i64,
+) -> Result<()> {
+    require!(
+        approval_threshold > 0 && approval_threshold as usize <= RoleRegistry::MAX_MEMBERS_PER_ROLE,
+        LotteryError::InvalidApprovalThreshold
+    );
+    
+    require!(
+        (0..=TreasuryState::MAX_WITHDRAWAL_DELAY).contains(&withdrawal_delay),
+        LotteryError::InvalidWithdrawalDelay
+    );
+    
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    
+    // A single admin key may only tighten the policy, never weaken it
+    require!(
+        approval_threshold >= treasury_state.approval_threshold
+            && withdrawal_delay >= treasury_state.withdrawal_delay,
+        LotteryError::WithdrawalSafeguardLowered
+    );
+    
+    treasury_state.approval_threshold = approval_threshold;
+    treasury_state.withdrawal_delay = withdrawal_delay;
+    
+    emit!(WithdrawalConfigUpdatedEvent {
+        updated_by: ctx.accounts.admin.key(),
+        approval_threshold,
+        withdrawal_delay,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
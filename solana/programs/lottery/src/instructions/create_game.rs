@@ -1,13 +1,25 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use randomness::RandomnessProvider;
+use settlement::fees::percent_to_bps;
 use crate::{state::*, errors::*};
 
 #[derive(Accounts)]
 #[instruction(game_id: String)]
 pub struct CreateGame<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
+    )]
     pub authority: Signer<'info>,
     
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
     #[account(
         init,
         payer = authority,
@@ -17,6 +29,14 @@ pub struct CreateGame<'info> {
     )]
     pub game_state: Account<'info, GameState>,
     
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_CREATE) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
     #[account(
         init,
         payer = authority,
@@ -47,8 +67,18 @@ pub struct CreateGame<'info> {
     )]
     pub escrow_account: Account<'info, TokenAccount>,
     
+    /// Approved oracle registry
+    #[account(
+        seeds = [b"oracles"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
     /// VRF oracle account (configured oracle authority)
-    /// CHECK: This is just the oracle's pubkey, verified in handler
+    /// CHECK: Only the key is used; it must be in the oracle registry
+    #[account(
+        constraint = oracle_registry.is_registered(&vrf_oracle.key()) @ LotteryError::InvalidVrfOracle
+    )]
     pub vrf_oracle: AccountInfo<'info>,
     
     pub system_program: Program<'info, System>,
@@ -64,6 +94,7 @@ pub fn handler(
     max_players: u8,
     winner_count: u8,
     payment_deadline_minutes: u16,
+    randomness_provider: RandomnessProvider,
 ) -> Result<()> {
     // Validate inputs
     require!(
@@ -72,13 +103,18 @@ pub fn handler(
     );
     
     require!(
-        entry_fee > 0,
+        entry_fee > 0 && entry_fee <= ctx.accounts.config.max_entry_fee,
         LotteryError::InvalidEntryFee
     );
     
+    require!(
+        ctx.accounts.config.allowed_mints.contains(&ctx.accounts.token_mint.key()),
+        LotteryError::InvalidTokenMint
+    );
+    
     require!(
         max_players >= 2 && max_players <= GameState::MAX_PLAYERS as u8,
-        LotteryError::InvalidWinnerCount
+        LotteryError::InvalidMaxPlayers
     );
     
     require!(
@@ -86,6 +122,11 @@ pub fn handler(
         LotteryError::InvalidWinnerCount
     );
     
+    require!(
+        randomness_provider.is_enabled(),
+        LotteryError::RandomnessProviderDisabled
+    );
+    
     let game_state = &mut ctx.accounts.game_state;
     let player_list = &mut ctx.accounts.player_list;
     let clock = &ctx.accounts.clock;
@@ -100,9 +141,10 @@ pub fn handler(
     game_state.state = GameStatus::Created;
     game_state.prize_pool = 0;
     game_state.treasury_fee = 0;
+    game_state.fee_bps = percent_to_bps(ctx.accounts.treasury_state.fee_percentage)?;
     game_state.number_range = NumberRange {
         min: 1,
-        max: (max_players * 2) as u8, // Dynamic range based on players
+        max: max_players * 2, // Dynamic range based on players
     };
     game_state.created_at = clock.unix_timestamp;
     game_state.started_at = None;
@@ -115,12 +157,14 @@ pub fn handler(
     game_state.vrf_oracle = ctx.accounts.vrf_oracle.key();
     game_state.vrf_request_pending = false;
     game_state.pending_round = 0;
-    game_state.bump = *ctx.bumps.get("game_state").unwrap();
+    game_state.randomness_provider = randomness_provider;
+    game_state.randomness_request = None;
+    game_state.bump = ctx.bumps.game_state;
     
     // Initialize player list
     player_list.game_id = game_id.clone();
     player_list.players = Vec::new();
-    player_list.bump = *ctx.bumps.get("player_list").unwrap();
+    player_list.bump = ctx.bumps.player_list;
     
     // Update game status to joining
     game_state.state = GameStatus::Joining;
@@ -129,6 +173,8 @@ pub fn handler(
     emit!(GameCreatedEvent {
         game_id,
         authority: ctx.accounts.authority.key(),
+        vrf_oracle: ctx.accounts.vrf_oracle.key(),
+        randomness_provider,
         entry_fee,
         max_players,
         timestamp: clock.unix_timestamp,
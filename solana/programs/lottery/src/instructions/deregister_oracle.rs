@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct DeregisterOracle<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracles"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<DeregisterOracle>, oracle: Pubkey) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    
+    let index = oracle_registry.oracles
+        .iter()
+        .position(|key| *key == oracle)
+        .ok_or(LotteryError::OracleNotRegistered)?;
+    oracle_registry.oracles.remove(index);
+    
+    emit!(OracleDeregisteredEvent {
+        oracle,
+        deregistered_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use orao_solana_vrf::state::Randomness;
-use orao_solana_vrf::{RANDOMNESS_ACCOUNT_SEED};
-use crate::{state::*, errors::*};
+use randomness::orao::OraoRandomness;
+use randomness::RandomnessSource;
+use crate::{state::*, errors::*, utils::generate_number_from_random};
 
 #[derive(Accounts)]
 #[instruction(game_id: String, round: u8)]
@@ -14,21 +14,22 @@ pub struct FulfillOraoVrf<'info> {
         seeds = [b"game", game_id.as_bytes()],
         bump = game_state.bump,
         constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState,
-        constraint = game_state.vrf_request_pending == true @ LotteryError::NoVrfRequestPending,
+        constraint = game_state.vrf_request_pending @ LotteryError::NoVrfRequestPending,
         constraint = game_state.pending_round == round @ LotteryError::InvalidRound
     )]
     pub game_state: Account<'info, GameState>,
     
-    /// The randomness account from ORAO VRF
+    /// Lottery config
     #[account(
-        seeds = [
-            RANDOMNESS_ACCOUNT_SEED.as_ref(),
-            &game_state.key().to_bytes()
-        ],
-        bump,
-        seeds::program = orao_vrf_program.key()
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
     )]
-    pub randomness: Account<'info, Randomness>,
+    pub config: Account<'info, LotteryConfig>,
+    
+    /// The randomness account from ORAO VRF
+    /// CHECK: Owner, address and seed checked against the open request
+    pub randomness: UncheckedAccount<'info>,
     
     #[account(
         init,
@@ -39,10 +40,6 @@ pub struct FulfillOraoVrf<'info> {
     )]
     pub vrf_result: Account<'info, VrfResult>,
     
-    /// ORAO VRF program for PDA validation
-    /// CHECK: Only used for PDA validation
-    pub orao_vrf_program: UncheckedAccount<'info>,
-    
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
@@ -54,17 +51,14 @@ pub fn handler(
 ) -> Result<()> {
     let game_state = &mut ctx.accounts.game_state;
     let vrf_result = &mut ctx.accounts.vrf_result;
-    let randomness = &ctx.accounts.randomness;
     let clock = &ctx.accounts.clock;
     
-    // Verify the randomness has been fulfilled
-    require!(
-        randomness.fulfilled(),
-        LotteryError::VrfNotFulfilled
-    );
+    // Load the ORAO account recorded by request_orao_vrf
+    let request = game_state.randomness_request.ok_or(LotteryError::NoVrfRequestPending)?;
+    let randomness = OraoRandomness::load(&ctx.accounts.randomness, &request)?;
     
-    // Get the random value from ORAO
-    let random_value = randomness.get_value().ok_or(LotteryError::VrfNotFulfilled)?;
+    // Get the random value from ORAO once fulfilled
+    let random_value = randomness.read_32().ok_or(LotteryError::VrfNotFulfilled)?;
     
     // Initialize VRF result
     vrf_result.game_id = game_id.clone();
@@ -73,12 +67,14 @@ pub fn handler(
     vrf_result.proof = vec![]; // ORAO handles proof verification internally
     vrf_result.timestamp = clock.unix_timestamp;
     vrf_result.used = false;
-    vrf_result.bump = *ctx.bumps.get("vrf_result").unwrap();
+    vrf_result.bump = ctx.bumps.vrf_result;
     
     // Generate drawn number from random value
-    let random_u64 = u64::from_le_bytes(random_value[..8].try_into().unwrap());
-    let range = (game_state.number_range.max - game_state.number_range.min + 1) as u64;
-    let drawn_number = (random_u64 % range) as u8 + game_state.number_range.min;
+    let drawn_number = generate_number_from_random(
+        &random_value,
+        game_state.number_range.min,
+        game_state.number_range.max,
+    )?;
     
     vrf_result.drawn_number = drawn_number;
     
@@ -87,6 +83,7 @@ pub fn handler(
     game_state.drawn_numbers.push(drawn_number);
     game_state.vrf_request_pending = false;
     game_state.pending_round = 0;
+    game_state.randomness_request = None;
     
     // Emit event
     emit!(VrfFulfilledEvent {
@@ -101,6 +98,7 @@ pub fn handler(
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct VrfFulfilledEvent {
     pub game_id: String,
     pub round: u8,
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        constraint = admin.key() == role_registry.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    
+    require!(
+        !role_registry.has_role(role, &member),
+        LotteryError::RoleAlreadyGranted
+    );
+    
+    let members = role_registry.members_mut(role);
+    require!(
+        members.len() < RoleRegistry::MAX_MEMBERS_PER_ROLE,
+        LotteryError::RoleMemberLimitReached
+    );
+    members.push(member);
+    
+    emit!(RoleGrantedEvent {
+        role,
+        member,
+        granted_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -53,8 +53,12 @@ pub fn handler(
     treasury_state.treasury_token_account = ctx.accounts.treasury_token_account.key();
     treasury_state.fee_percentage = fee_percentage;
     treasury_state.total_collected = 0;
+    treasury_state.total_distributed = 0;
     treasury_state.pending_withdrawal = 0;
-    treasury_state.bump = *ctx.bumps.get("treasury_state").unwrap();
+    treasury_state.approval_threshold = TreasuryState::DEFAULT_APPROVAL_THRESHOLD;
+    treasury_state.withdrawal_delay = TreasuryState::DEFAULT_WITHDRAWAL_DELAY;
+    treasury_state.proposal_count = 0;
+    treasury_state.bump = ctx.bumps.treasury_state;
     
     // Emit initialization event
     emit!(TreasuryInitializedEvent {
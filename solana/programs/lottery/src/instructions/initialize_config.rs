@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == role_registry.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = LotteryConfig::SIZE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeConfig>,
+    allowed_mints: Vec<Pubkey>,
+    max_entry_fee: u64,
+) -> Result<()> {
+    require!(
+        allowed_mints.len() <= LotteryConfig::MAX_ALLOWED_MINTS,
+        LotteryError::TooManyAllowedMints
+    );
+    
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.pause_flags = 0;
+    config.allowed_mints = allowed_mints;
+    config.max_entry_fee = max_entry_fee;
+    config.bump = ctx.bumps.config;
+    
+    emit!(ConfigUpdatedEvent {
+        admin: config.admin,
+        allowed_mints: config.allowed_mints.clone(),
+        max_entry_fee,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct InitializeOracleRegistry<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == config.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = OracleRegistry::SIZE,
+        seeds = [b"oracles"],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeOracleRegistry>) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    oracle_registry.oracles = Vec::new();
+    oracle_registry.bump = ctx.bumps.oracle_registry;
+    
+    Ok(())
+}
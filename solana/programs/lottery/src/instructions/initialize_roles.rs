@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == treasury_state.authority @ LotteryError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RoleRegistry::SIZE,
+        seeds = [b"roles"],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeRoles>) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    
+    // Treasury authority becomes the role admin; every role starts empty
+    role_registry.admin = ctx.accounts.authority.key();
+    role_registry.operators = Vec::new();
+    role_registry.pausers = Vec::new();
+    role_registry.fee_managers = Vec::new();
+    role_registry.treasury_withdrawers = Vec::new();
+    role_registry.bump = ctx.bumps.role_registry;
+    
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
+use settlement::{escrow::transfer_tokens_to_escrow, fees::split_fee};
 use crate::{state::*, errors::*};
 
 #[derive(Accounts)]
@@ -16,6 +17,14 @@ pub struct JoinGame<'info> {
     )]
     pub game_state: Account<'info, GameState>,
     
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_JOIN) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
     #[account(
         mut,
         seeds = [b"players", game_id.as_bytes()],
@@ -76,16 +85,13 @@ pub fn handler(
     );
     
     // Transfer entry fee to escrow
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.player_token_account.to_account_info(),
-        to: ctx.accounts.escrow_account.to_account_info(),
-        authority: ctx.accounts.player.to_account_info(),
-    };
-    
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    
-    token::transfer(cpi_ctx, game_state.entry_fee)?;
+    transfer_tokens_to_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.player_token_account.to_account_info(),
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.player.to_account_info(),
+        game_state.entry_fee,
+    )?;
     
     // Add player to the game
     let new_player = Player {
@@ -106,10 +112,8 @@ pub fn handler(
         .checked_add(game_state.entry_fee)
         .ok_or(LotteryError::ArithmeticOverflow)?;
     
-    // Calculate treasury fee (10%)
-    let fee_amount = game_state.entry_fee
-        .checked_div(10)
-        .ok_or(LotteryError::ArithmeticOverflow)?;
+    // Accrue the treasury's share at the rate fixed when the game was created
+    let fee_amount = split_fee(game_state.entry_fee, game_state.fee_bps)?.fee;
     
     game_state.treasury_fee = game_state.treasury_fee
         .checked_add(fee_amount)
@@ -2,26 +2,62 @@ pub mod initialize;
 pub mod create_game;
 pub mod join_game;
 pub mod select_number;
+pub mod start_game;
 pub mod submit_vrf;
 pub mod request_orao_vrf;
 pub mod fulfill_orao_vrf;
+pub mod commit_round_randomness;
+pub mod reveal_round_randomness;
 pub mod process_elimination;
 pub mod complete_game;
 pub mod claim_prize;
 pub mod request_refund;
 pub mod cancel_game;
 pub mod withdraw_treasury;
+pub mod initialize_roles;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod update_fee_percentage;
+pub mod configure_withdrawals;
+pub mod propose_withdrawal;
+pub mod approve_withdrawal;
+pub mod cancel_withdrawal;
+pub mod initialize_config;
+pub mod update_config;
+pub mod set_pause_flags;
+pub mod initialize_oracle_registry;
+pub mod register_oracle;
+pub mod deregister_oracle;
 
+// Every instruction module exports a `handler`; lib.rs calls them by path
+#[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
 pub use create_game::*;
 pub use join_game::*;
 pub use select_number::*;
+pub use start_game::*;
 pub use submit_vrf::*;
 pub use request_orao_vrf::*;
 pub use fulfill_orao_vrf::*;
+pub use commit_round_randomness::*;
+pub use reveal_round_randomness::*;
 pub use process_elimination::*;
 pub use complete_game::*;
 pub use claim_prize::*;
 pub use request_refund::*;
 pub use cancel_game::*;
-pub use withdraw_treasury::*;
\ No newline at end of file
+pub use withdraw_treasury::*;
+pub use initialize_roles::*;
+pub use grant_role::*;
+pub use revoke_role::*;
+pub use update_fee_percentage::*;
+pub use configure_withdrawals::*;
+pub use propose_withdrawal::*;
+pub use approve_withdrawal::*;
+pub use cancel_withdrawal::*;
+pub use initialize_config::*;
+pub use update_config::*;
+pub use set_pause_flags::*;
+pub use initialize_oracle_registry::*;
+pub use register_oracle::*;
+pub use deregister_oracle::*;
\ No newline at end of file
@@ -6,10 +6,17 @@ use crate::{state::*, errors::*};
 pub struct ProcessElimination<'info> {
     #[account(
         mut,
-        constraint = authority.key() == game_state.authority @ LotteryError::Unauthorized
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
     )]
     pub authority: Signer<'info>,
     
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
     #[account(
         mut,
         seeds = [b"game", game_id.as_bytes()],
@@ -18,6 +25,14 @@ pub struct ProcessElimination<'info> {
     )]
     pub game_state: Account<'info, GameState>,
     
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
     #[account(
         mut,
         seeds = [b"players", game_id.as_bytes()],
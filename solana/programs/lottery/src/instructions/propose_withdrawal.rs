@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(
+        mut,
+        constraint = role_registry.has_role(Role::TreasuryWithdrawer, &proposer.key()) @ LotteryError::MissingRole
+    )]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = WithdrawalProposal::SIZE,
+        seeds = [b"withdrawal", treasury_state.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    /// Destination token account (must hold the treasury mint)
+    pub destination_token_account: Account<'info, TokenAccount>,
+    
+    /// Treasury token account
+    #[account(
+        constraint = treasury_token_account.key() == treasury_state.treasury_token_account,
+        constraint = destination_token_account.mint == treasury_token_account.mint
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ProposeWithdrawal>, amount: u64) -> Result<()> {
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = &ctx.accounts.clock;
+    
+    require!(amount > 0, LotteryError::NoFundsToWithdraw);
+    require!(
+        amount <= treasury_state.pending_withdrawal,
+        LotteryError::InsufficientTreasuryBalance
+    );
+    
+    let executable_at = clock.unix_timestamp
+        .checked_add(treasury_state.withdrawal_delay)
+        .ok_or(LotteryError::ArithmeticOverflow)?;
+    
+    // Proposer counts as the first approval
+    proposal.id = treasury_state.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.amount = amount;
+    proposal.destination = ctx.accounts.destination_token_account.key();
+    proposal.approvals = vec![ctx.accounts.proposer.key()];
+    proposal.created_at = clock.unix_timestamp;
+    proposal.executable_at = executable_at;
+    proposal.executed = false;
+    proposal.cancelled = false;
+    proposal.bump = ctx.bumps.proposal;
+    
+    treasury_state.proposal_count = treasury_state.proposal_count
+        .checked_add(1)
+        .ok_or(LotteryError::ArithmeticOverflow)?;
+    
+    emit!(WithdrawalProposedEvent {
+        proposal_id: proposal.id,
+        proposer: proposal.proposer,
+        amount,
+        destination: proposal.destination,
+        executable_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
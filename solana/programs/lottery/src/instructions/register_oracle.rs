@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct RegisterOracle<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"oracles"],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RegisterOracle>, oracle: Pubkey) -> Result<()> {
+    let oracle_registry = &mut ctx.accounts.oracle_registry;
+    
+    require!(
+        !oracle_registry.is_registered(&oracle),
+        LotteryError::OracleAlreadyRegistered
+    );
+    
+    require!(
+        oracle_registry.oracles.len() < OracleRegistry::MAX_ORACLES,
+        LotteryError::OracleRegistryFull
+    );
+    
+    oracle_registry.oracles.push(oracle);
+    
+    emit!(OracleRegisteredEvent {
+        oracle,
+        registered_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -1,9 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::sysvar;
-use orao_solana_vrf::cpi::accounts::Request;
-use orao_solana_vrf::program::OraoVrf;
-use orao_solana_vrf::state::NetworkState;
-use orao_solana_vrf::{CONFIG_ACCOUNT_SEED, RANDOMNESS_ACCOUNT_SEED};
+use randomness::orao::{OraoRandomness, OraoRequest};
+use randomness::{RandomnessProvider, RandomnessSource};
 use crate::{state::*, errors::*};
 
 #[derive(Accounts)]
@@ -16,45 +13,41 @@ pub struct RequestOraoVrf<'info> {
         mut,
         seeds = [b"game", game_id.as_bytes()],
         bump = game_state.bump,
-        constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState
+        constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState,
+        constraint = game_state.randomness_provider == RandomnessProvider::Orao @ LotteryError::WrongRandomnessProvider,
+        constraint = !game_state.vrf_request_pending @ LotteryError::VrfRequestAlreadyPending
     )]
     pub game_state: Account<'info, GameState>,
     
-    /// ORAO Network state account
+    /// Lottery config
     #[account(
-        seeds = [CONFIG_ACCOUNT_SEED.as_ref()],
-        bump = network_state.bump,
-        seeds::program = orao_vrf.key()
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
     )]
-    pub network_state: Account<'info, NetworkState>,
+    pub config: Account<'info, LotteryConfig>,
+    
+    /// ORAO Network state account
+    /// CHECK: Address verified against the ORAO network state PDA
+    #[account(mut, address = randomness::orao::network_state_address())]
+    pub network_state: UncheckedAccount<'info>,
     
     /// ORAO VRF Treasury account (receives payment)
     #[account(mut)]
     pub treasury: SystemAccount<'info>,
     
     /// The account that will store the generated randomness
-    /// CHECK: This account is created and managed by ORAO VRF
-    #[account(
-        mut,
-        seeds = [
-            RANDOMNESS_ACCOUNT_SEED.as_ref(),
-            &game_state.key().to_bytes()
-        ],
-        bump,
-        seeds::program = orao_vrf.key()
-    )]
+    /// CHECK: Created by ORAO VRF; address checked against the round seed
+    #[account(mut)]
     pub randomness: UncheckedAccount<'info>,
     
     /// ORAO VRF program
-    pub orao_vrf: Program<'info, OraoVrf>,
+    /// CHECK: Address verified against the ORAO program id
+    #[account(address = randomness::orao::ID @ LotteryError::InvalidOraoVrfProgram)]
+    pub orao_vrf: UncheckedAccount<'info>,
     
     /// System program
     pub system_program: Program<'info, System>,
-    
-    /// Recent slothashes sysvar
-    /// CHECK: Validated by ORAO VRF program
-    #[account(address = sysvar::recent_blockhashes::ID)]
-    pub recent_slothashes: UncheckedAccount<'info>,
 }
 
 pub fn handler(
@@ -62,6 +55,7 @@ pub fn handler(
     game_id: String,
     round: u8,
 ) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
     let game_state = &mut ctx.accounts.game_state;
     
     // Validate round number
@@ -70,27 +64,26 @@ pub fn handler(
         LotteryError::InvalidRound
     );
     
-    // Prepare the seed for ORAO VRF request
-    // Using game_id and round to ensure unique randomness per round
-    let seed = format!("{}-round-{}", game_id, round).as_bytes().to_vec();
-    
-    // Create CPI context for ORAO VRF request
-    let cpi_program = ctx.accounts.orao_vrf.to_account_info();
-    let cpi_accounts = Request {
-        payer: ctx.accounts.player.to_account_info(),
-        network_state: ctx.accounts.network_state.to_account_info(),
-        treasury: ctx.accounts.treasury.to_account_info(),
-        request: ctx.accounts.randomness.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    // Seed is unique per game and round, so every round gets its own request
+    let seed = GameState::round_seed(&game_key, round);
     
     // Request randomness from ORAO VRF
-    orao_solana_vrf::cpi::request(cpi_ctx, seed)?;
+    let request = OraoRandomness::request(
+        OraoRequest {
+            payer: ctx.accounts.player.to_account_info(),
+            network_state: ctx.accounts.network_state.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            request: ctx.accounts.randomness.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            orao_program: ctx.accounts.orao_vrf.to_account_info(),
+        },
+        seed,
+    )?;
     
     // Update game state to indicate VRF request is pending
     game_state.vrf_request_pending = true;
     game_state.pending_round = round;
+    game_state.randomness_request = Some(request);
     
     // Emit event
     emit!(VrfRequestedEvent {
@@ -104,9 +97,10 @@ pub fn handler(
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct VrfRequestedEvent {
     pub game_id: String,
     pub round: u8,
     pub randomness_account: Pubkey,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
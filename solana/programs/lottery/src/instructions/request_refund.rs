@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
+use settlement::escrow::transfer_tokens_from_pda;
 use crate::{state::*, errors::*};
 
 #[derive(Accounts)]
@@ -72,16 +73,14 @@ pub fn handler(ctx: Context<RequestRefund>, game_id: String) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_account.to_account_info(),
-        to: ctx.accounts.player_token_account.to_account_info(),
-        authority: game_state.to_account_info(),
-    };
-    
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    
-    token::transfer(cpi_ctx, refund_amount)?;
+    transfer_tokens_from_pda(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.player_token_account.to_account_info(),
+        &game_state.to_account_info(),
+        signer_seeds,
+        refund_amount,
+    )?;
     
     // Mark refund as processed
     player.prize_claimed = true; // Reusing flag for refund tracking
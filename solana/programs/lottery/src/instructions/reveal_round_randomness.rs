@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use randomness::slot_hash::SlotHashRandomness;
+use randomness::{RandomnessProvider, RandomnessSource};
+use crate::{state::*, errors::*, utils::generate_number_from_random};
+
+#[derive(Accounts)]
+#[instruction(game_id: String, round: u8)]
+pub struct RevealRoundRandomness<'info> {
+    #[account(
+        mut,
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"game", game_id.as_bytes()],
+        bump = game_state.bump,
+        constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState,
+        constraint = game_state.randomness_provider == RandomnessProvider::SlotHash @ LotteryError::WrongRandomnessProvider,
+        constraint = game_state.vrf_request_pending @ LotteryError::NoVrfRequestPending,
+        constraint = game_state.pending_round == round @ LotteryError::InvalidRound
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = VrfResult::SIZE,
+        seeds = [b"vrf", game_id.as_bytes(), &[round]],
+        bump
+    )]
+    pub vrf_result: Account<'info, VrfResult>,
+    
+    /// Slot hashes sysvar
+    /// CHECK: Address checked against the slot hashes sysvar
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<RevealRoundRandomness>,
+    game_id: String,
+    round: u8,
+    secret: [u8; 32],
+) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    let vrf_result = &mut ctx.accounts.vrf_result;
+    let clock = &ctx.accounts.clock;
+    
+    // Check the secret and mix it with the first slot hash after the commit
+    let request = game_state.randomness_request.ok_or(LotteryError::NoVrfRequestPending)?;
+    let revealed = SlotHashRandomness::reveal(&ctx.accounts.slot_hashes, &request, &secret)?;
+    let random_value = revealed.read_32().ok_or(LotteryError::VrfNotFulfilled)?;
+    
+    // Initialize VRF result
+    vrf_result.game_id = game_id.clone();
+    vrf_result.round = round;
+    vrf_result.random_value = random_value;
+    vrf_result.proof = secret.to_vec();
+    vrf_result.timestamp = clock.unix_timestamp;
+    vrf_result.used = false;
+    vrf_result.bump = ctx.bumps.vrf_result;
+    
+    // Generate drawn number from random value
+    let drawn_number = generate_number_from_random(
+        &random_value,
+        game_state.number_range.min,
+        game_state.number_range.max,
+    )?;
+    
+    vrf_result.drawn_number = drawn_number;
+    
+    // Update game state
+    game_state.current_round = round;
+    game_state.drawn_numbers.push(drawn_number);
+    game_state.vrf_request_pending = false;
+    game_state.pending_round = 0;
+    game_state.randomness_request = None;
+    
+    emit!(RoundRandomnessRevealedEvent {
+        game_id,
+        round,
+        drawn_number,
+        slot: revealed.slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        constraint = admin.key() == role_registry.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    
+    let members = role_registry.members_mut(role);
+    let index = members
+        .iter()
+        .position(|key| *key == member)
+        .ok_or(LotteryError::RoleNotGranted)?;
+    members.remove(index);
+    
+    emit!(RoleRevokedEvent {
+        role,
+        member,
+        revoked_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -15,6 +15,14 @@ pub struct SelectNumber<'info> {
     )]
     pub game_state: Account<'info, GameState>,
     
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
     #[account(
         mut,
         seeds = [b"players", game_id.as_bytes()],
@@ -40,6 +48,11 @@ pub fn handler(
         LotteryError::NumberOutOfRange
     );
     
+    // Check if number is already taken by another player
+    let number_taken = player_list.players
+        .iter()
+        .any(|p| p.selected_number == Some(number) && p.wallet != ctx.accounts.player.key());
+    
     // Find the player in the list
     let player = player_list.players
         .iter_mut()
@@ -49,7 +62,7 @@ pub fn handler(
     // Check if player already selected a number
     require!(
         player.selected_number.is_none(),
-        LotteryError::NumberAlreadySelected
+        LotteryError::PlayerAlreadySelectedNumber
     );
     
     // Check if player is eliminated
@@ -58,11 +71,6 @@ pub fn handler(
         LotteryError::PlayerEliminated
     );
     
-    // Check if number is already taken by another player
-    let number_taken = player_list.players
-        .iter()
-        .any(|p| p.selected_number == Some(number) && p.wallet != ctx.accounts.player.key());
-    
     require!(
         !number_taken,
         LotteryError::NumberAlreadyTaken
@@ -92,7 +100,7 @@ pub fn handler(
     
     // If all active players have selected numbers, we can transition to playing state
     if players_with_numbers == active_players {
-        // Note: State transition should be done by authority through a separate instruction
+        // Note: The operator moves the game to playing through start_game
         // This ensures proper VRF setup before starting eliminations
         emit!(AllNumbersSelectedEvent {
             game_id,
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        constraint = role_registry.has_role(Role::Pauser, &pauser.key()) @ LotteryError::MissingRole
+    )]
+    pub pauser: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+    // Refund and claim paths have no flag and cannot be paused
+    require!(
+        pause_flags & !LotteryConfig::PAUSE_ALL == 0,
+        LotteryError::InvalidPauseFlags
+    );
+    
+    let config = &mut ctx.accounts.config;
+    let old_pause_flags = config.pause_flags;
+    config.pause_flags = pause_flags;
+    
+    emit!(PauseFlagsUpdatedEvent {
+        pauser: ctx.accounts.pauser.key(),
+        old_pause_flags,
+        new_pause_flags: pause_flags,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+#[instruction(game_id: String)]
+pub struct StartGame<'info> {
+    #[account(
+        constraint = role_registry.has_role(Role::Operator, &authority.key()) @ LotteryError::MissingRole
+    )]
+    pub authority: Signer<'info>,
+    
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"game", game_id.as_bytes()],
+        bump = game_state.bump,
+        constraint = game_state.state == GameStatus::NumberSelection @ LotteryError::InvalidGameState
+    )]
+    pub game_state: Account<'info, GameState>,
+    
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    #[account(
+        seeds = [b"players", game_id.as_bytes()],
+        bump = player_list.bump
+    )]
+    pub player_list: Account<'info, PlayerList>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<StartGame>, game_id: String) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    let player_list = &ctx.accounts.player_list;
+    let clock = &ctx.accounts.clock;
+    
+    // Every active player needs a number before rounds can eliminate anyone
+    let all_selected = player_list.players
+        .iter()
+        .filter(|p| p.eliminated_round.is_none())
+        .all(|p| p.selected_number.is_some());
+    
+    require!(
+        all_selected,
+        LotteryError::NumbersNotSelected
+    );
+    
+    game_state.state = GameStatus::Playing;
+    
+    emit!(GameStartedEvent {
+        game_id,
+        player_count: player_list.players.len() as u8,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::{state::*, errors::*};
+use anchor_lang::solana_program::sysvar;
+use randomness::signed_oracle::SignedOracleRandomness;
+use randomness::{RandomnessProvider, RandomnessRequest, RandomnessSource};
+use crate::{state::*, errors::*, utils::generate_number_from_random};
 
 #[derive(Accounts)]
 #[instruction(game_id: String, round: u8)]
@@ -14,10 +17,27 @@ pub struct SubmitVrf<'info> {
         mut,
         seeds = [b"game", game_id.as_bytes()],
         bump = game_state.bump,
-        constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState
+        constraint = game_state.state == GameStatus::Playing @ LotteryError::InvalidGameState,
+        constraint = game_state.randomness_provider == RandomnessProvider::SignedOracle @ LotteryError::WrongRandomnessProvider
     )]
     pub game_state: Account<'info, GameState>,
     
+    /// Lottery config
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(LotteryConfig::PAUSE_PLAY) @ LotteryError::OperationPaused
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    /// Oracles deregistered after game creation can no longer submit
+    #[account(
+        seeds = [b"oracles"],
+        bump = oracle_registry.bump,
+        constraint = oracle_registry.is_registered(&vrf_oracle.key()) @ LotteryError::InvalidVrfOracle
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    
     #[account(
         init,
         payer = vrf_oracle,
@@ -27,6 +47,11 @@ pub struct SubmitVrf<'info> {
     )]
     pub vrf_result: Account<'info, VrfResult>,
     
+    /// Instructions sysvar holding the oracle's Ed25519 signature instruction
+    /// CHECK: Address checked against the instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
@@ -35,9 +60,8 @@ pub fn handler(
     ctx: Context<SubmitVrf>,
     game_id: String,
     round: u8,
-    random_value: [u8; 32],
-    proof: Vec<u8>,
 ) -> Result<()> {
+    let game_key = ctx.accounts.game_state.key();
     let game_state = &mut ctx.accounts.game_state;
     let vrf_result = &mut ctx.accounts.vrf_result;
     let clock = &ctx.accounts.clock;
@@ -48,26 +72,34 @@ pub fn handler(
         LotteryError::InvalidRound
     );
     
-    // Validate proof length (simplified - in production would verify actual VRF proof)
-    require!(
-        proof.len() >= 64 && proof.len() <= 256,
+    // The game's oracle must have signed this round's seed in the same transaction
+    let request = RandomnessRequest {
+        seed: GameState::round_seed(&game_key, round),
+        ..Default::default()
+    };
+    let signed = SignedOracleRandomness::load(&ctx.accounts.instructions, &request)?;
+    require_keys_eq!(
+        signed.oracle,
+        game_state.vrf_oracle,
         LotteryError::InvalidVrfProof
     );
+    let random_value = signed.read_32().ok_or(LotteryError::VrfNotFulfilled)?;
     
     // Initialize VRF result
     vrf_result.game_id = game_id.clone();
     vrf_result.round = round;
     vrf_result.random_value = random_value;
-    vrf_result.proof = proof;
+    vrf_result.proof = signed.signature.to_vec();
     vrf_result.timestamp = clock.unix_timestamp;
     vrf_result.used = false;
-    vrf_result.bump = *ctx.bumps.get("vrf_result").unwrap();
+    vrf_result.bump = ctx.bumps.vrf_result;
     
     // Generate drawn number from random value
-    // Use first 8 bytes of random value to generate number within range
-    let random_u64 = u64::from_le_bytes(random_value[..8].try_into().unwrap());
-    let range = (game_state.number_range.max - game_state.number_range.min + 1) as u64;
-    let drawn_number = (random_u64 % range) as u8 + game_state.number_range.min;
+    let drawn_number = generate_number_from_random(
+        &random_value,
+        game_state.number_range.min,
+        game_state.number_range.max,
+    )?;
     
     vrf_result.drawn_number = drawn_number;
     
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ LotteryError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, LotteryConfig>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    allowed_mints: Option<Vec<Pubkey>>,
+    max_entry_fee: Option<u64>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    if let Some(allowed_mints) = allowed_mints {
+        require!(
+            allowed_mints.len() <= LotteryConfig::MAX_ALLOWED_MINTS,
+            LotteryError::TooManyAllowedMints
+        );
+        config.allowed_mints = allowed_mints;
+    }
+    
+    if let Some(max_entry_fee) = max_entry_fee {
+        config.max_entry_fee = max_entry_fee;
+    }
+    
+    emit!(ConfigUpdatedEvent {
+        admin: config.admin,
+        allowed_mints: config.allowed_mints.clone(),
+        max_entry_fee: config.max_entry_fee,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
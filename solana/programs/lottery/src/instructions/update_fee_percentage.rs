@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::{state::*, errors::*};
+
+#[derive(Accounts)]
+pub struct UpdateFeePercentage<'info> {
+    #[account(
+        constraint = role_registry.has_role(Role::FeeManager, &fee_manager.key()) @ LotteryError::MissingRole
+    )]
+    pub fee_manager: Signer<'info>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury_state.bump
+    )]
+    pub treasury_state: Account<'info, TreasuryState>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<UpdateFeePercentage>, fee_percentage: u8) -> Result<()> {
+    // Same bounds as initialize
+    require!(
+        fee_percentage > 0 && fee_percentage <= 50,
+        LotteryError::InvalidFeePercentage
+    );
+    
+    let treasury_state = &mut ctx.accounts.treasury_state;
+    let old_fee_percentage = treasury_state.fee_percentage;
+    treasury_state.fee_percentage = fee_percentage;
+    
+    emit!(FeePercentageUpdatedEvent {
+        updated_by: ctx.accounts.fee_manager.key(),
+        old_fee_percentage,
+        new_fee_percentage: fee_percentage,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
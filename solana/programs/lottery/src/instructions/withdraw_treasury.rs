@@ -1,15 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
+use settlement::escrow::transfer_tokens_from_pda;
 use crate::{state::*, errors::*};
 
 #[derive(Accounts)]
+#[instruction(proposal_id: u64)]
 pub struct WithdrawTreasury<'info> {
     #[account(
         mut,
-        constraint = authority.key() == treasury_state.authority @ LotteryError::Unauthorized
+        constraint = role_registry.has_role(Role::TreasuryWithdrawer, &authority.key()) @ LotteryError::MissingRole
     )]
     pub authority: Signer<'info>,
     
+    /// Role registry
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
     #[account(
         mut,
         seeds = [b"treasury"],
@@ -17,6 +26,15 @@ pub struct WithdrawTreasury<'info> {
     )]
     pub treasury_state: Account<'info, TreasuryState>,
     
+    /// Approved withdrawal proposal
+    #[account(
+        mut,
+        seeds = [b"withdrawal", proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.is_open() @ LotteryError::ProposalClosed
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
     /// Treasury token account
     #[account(
         mut,
@@ -24,10 +42,10 @@ pub struct WithdrawTreasury<'info> {
     )]
     pub treasury_token_account: Account<'info, TokenAccount>,
     
-    /// Destination token account (owned by authority)
+    /// Destination token account named in the proposal
     #[account(
         mut,
-        constraint = destination_token_account.owner == authority.key(),
+        constraint = destination_token_account.key() == proposal.destination @ LotteryError::DestinationMismatch,
         constraint = destination_token_account.mint == treasury_token_account.mint
     )]
     pub destination_token_account: Account<'info, TokenAccount>,
@@ -38,28 +56,29 @@ pub struct WithdrawTreasury<'info> {
 
 pub fn handler(
     ctx: Context<WithdrawTreasury>,
-    amount: Option<u64>, // None means withdraw all
+    proposal_id: u64,
 ) -> Result<()> {
     let treasury_state = &mut ctx.accounts.treasury_state;
+    let proposal = &mut ctx.accounts.proposal;
     let clock = &ctx.accounts.clock;
     
-    // Determine withdrawal amount
-    let withdrawal_amount = if let Some(amt) = amount {
-        // Validate requested amount
-        require!(
-            amt <= treasury_state.pending_withdrawal,
-            LotteryError::InsufficientTreasuryBalance
-        );
-        amt
-    } else {
-        // Withdraw all pending
-        treasury_state.pending_withdrawal
-    };
-    
-    // Ensure there's something to withdraw
+    // Only approvals from current treasury withdrawers count
+    require!(
+        proposal.valid_approvals(&ctx.accounts.role_registry) >= treasury_state.approval_threshold as usize,
+        LotteryError::InsufficientApprovals
+    );
+    
     require!(
-        withdrawal_amount > 0,
-        LotteryError::NoFundsToWithdraw
+        clock.unix_timestamp >= proposal.executable_at,
+        LotteryError::WithdrawalDelayNotElapsed
+    );
+    
+    let withdrawal_amount = proposal.amount;
+    
+    // Balance may have been drawn down by an earlier proposal
+    require!(
+        withdrawal_amount <= treasury_state.pending_withdrawal,
+        LotteryError::InsufficientTreasuryBalance
     );
     
     // Transfer from treasury to destination
@@ -69,30 +88,36 @@ pub fn handler(
     ];
     let signer_seeds = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.treasury_token_account.to_account_info(),
-        to: ctx.accounts.destination_token_account.to_account_info(),
-        authority: treasury_state.to_account_info(),
-    };
-    
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-    
-    token::transfer(cpi_ctx, withdrawal_amount)?;
+    transfer_tokens_from_pda(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.destination_token_account.to_account_info(),
+        &treasury_state.to_account_info(),
+        signer_seeds,
+        withdrawal_amount,
+    )?;
     
     // Update treasury state
     treasury_state.pending_withdrawal = treasury_state.pending_withdrawal
         .checked_sub(withdrawal_amount)
         .ok_or(LotteryError::ArithmeticOverflow)?;
+    treasury_state.total_distributed = treasury_state.total_distributed
+        .checked_add(withdrawal_amount)
+        .ok_or(LotteryError::ArithmeticOverflow)?;
+    
+    proposal.executed = true;
     
     // Emit event
     emit!(TreasuryWithdrawalEvent {
+        proposal_id,
         authority: ctx.accounts.authority.key(),
+        destination: proposal.destination,
         amount: withdrawal_amount,
         remaining_balance: treasury_state.pending_withdrawal,
         total_collected: treasury_state.total_collected,
+        total_distributed: treasury_state.total_distributed,
         timestamp: clock.unix_timestamp,
     });
     
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,15 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
-declare_id!("11111111111111111111111111111111"); // Replace with actual program ID
+declare_id!("EP4Tr2vPKcQU5WD7mng8kM56pttU97igHep5Y23ZFEuJ");
 
 pub mod state;
 pub mod errors;
 pub mod instructions;
 pub mod utils;
 
-use state::*;
-use errors::*;
 use instructions::*;
 
 #[program]
@@ -25,7 +22,7 @@ pub mod telegram_lottery {
         instructions::initialize::handler(ctx, treasury_authority, fee_percentage)
     }
 
-    /// Create a new lottery game
+    /// Create a new lottery game (operator role)
     pub fn create_game(
         ctx: Context<CreateGame>,
         game_id: String,
@@ -33,6 +30,7 @@ pub mod telegram_lottery {
         max_players: u8,
         winner_count: u8,
         payment_deadline_minutes: u16,
+        randomness_provider: RandomnessProvider,
     ) -> Result<()> {
         instructions::create_game::handler(
             ctx,
@@ -41,6 +39,7 @@ pub mod telegram_lottery {
             max_players,
             winner_count,
             payment_deadline_minutes,
+            randomness_provider,
         )
     }
 
@@ -62,15 +61,18 @@ pub mod telegram_lottery {
         instructions::select_number::handler(ctx, game_id, number)
     }
 
-    /// Submit VRF result (oracle only - legacy method)
+    /// Start elimination rounds once every player has selected a number (operator role)
+    pub fn start_game(ctx: Context<StartGame>, game_id: String) -> Result<()> {
+        instructions::start_game::handler(ctx, game_id)
+    }
+
+    /// Submit the game oracle's Ed25519 signature over the round seed (signed-oracle games)
     pub fn submit_vrf(
         ctx: Context<SubmitVrf>,
         game_id: String,
         round: u8,
-        random_value: [u8; 32],
-        proof: Vec<u8>,
     ) -> Result<()> {
-        instructions::submit_vrf::handler(ctx, game_id, round, random_value, proof)
+        instructions::submit_vrf::handler(ctx, game_id, round)
     }
     
     /// Request randomness from ORAO VRF
@@ -90,8 +92,28 @@ pub mod telegram_lottery {
     ) -> Result<()> {
         instructions::fulfill_orao_vrf::handler(ctx, game_id, round)
     }
+    
+    /// Commit to a secret for the next round (slot-hash games, operator role)
+    pub fn commit_round_randomness(
+        ctx: Context<CommitRoundRandomness>,
+        game_id: String,
+        round: u8,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::commit_round_randomness::handler(ctx, game_id, round, commitment)
+    }
+    
+    /// Reveal the committed secret and draw the round number (slot-hash games, operator role)
+    pub fn reveal_round_randomness(
+        ctx: Context<RevealRoundRandomness>,
+        game_id: String,
+        round: u8,
+        secret: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_round_randomness::handler(ctx, game_id, round, secret)
+    }
 
-    /// Process elimination round based on VRF result
+    /// Process elimination round based on VRF result (operator role)
     pub fn process_elimination(
         ctx: Context<ProcessElimination>,
         game_id: String,
@@ -100,7 +122,7 @@ pub mod telegram_lottery {
         instructions::process_elimination::handler(ctx, game_id, round)
     }
 
-    /// Complete the game and distribute prizes
+    /// Complete the game and distribute prizes (operator role)
     pub fn complete_game(ctx: Context<CompleteGame>, game_id: String) -> Result<()> {
         instructions::complete_game::handler(ctx, game_id)
     }
@@ -115,7 +137,7 @@ pub mod telegram_lottery {
         instructions::request_refund::handler(ctx, game_id)
     }
 
-    /// Cancel game if conditions not met
+    /// Cancel game if conditions not met (operator role)
     pub fn cancel_game(
         ctx: Context<CancelGame>,
         game_id: String,
@@ -124,12 +146,95 @@ pub mod telegram_lottery {
         instructions::cancel_game::handler(ctx, game_id, reason)
     }
 
-    /// Withdraw treasury fees
-    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: Option<u64>) -> Result<()> {
-        instructions::withdraw_treasury::handler(ctx, amount)
+    /// Execute an approved treasury withdrawal proposal (treasury withdrawer role)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, proposal_id: u64) -> Result<()> {
+        instructions::withdraw_treasury::handler(ctx, proposal_id)
+    }
+
+    /// Raise the withdrawal approval threshold and delay (role admin only)
+    pub fn configure_withdrawals(
+        ctx: Context<ConfigureWithdrawals>,
+        approval_threshold: u8,
+        withdrawal_delay: i64,
+    ) -> Result<()> {
+        instructions::configure_withdrawals::handler(ctx, approval_threshold, withdrawal_delay)
+    }
+
+    /// Propose a treasury withdrawal to the destination account (treasury withdrawer role)
+    pub fn propose_withdrawal(ctx: Context<ProposeWithdrawal>, amount: u64) -> Result<()> {
+        instructions::propose_withdrawal::handler(ctx, amount)
+    }
+
+    /// Approve a pending treasury withdrawal (treasury withdrawer role)
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>, proposal_id: u64) -> Result<()> {
+        instructions::approve_withdrawal::handler(ctx, proposal_id)
+    }
+
+    /// Cancel a pending treasury withdrawal (proposer or role admin)
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>, proposal_id: u64) -> Result<()> {
+        instructions::cancel_withdrawal::handler(ctx, proposal_id)
+    }
+
+    /// Create the global lottery config (role admin only)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        allowed_mints: Vec<Pubkey>,
+        max_entry_fee: u64,
+    ) -> Result<()> {
+        instructions::initialize_config::handler(ctx, allowed_mints, max_entry_fee)
+    }
+
+    /// Update allowed token mints and maximum entry fee (config admin only)
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        allowed_mints: Option<Vec<Pubkey>>,
+        max_entry_fee: Option<u64>,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, allowed_mints, max_entry_fee)
+    }
+
+    /// Pause or unpause create, join, play and settle operations (pauser role)
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        instructions::set_pause_flags::handler(ctx, pause_flags)
+    }
+
+    /// Create the approved oracle registry (config admin only)
+    pub fn initialize_oracle_registry(ctx: Context<InitializeOracleRegistry>) -> Result<()> {
+        instructions::initialize_oracle_registry::handler(ctx)
+    }
+
+    /// Approve a randomness oracle for new games (config admin only)
+    pub fn register_oracle(ctx: Context<RegisterOracle>, oracle: Pubkey) -> Result<()> {
+        instructions::register_oracle::handler(ctx, oracle)
+    }
+
+    /// Remove a randomness oracle from the registry (config admin only)
+    pub fn deregister_oracle(ctx: Context<DeregisterOracle>, oracle: Pubkey) -> Result<()> {
+        instructions::deregister_oracle::handler(ctx, oracle)
+    }
+
+    /// Create the role registry (treasury authority becomes role admin)
+    pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+        instructions::initialize_roles::handler(ctx)
+    }
+
+    /// Grant a role to a key (role admin only)
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::grant_role::handler(ctx, role, member)
+    }
+
+    /// Revoke a role from a key (role admin only)
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::revoke_role::handler(ctx, role, member)
+    }
+
+    /// Update the treasury fee percentage (fee manager role)
+    pub fn update_fee_percentage(ctx: Context<UpdateFeePercentage>, fee_percentage: u8) -> Result<()> {
+        instructions::update_fee_percentage::handler(ctx, fee_percentage)
     }
 }
 
 // Re-export for external use
-pub use state::{GameState, GameStatus, Player, TreasuryState, VrfResult};
-pub use errors::LotteryError;
\ No newline at end of file
+pub use state::{GameState, GameStatus, LotteryConfig, OracleRegistry, Player, Role, RoleRegistry, TreasuryState, VrfResult, WithdrawalProposal};
+pub use errors::LotteryError;
+pub use randomness::{RandomnessProvider, RandomnessRequest};
\ No newline at end of file
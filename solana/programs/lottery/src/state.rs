@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+pub use randomness::{RandomnessProvider, RandomnessRequest};
 
 /// Main game state account
 #[account]
+#[derive(Debug)]
 pub struct GameState {
     /// Unique game identifier
     pub game_id: String,
@@ -19,8 +22,10 @@ pub struct GameState {
     pub state: GameStatus,
     /// Total prize pool collected
     pub prize_pool: u64,
-    /// Treasury fee amount (10%)
+    /// Treasury fee accrued from entry fees
     pub treasury_fee: u64,
+    /// Treasury fee rate in basis points, fixed at creation
+    pub fee_bps: u16,
     /// Number range for selection
     pub number_range: NumberRange,
     /// Unix timestamp when created
@@ -45,6 +50,10 @@ pub struct GameState {
     pub vrf_request_pending: bool,
     /// Round number for pending VRF request
     pub pending_round: u8,
+    /// Randomness provider chosen at creation
+    pub randomness_provider: RandomnessProvider,
+    /// Open randomness request for the pending round
+    pub randomness_request: Option<RandomnessRequest>,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -65,6 +74,7 @@ impl GameState {
         1 + 1 +                               // state (enum)
         8 +                                    // prize_pool
         8 +                                    // treasury_fee
+        2 +                                    // fee_bps
         1 + 1 +                               // number_range
         8 +                                    // created_at
         1 + 8 +                               // started_at (Option)
@@ -77,7 +87,14 @@ impl GameState {
         32 +                                   // vrf_oracle
         1 +                                    // vrf_request_pending
         1 +                                    // pending_round
+        RandomnessProvider::LEN +              // randomness_provider
+        1 + RandomnessRequest::LEN +          // randomness_request (Option)
         1;                                     // bump
+    
+    /// Randomness seed for a round, unique per game and round
+    pub fn round_seed(game: &Pubkey, round: u8) -> [u8; 32] {
+        hashv(&[game.as_ref(), &[round]]).to_bytes()
+    }
 }
 
 /// Player information
@@ -116,6 +133,7 @@ impl Player {
 
 /// Treasury state for fee collection
 #[account]
+#[derive(Debug)]
 pub struct TreasuryState {
     /// Authority that can withdraw (multisig or DAO)
     pub authority: Pubkey,
@@ -131,9 +149,20 @@ pub struct TreasuryState {
     pub treasury_token_account: Pubkey,
     /// Bump seed
     pub bump: u8,
+    /// Treasury withdrawer approvals required to execute a withdrawal
+    pub approval_threshold: u8,
+    /// Seconds a withdrawal proposal must wait before execution
+    pub withdrawal_delay: i64,
+    /// Number of withdrawal proposals created (next proposal id)
+    pub proposal_count: u64,
 }
 
 impl TreasuryState {
+    pub const MAX_WITHDRAWAL_DELAY: i64 = 7 * 24 * 60 * 60;
+    /// Approvals required until the role admin raises it
+    pub const DEFAULT_APPROVAL_THRESHOLD: u8 = 2;
+    /// Delay applied until the role admin raises it
+    pub const DEFAULT_WITHDRAWAL_DELAY: i64 = 24 * 60 * 60;
     pub const SIZE: usize = 
         8 +                                    // discriminator
         32 +                                   // authority
@@ -142,11 +171,194 @@ impl TreasuryState {
         8 +                                    // pending_withdrawal
         1 +                                    // fee_percentage
         32 +                                   // treasury_token_account
+        1 +                                    // bump
+        1 +                                    // approval_threshold
+        8 +                                    // withdrawal_delay
+        8;                                     // proposal_count
+}
+
+/// Treasury withdrawal awaiting M-of-N approval
+#[account]
+#[derive(Debug)]
+pub struct WithdrawalProposal {
+    /// Sequential proposal id
+    pub id: u64,
+    /// Treasury withdrawer that proposed it
+    pub proposer: Pubkey,
+    /// Amount to withdraw
+    pub amount: u64,
+    /// Destination token account
+    pub destination: Pubkey,
+    /// Treasury withdrawers that approved it
+    pub approvals: Vec<Pubkey>,
+    /// Unix timestamp when proposed
+    pub created_at: i64,
+    /// Earliest execution time
+    pub executable_at: i64,
+    /// Whether the withdrawal has been executed
+    pub executed: bool,
+    /// Whether the proposal has been cancelled
+    pub cancelled: bool,
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl WithdrawalProposal {
+    pub const SIZE: usize = 
+        8 +                                    // discriminator
+        8 +                                    // id
+        32 +                                   // proposer
+        8 +                                    // amount
+        32 +                                   // destination
+        4 + 32 * RoleRegistry::MAX_MEMBERS_PER_ROLE + // approvals
+        8 +                                    // created_at
+        8 +                                    // executable_at
+        1 +                                    // executed
+        1 +                                    // cancelled
+        1;                                     // bump
+    
+    /// Approvals from keys that still hold the treasury withdrawer role
+    pub fn valid_approvals(&self, role_registry: &RoleRegistry) -> usize {
+        self.approvals
+            .iter()
+            .filter(|key| role_registry.has_role(Role::TreasuryWithdrawer, key))
+            .count()
+    }
+    
+    /// Whether the proposal is still open for approval or execution
+    pub fn is_open(&self) -> bool {
+        !self.executed && !self.cancelled
+    }
+}
+
+/// Global lottery configuration (single PDA)
+#[account]
+#[derive(Debug)]
+pub struct LotteryConfig {
+    /// Authority that updates mints and fee limits
+    pub admin: Pubkey,
+    /// Paused operations bitmask (see `PAUSE_*`)
+    pub pause_flags: u8,
+    /// Token mints games may be created with
+    pub allowed_mints: Vec<Pubkey>,
+    /// Maximum entry fee per game (token base units)
+    pub max_entry_fee: u64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl LotteryConfig {
+    pub const PAUSE_CREATE: u8 = 1 << 0;
+    pub const PAUSE_JOIN: u8 = 1 << 1;
+    pub const PAUSE_PLAY: u8 = 1 << 2;
+    pub const PAUSE_SETTLE: u8 = 1 << 3;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_CREATE | Self::PAUSE_JOIN | Self::PAUSE_PLAY | Self::PAUSE_SETTLE;
+    pub const MAX_ALLOWED_MINTS: usize = 8;
+    pub const SIZE: usize = 
+        8 +                                    // discriminator
+        32 +                                   // admin
+        1 +                                    // pause_flags
+        4 + 32 * Self::MAX_ALLOWED_MINTS +    // allowed_mints
+        8 +                                    // max_entry_fee
+        1;                                     // bump
+    
+    /// Check if the given operation is paused
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+}
+
+/// Approved randomness oracles (single PDA)
+#[account]
+#[derive(Debug)]
+pub struct OracleRegistry {
+    /// Oracle keys allowed to back new games
+    pub oracles: Vec<Pubkey>,
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl OracleRegistry {
+    pub const MAX_ORACLES: usize = 16;
+    pub const SIZE: usize = 
+        8 +                                    // discriminator
+        4 + 32 * Self::MAX_ORACLES +          // oracles
+        1;                                     // bump
+    
+    /// Check if `oracle` is approved
+    pub fn is_registered(&self, oracle: &Pubkey) -> bool {
+        self.oracles.contains(oracle)
+    }
+}
+
+/// Operational roles granted by the role admin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    /// Creates games and cranks rounds
+    Operator,
+    /// Pauses and unpauses program operations
+    Pauser,
+    /// Changes fee configuration
+    FeeManager,
+    /// Withdraws collected treasury fees
+    TreasuryWithdrawer,
+}
+
+/// Role membership registry (single PDA)
+#[account]
+#[derive(Debug)]
+pub struct RoleRegistry {
+    /// Authority that grants and revokes roles
+    pub admin: Pubkey,
+    /// Game operators
+    pub operators: Vec<Pubkey>,
+    /// Pausers
+    pub pausers: Vec<Pubkey>,
+    /// Fee managers
+    pub fee_managers: Vec<Pubkey>,
+    /// Treasury withdrawers
+    pub treasury_withdrawers: Vec<Pubkey>,
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl RoleRegistry {
+    pub const MAX_MEMBERS_PER_ROLE: usize = 8;
+    pub const SIZE: usize = 
+        8 +                                    // discriminator
+        32 +                                   // admin
+        4 * (4 + 32 * Self::MAX_MEMBERS_PER_ROLE) + // role member vectors
         1;                                     // bump
+    
+    /// Members holding the given role
+    pub fn members(&self, role: Role) -> &Vec<Pubkey> {
+        match role {
+            Role::Operator => &self.operators,
+            Role::Pauser => &self.pausers,
+            Role::FeeManager => &self.fee_managers,
+            Role::TreasuryWithdrawer => &self.treasury_withdrawers,
+        }
+    }
+    
+    /// Mutable members holding the given role
+    pub fn members_mut(&mut self, role: Role) -> &mut Vec<Pubkey> {
+        match role {
+            Role::Operator => &mut self.operators,
+            Role::Pauser => &mut self.pausers,
+            Role::FeeManager => &mut self.fee_managers,
+            Role::TreasuryWithdrawer => &mut self.treasury_withdrawers,
+        }
+    }
+    
+    /// Check if `key` holds `role`
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        self.members(role).contains(key)
+    }
 }
 
 /// VRF result for verifiable randomness
 #[account]
+#[derive(Debug)]
 pub struct VrfResult {
     /// Game ID this result belongs to
     pub game_id: String,
@@ -210,6 +422,7 @@ pub enum CancelReason {
 
 /// Player list account (separate to handle dynamic sizing)
 #[account]
+#[derive(Debug)]
 pub struct PlayerList {
     /// Game ID this list belongs to
     pub game_id: String,
@@ -229,15 +442,19 @@ impl PlayerList {
 
 // Events
 #[event]
+#[derive(Clone, Debug)]
 pub struct GameCreatedEvent {
     pub game_id: String,
     pub authority: Pubkey,
+    pub vrf_oracle: Pubkey,
+    pub randomness_provider: RandomnessProvider,
     pub entry_fee: u64,
     pub max_players: u8,
     pub timestamp: i64,
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct PlayerJoinedEvent {
     pub game_id: String,
     pub player: Pubkey,
@@ -246,6 +463,7 @@ pub struct PlayerJoinedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct GameCompletedEvent {
     pub game_id: String,
     pub winners: Vec<Pubkey>,
@@ -255,6 +473,7 @@ pub struct GameCompletedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct PrizeClaimedEvent {
     pub game_id: String,
     pub winner: Pubkey,
@@ -263,6 +482,7 @@ pub struct PrizeClaimedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct GameCancelledEvent {
     pub game_id: String,
     pub reason: String,
@@ -273,6 +493,7 @@ pub struct GameCancelledEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct TreasuryInitializedEvent {
     pub authority: Pubkey,
     pub treasury_token_account: Pubkey,
@@ -281,6 +502,7 @@ pub struct TreasuryInitializedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct NumberSelectedEvent {
     pub game_id: String,
     pub player: Pubkey,
@@ -289,6 +511,7 @@ pub struct NumberSelectedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct AllNumbersSelectedEvent {
     pub game_id: String,
     pub total_players: u8,
@@ -296,6 +519,15 @@ pub struct AllNumbersSelectedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
+pub struct GameStartedEvent {
+    pub game_id: String,
+    pub player_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
 pub struct VrfSubmittedEvent {
     pub game_id: String,
     pub round: u8,
@@ -304,6 +536,27 @@ pub struct VrfSubmittedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
+pub struct RoundRandomnessCommittedEvent {
+    pub game_id: String,
+    pub round: u8,
+    pub commitment: [u8; 32],
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RoundRandomnessRevealedEvent {
+    pub game_id: String,
+    pub round: u8,
+    pub drawn_number: u8,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
 pub struct EliminationProcessedEvent {
     pub game_id: String,
     pub round: u8,
@@ -314,6 +567,7 @@ pub struct EliminationProcessedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct GameReadyToCompleteEvent {
     pub game_id: String,
     pub winner_count: u8,
@@ -321,12 +575,14 @@ pub struct GameReadyToCompleteEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct AllPrizesClaimedEvent {
     pub game_id: String,
     pub timestamp: i64,
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct RefundProcessedEvent {
     pub game_id: String,
     pub player: Pubkey,
@@ -335,6 +591,7 @@ pub struct RefundProcessedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct AllRefundsProcessedEvent {
     pub game_id: String,
     pub total_refunded: u64,
@@ -342,10 +599,112 @@ pub struct AllRefundsProcessedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
+pub struct WithdrawalProposedEvent {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct WithdrawalApprovedEvent {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct WithdrawalCancelledEvent {
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct WithdrawalConfigUpdatedEvent {
+    pub updated_by: Pubkey,
+    pub approval_threshold: u8,
+    pub withdrawal_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
 pub struct TreasuryWithdrawalEvent {
+    pub proposal_id: u64,
     pub authority: Pubkey,
+    pub destination: Pubkey,
     pub amount: u64,
     pub remaining_balance: u64,
     pub total_collected: u64,
+    pub total_distributed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RoleGrantedEvent {
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct RoleRevokedEvent {
+    pub role: Role,
+    pub member: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct FeePercentageUpdatedEvent {
+    pub updated_by: Pubkey,
+    pub old_fee_percentage: u8,
+    pub new_fee_percentage: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub allowed_mints: Vec<Pubkey>,
+    pub max_entry_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct PauseFlagsUpdatedEvent {
+    pub pauser: Pubkey,
+    pub old_pause_flags: u8,
+    pub new_pause_flags: u8,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct OracleRegisteredEvent {
+    pub oracle: Pubkey,
+    pub registered_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct OracleDeregisteredEvent {
+    pub oracle: Pubkey,
+    pub deregistered_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,42 +1,32 @@
 use anchor_lang::prelude::*;
+use settlement::fees::{percent_to_bps, split_evenly, split_fee};
+use settlement::range::uniform_in_range;
+use crate::errors::LotteryError;
 
 /// Calculate the prize distribution for winners
+///
+/// Returns the prize per winner and the remainder left after an even split.
 pub fn calculate_prize_distribution(
     total_prize_pool: u64,
     treasury_fee: u64,
     winner_count: usize,
-) -> Result<u64> {
+) -> Result<(u64, u64)> {
     // Calculate distributable amount (total - treasury fee)
     let distributable = total_prize_pool
         .checked_sub(treasury_fee)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(LotteryError::ArithmeticOverflow)?;
     
-    // Calculate prize per winner
-    let prize_per_winner = distributable
-        .checked_div(winner_count as u64)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    
-    Ok(prize_per_winner)
+    split_evenly(distributable, winner_count as u64)
 }
 
-/// Generate a number from random bytes within a range
+/// Map VRF output onto the inclusive number range without modulo bias
 pub fn generate_number_from_random(
     random_bytes: &[u8],
     min: u8,
     max: u8,
-) -> u8 {
-    // Use first 8 bytes to generate a u64
-    let random_u64 = u64::from_le_bytes(
-        random_bytes[..8]
-            .try_into()
-            .expect("slice with incorrect length")
-    );
-    
-    // Calculate range
-    let range = (max - min + 1) as u64;
-    
-    // Generate number within range
-    (random_u64 % range) as u8 + min
+) -> Result<u8> {
+    let number = uniform_in_range(random_bytes, min as u64, max as u64)?;
+    Ok(number as u8)
 }
 
 /// Validate game configuration
@@ -47,28 +37,25 @@ pub fn validate_game_config(
 ) -> Result<()> {
     require!(
         entry_fee > 0,
-        ProgramError::InvalidArgument
+        LotteryError::InvalidEntryFee
     );
     
     require!(
-        max_players >= 2 && max_players <= 100,
-        ProgramError::InvalidArgument
+        (2..=100).contains(&max_players),
+        LotteryError::InvalidMaxPlayers
     );
     
     require!(
         winner_count > 0 && winner_count < max_players,
-        ProgramError::InvalidArgument
+        LotteryError::InvalidWinnerCount
     );
     
     Ok(())
 }
 
-/// Calculate treasury fee based on percentage
+/// Calculate treasury fee based on percentage, rounded down
 pub fn calculate_treasury_fee(amount: u64, fee_percentage: u8) -> Result<u64> {
-    amount
-        .checked_mul(fee_percentage as u64)
-        .and_then(|v| v.checked_div(100))
-        .ok_or(ProgramError::ArithmeticOverflow)
+    Ok(split_fee(amount, percent_to_bps(fee_percentage)?)?.fee)
 }
 
 #[cfg(test)]
@@ -81,8 +68,15 @@ mod tests {
         let fee = 100;
         let winners = 3;
         
-        let prize = calculate_prize_distribution(total, fee, winners).unwrap();
+        let (prize, remainder) = calculate_prize_distribution(total, fee, winners).unwrap();
         assert_eq!(prize, 300); // (1000 - 100) / 3 = 300
+        assert_eq!(remainder, 0);
+        
+        let (prize, remainder) = calculate_prize_distribution(1000, 99, 2).unwrap();
+        assert_eq!((prize, remainder), (450, 1));
+        
+        assert!(calculate_prize_distribution(1000, 100, 0).is_err());
+        assert!(calculate_prize_distribution(100, 101, 1).is_err());
     }
 
     #[test]
@@ -91,8 +85,14 @@ mod tests {
         let min = 1;
         let max = 10;
         
-        let number = generate_number_from_random(&random_bytes, min, max);
+        let number = generate_number_from_random(&random_bytes, min, max).unwrap();
         assert!(number >= min && number <= max);
+        
+        // Extreme words land on the range ends
+        assert_eq!(generate_number_from_random(&[0u8; 8], 1, 6).unwrap(), 1);
+        assert_eq!(generate_number_from_random(&[0xFFu8; 8], 1, 6).unwrap(), 6);
+        assert_eq!(generate_number_from_random(&[7u8; 8], 0, 255).unwrap(), 7);
+        assert!(generate_number_from_random(&[0u8; 4], 1, 6).is_err());
     }
 
     #[test]
@@ -102,5 +102,19 @@ mod tests {
         
         let fee = calculate_treasury_fee(amount, percentage).unwrap();
         assert_eq!(fee, 100); // 10% of 1000 = 100
+        
+        assert_eq!(calculate_treasury_fee(999, 10).unwrap(), 99);
+        assert_eq!(calculate_treasury_fee(u64::MAX, 50).unwrap(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn test_validate_game_config() {
+        assert!(validate_game_config(1, 4, 2).is_ok());
+        
+        assert_eq!(validate_game_config(0, 4, 2), Err(LotteryError::InvalidEntryFee.into()));
+        assert_eq!(validate_game_config(1, 1, 1), Err(LotteryError::InvalidMaxPlayers.into()));
+        assert_eq!(validate_game_config(1, 101, 1), Err(LotteryError::InvalidMaxPlayers.into()));
+        assert_eq!(validate_game_config(1, 4, 0), Err(LotteryError::InvalidWinnerCount.into()));
+        assert_eq!(validate_game_config(1, 4, 4), Err(LotteryError::InvalidWinnerCount.into()));
     }
-}
\ No newline at end of file
+}
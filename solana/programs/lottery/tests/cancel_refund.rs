@@ -0,0 +1,191 @@
+//! Cancellation windows, deadline expiry and refunds
+
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+use telegram_lottery::errors::LotteryError;
+use telegram_lottery::state::GameStatus;
+
+const MINUTE: i64 = 60;
+const DAY: i64 = 24 * 60 * MINUTE;
+
+#[tokio::test]
+async fn test_payment_deadline_expiry_and_refunds() {
+    let mut test = LotteryTest::start(4).await;
+    let game_id = "deadline";
+    let args = test.game_args(game_id, 4, 1);
+    test.create_game(&args).await;
+    test.join(game_id, 0).await;
+    test.join(game_id, 1).await;
+    assert_eq!(test.escrow_balance(game_id).await, 2 * ENTRY_FEE);
+
+    // Players have joined and the deadline is still open
+    let operator = test.clone_keypair(&test.operator);
+    let cancel = test.cancel_game_ix(&operator.pubkey(), game_id, "not enough players");
+    test.expect_error(
+        std::slice::from_ref(&cancel),
+        &[&operator],
+        LotteryError::CannotCancelActiveGame,
+    )
+    .await;
+
+    test.warp_seconds(PAYMENT_DEADLINE_MINUTES as i64 * MINUTE + 1)
+        .await;
+
+    // Late joiners are turned away without paying
+    let late = test.clone_keypair(&test.players[2].keypair);
+    let join = test.join_game_ix(game_id, 2);
+    test.expect_error(&[join], &[&late], LotteryError::PaymentDeadlineExpired)
+        .await;
+    assert_eq!(test.player_balance(2).await, STARTING_BALANCE);
+    assert_eq!(test.escrow_balance(game_id).await, 2 * ENTRY_FEE);
+
+    test.send(&[cancel], &[&operator]).await.unwrap();
+    assert_eq!(test.game(game_id).await.state, GameStatus::Cancelled);
+    assert_eq!(test.escrow_balance(game_id).await, 2 * ENTRY_FEE);
+
+    // Refunds restore each player and drain the escrow
+    test.refund(game_id, 0).await;
+    assert_eq!(test.player_balance(0).await, STARTING_BALANCE);
+    assert_eq!(test.escrow_balance(game_id).await, ENTRY_FEE);
+    test.refund(game_id, 1).await;
+    assert_eq!(test.player_balance(1).await, STARTING_BALANCE);
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+    assert_eq!(test.treasury_balance().await, 0);
+
+    let first = test.clone_keypair(&test.players[0].keypair);
+    let refund = test.request_refund_ix(game_id, 0);
+    test.expect_error(&[refund], &[&first], LotteryError::RefundAlreadyProcessed)
+        .await;
+    let refund = test.request_refund_ix(game_id, 2);
+    test.expect_error(&[refund], &[&late], LotteryError::PlayerNotInGame)
+        .await;
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+}
+
+#[tokio::test]
+async fn test_empty_game_cancels_immediately() {
+    let mut test = LotteryTest::start(1).await;
+    let game_id = "empty";
+    let args = test.game_args(game_id, 4, 1);
+    test.create_game(&args).await;
+
+    test.cancel(game_id, "no players").await;
+    let game = test.game(game_id).await;
+    assert_eq!(game.state, GameStatus::Cancelled);
+    assert!(game.completed_at.is_some());
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+
+    // A cancelled game cannot be cancelled or joined again
+    let operator = test.clone_keypair(&test.operator);
+    let cancel = test.cancel_game_ix(&operator.pubkey(), game_id, "again");
+    test.expect_error(&[cancel], &[&operator], LotteryError::CannotCancelGame)
+        .await;
+    let player = test.clone_keypair(&test.players[0].keypair);
+    let join = test.join_game_ix(game_id, 0);
+    test.expect_error(&[join], &[&player], LotteryError::InvalidGameState)
+        .await;
+}
+
+#[tokio::test]
+async fn test_number_selection_timeout() {
+    let mut test = LotteryTest::start(2).await;
+    let game_id = "selection";
+    let args = test.game_args(game_id, 2, 1);
+    test.create_game(&args).await;
+    test.join(game_id, 0).await;
+    test.join(game_id, 1).await;
+    test.select(game_id, 0, 3).await;
+    assert_eq!(test.game(game_id).await.state, GameStatus::NumberSelection);
+
+    // Selection stays open for a day after the game fills
+    let operator = test.clone_keypair(&test.operator);
+    let cancel = test.cancel_game_ix(&operator.pubkey(), game_id, "selection stalled");
+    test.expect_error(
+        std::slice::from_ref(&cancel),
+        &[&operator],
+        LotteryError::CannotCancelActiveGame,
+    )
+    .await;
+    test.warp_seconds(DAY).await;
+    test.expect_error(
+        std::slice::from_ref(&cancel),
+        &[&operator],
+        LotteryError::CannotCancelActiveGame,
+    )
+    .await;
+    test.warp_seconds(1).await;
+    test.send(&[cancel], &[&operator]).await.unwrap();
+    assert_eq!(test.game(game_id).await.state, GameStatus::Cancelled);
+
+    // Selecting after cancellation is rejected
+    let player = test.clone_keypair(&test.players[1].keypair);
+    let select = test.select_number_ix(game_id, 1, 4);
+    test.expect_error(&[select], &[&player], LotteryError::InvalidGameState)
+        .await;
+
+    test.refund(game_id, 0).await;
+    test.refund(game_id, 1).await;
+    assert_eq!(test.player_balance(0).await, STARTING_BALANCE);
+    assert_eq!(test.player_balance(1).await, STARTING_BALANCE);
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+}
+
+#[tokio::test]
+async fn test_cancel_while_playing_refunds_eliminated_players() {
+    let mut test = LotteryTest::start(3).await;
+    let game_id = "playing";
+    let args = test.game_args(game_id, 3, 1);
+    test.start_playing(&args, &[1, 2, 3]).await;
+    test.draw(game_id, 1, 2).await;
+    assert_eq!(
+        test.player_list(game_id).await.players[1].eliminated_round,
+        Some(1)
+    );
+
+    // Refunds are only open once the game is cancelled
+    let eliminated = test.clone_keypair(&test.players[1].keypair);
+    let refund = test.request_refund_ix(game_id, 1);
+    test.expect_error(&[refund], &[&eliminated], LotteryError::GameNotCancelled)
+        .await;
+
+    test.cancel(game_id, "oracle outage").await;
+    assert_eq!(test.game(game_id).await.state, GameStatus::Cancelled);
+    for player in 0..3 {
+        test.refund(game_id, player).await;
+        assert_eq!(test.player_balance(player).await, STARTING_BALANCE);
+        assert_eq!(
+            test.escrow_balance(game_id).await,
+            (2 - player as u64) * ENTRY_FEE
+        );
+    }
+    assert_eq!(test.treasury_balance().await, 0);
+    assert_eq!(test.treasury_state().await.total_collected, 0);
+}
+
+#[tokio::test]
+async fn test_settled_game_cannot_be_cancelled() {
+    let mut test = LotteryTest::start(2).await;
+    let game_id = "settled";
+    let args = test.game_args(game_id, 2, 1);
+    test.start_playing(&args, &[1, 2]).await;
+    test.draw(game_id, 1, 1).await;
+    test.complete(game_id).await;
+    assert_eq!(test.game(game_id).await.state, GameStatus::Distributing);
+
+    let escrow = test.escrow_balance(game_id).await;
+    let operator = test.clone_keypair(&test.operator);
+    let cancel = test.cancel_game_ix(&operator.pubkey(), game_id, "too late");
+    test.expect_error(&[cancel], &[&operator], LotteryError::CannotCancelGame)
+        .await;
+
+    // Escrow is untouched and stays claimable by the winner
+    let loser = test.clone_keypair(&test.players[0].keypair);
+    let refund = test.request_refund_ix(game_id, 0);
+    test.expect_error(&[refund], &[&loser], LotteryError::GameNotCancelled)
+        .await;
+    assert_eq!(test.escrow_balance(game_id).await, escrow);
+    test.claim(game_id, 1).await;
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+}
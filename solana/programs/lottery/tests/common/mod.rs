@@ -0,0 +1,1281 @@
+//! BanksClient harness shared by the lottery program test suites
+//!
+//! Boots the lottery next to the mock ORAO VRF program (deployed at ORAO's
+//! program id), seeds a fixture MWOR mint with funded player token accounts,
+//! and runs the admin setup every game needs: treasury, roles, config and the
+//! oracle registry.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption,
+    program_pack::Pack, system_instruction, system_program, sysvar,
+};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::spl_token;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use telegram_lottery::state::{
+    GameState, LotteryConfig, OracleRegistry, PlayerList, RandomnessProvider, Role, RoleRegistry,
+    TreasuryState, VrfResult, WithdrawalProposal,
+};
+
+/// MWOR token decimals
+pub const MWOR_DECIMALS: u8 = 6;
+/// One whole MWOR token in base units
+pub const MWOR: u64 = 1_000_000;
+/// Default game entry fee
+pub const ENTRY_FEE: u64 = 10 * MWOR;
+/// Config cap on entry fees
+pub const MAX_ENTRY_FEE: u64 = 100 * MWOR;
+/// Treasury fee percentage set at initialization
+pub const FEE_PERCENTAGE: u8 = 10;
+/// MWOR preloaded into every player token account
+pub const STARTING_BALANCE: u64 = 1_000 * MWOR;
+/// Lamports charged by the mock ORAO network per request
+pub const ORAO_REQUEST_FEE: u64 = 2_000_000;
+/// Default payment deadline for new games
+pub const PAYMENT_DEADLINE_MINUTES: u16 = 30;
+
+// Anchor's entrypoints tie the account slice to 'info, which program-test cannot provide
+fn process_lottery(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    telegram_lottery::entry(program_id, accounts, data)
+}
+
+fn process_mock_orao(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_orao::entry(program_id, accounts, data)
+}
+
+/// Anchor error code for a lottery, randomness or framework error
+pub fn code(error: impl Into<u32>) -> u32 {
+    error.into()
+}
+
+/// Custom program error carried by a failed transaction
+pub fn custom_error(error: BanksClientError) -> Option<u32> {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
+
+/// Randomness that makes the lottery draw `number` from `min..=max`
+///
+/// Picks the largest first-lane word mapping to `number`, which is never in the
+/// rejected zone, so the draw resolves on the first lane.
+pub fn randomness_for(number: u8, min: u8, max: u8) -> [u8; 64] {
+    let range = (max - min) as u128 + 1;
+    let index = (number - min) as u128;
+    let word = (((index + 1) << 64) - 1) / range;
+    let mut randomness = [0xA5u8; 64];
+    randomness[..8].copy_from_slice(&(word as u64).to_le_bytes());
+    randomness
+}
+
+pub fn treasury_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury"], &telegram_lottery::ID).0
+}
+
+pub fn roles_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"roles"], &telegram_lottery::ID).0
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &telegram_lottery::ID).0
+}
+
+pub fn oracles_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"oracles"], &telegram_lottery::ID).0
+}
+
+pub fn game_pda(game_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&[b"game", game_id.as_bytes()], &telegram_lottery::ID).0
+}
+
+pub fn player_list_pda(game_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&[b"players", game_id.as_bytes()], &telegram_lottery::ID).0
+}
+
+pub fn escrow_pda(game_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", game_id.as_bytes()], &telegram_lottery::ID).0
+}
+
+pub fn vrf_pda(game_id: &str, round: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vrf", game_id.as_bytes(), &[round]],
+        &telegram_lottery::ID,
+    )
+    .0
+}
+
+pub fn withdrawal_pda(proposal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"withdrawal", proposal_id.to_le_bytes().as_ref()],
+        &telegram_lottery::ID,
+    )
+    .0
+}
+
+/// Randomness seed the lottery uses for a game round
+pub fn round_seed(game_id: &str, round: u8) -> [u8; 32] {
+    GameState::round_seed(&game_pda(game_id), round)
+}
+
+/// Player with a funded MWOR token account
+pub struct TestPlayer {
+    pub keypair: Keypair,
+    pub token_account: Pubkey,
+    pub telegram_id: String,
+}
+
+impl TestPlayer {
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+}
+
+/// Parameters for `create_game`
+#[derive(Clone)]
+pub struct GameArgs {
+    pub game_id: String,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub winner_count: u8,
+    pub payment_deadline_minutes: u16,
+    pub randomness_provider: RandomnessProvider,
+    pub token_mint: Pubkey,
+    pub vrf_oracle: Pubkey,
+}
+
+/// Lottery and mock ORAO deployment with funded actors
+pub struct LotteryTest {
+    pub context: ProgramTestContext,
+    pub payer: Keypair,
+    /// Treasury authority, role admin and config admin
+    pub admin: Keypair,
+    /// Holds the operator role
+    pub operator: Keypair,
+    /// Registered game oracle and mock ORAO fulfillment authority
+    pub oracle: Keypair,
+    pub mint_authority: Keypair,
+    pub mwor_mint: Pubkey,
+    pub orao_treasury: Pubkey,
+    pub players: Vec<TestPlayer>,
+    sent: HashSet<Signature>,
+}
+
+impl LotteryTest {
+    /// Boot the programs and fixtures without running any lottery setup
+    pub async fn boot(player_count: usize) -> Self {
+        let mut program_test = ProgramTest::new(
+            "telegram_lottery",
+            telegram_lottery::ID,
+            processor!(process_lottery),
+        );
+        program_test.add_program("mock_orao", mock_orao::ID, processor!(process_mock_orao));
+
+        let admin = Keypair::new();
+        let operator = Keypair::new();
+        let oracle = Keypair::new();
+        let mint_authority = Keypair::new();
+        let mwor_mint = Pubkey::new_unique();
+        let orao_treasury = Pubkey::new_unique();
+
+        for key in [admin.pubkey(), operator.pubkey(), oracle.pubkey()] {
+            program_test.add_account(key, system_account(10_000_000_000));
+        }
+        program_test.add_account(
+            orao_treasury,
+            system_account(Rent::default().minimum_balance(0)),
+        );
+
+        let players: Vec<TestPlayer> = (0..player_count)
+            .map(|i| TestPlayer {
+                keypair: Keypair::new(),
+                token_account: Pubkey::new_unique(),
+                telegram_id: format!("tg-{}", i),
+            })
+            .collect();
+        for player in &players {
+            program_test.add_account(player.pubkey(), system_account(1_000_000_000));
+            program_test.add_account(
+                player.token_account,
+                token_account(&mwor_mint, &player.pubkey(), STARTING_BALANCE),
+            );
+        }
+
+        // Fixture MWOR mint whose supply matches the preloaded player balances
+        program_test.add_account(
+            mwor_mint,
+            mint_account(
+                &mint_authority.pubkey(),
+                STARTING_BALANCE * player_count as u64,
+            ),
+        );
+
+        let context = program_test.start_with_context().await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        Self {
+            context,
+            payer,
+            admin,
+            operator,
+            oracle,
+            mint_authority,
+            mwor_mint,
+            orao_treasury,
+            players,
+            sent: HashSet::new(),
+        }
+    }
+
+    /// Boot and run the full admin setup
+    pub async fn start(player_count: usize) -> Self {
+        let mut test = Self::boot(player_count).await;
+        test.setup().await;
+        test
+    }
+
+    /// Treasury, roles, operator grant, config, oracle registry and mock ORAO network
+    pub async fn setup(&mut self) {
+        let admin = self.admin.pubkey();
+        let instructions = vec![
+            self.initialize_ix(FEE_PERCENTAGE),
+            self.initialize_roles_ix(&admin),
+            self.grant_role_ix(&admin, Role::Operator, self.operator.pubkey()),
+            self.initialize_config_ix(&admin, vec![self.mwor_mint], MAX_ENTRY_FEE),
+            self.initialize_oracle_registry_ix(&admin),
+            self.register_oracle_ix(&admin, self.oracle.pubkey()),
+        ];
+        let admin_keypair = self.clone_keypair(&self.admin);
+        self.send(&instructions, &[&admin_keypair]).await.unwrap();
+
+        let orao = mock_orao::client::initialize(
+            self.payer.pubkey(),
+            self.orao_treasury,
+            ORAO_REQUEST_FEE,
+            vec![self.oracle.pubkey()],
+        );
+        self.send(&[orao], &[]).await.unwrap();
+    }
+
+    pub fn clone_keypair(&self, keypair: &Keypair) -> Keypair {
+        Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+    }
+
+    // ----- transactions -----
+
+    /// Sign with the payer plus `signers` and process; identical transactions
+    /// get a fresh blockhash so they are not rejected as duplicates
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut keypairs: Vec<&Keypair> = vec![&self.payer];
+        for signer in signers {
+            if !keypairs.iter().any(|k| k.pubkey() == signer.pubkey()) {
+                keypairs.push(signer);
+            }
+        }
+
+        let mut blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &keypairs,
+            blockhash,
+        );
+        while self.sent.contains(&transaction.signatures[0]) {
+            blockhash = self
+                .context
+                .get_new_latest_blockhash()
+                .await
+                .expect("new blockhash");
+            transaction = Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.payer.pubkey()),
+                &keypairs,
+                blockhash,
+            );
+        }
+        self.sent.insert(transaction.signatures[0]);
+
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Process and assert the transaction fails with custom error `expected`
+    pub async fn expect_error(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        expected: impl Into<u32>,
+    ) {
+        let expected = expected.into();
+        let error = self
+            .send(instructions, signers)
+            .await
+            .expect_err("transaction should fail");
+        let description = format!("{:?}", error);
+        assert_eq!(
+            custom_error(error),
+            Some(expected),
+            "expected custom error {}, got {}",
+            expected,
+            description
+        );
+    }
+
+    // ----- state -----
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {} missing", address));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn exists(&mut self, address: Pubkey) -> bool {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    pub async fn game(&mut self, game_id: &str) -> GameState {
+        self.account(game_pda(game_id)).await
+    }
+
+    pub async fn player_list(&mut self, game_id: &str) -> PlayerList {
+        self.account(player_list_pda(game_id)).await
+    }
+
+    pub async fn vrf_result(&mut self, game_id: &str, round: u8) -> VrfResult {
+        self.account(vrf_pda(game_id, round)).await
+    }
+
+    pub async fn treasury_state(&mut self) -> TreasuryState {
+        self.account(treasury_pda()).await
+    }
+
+    pub async fn role_registry(&mut self) -> RoleRegistry {
+        self.account(roles_pda()).await
+    }
+
+    pub async fn config(&mut self) -> LotteryConfig {
+        self.account(config_pda()).await
+    }
+
+    pub async fn oracle_registry(&mut self) -> OracleRegistry {
+        self.account(oracles_pda()).await
+    }
+
+    pub async fn proposal(&mut self, proposal_id: u64) -> WithdrawalProposal {
+        self.account(withdrawal_pda(proposal_id)).await
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("token account {} missing", address));
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub async fn escrow_balance(&mut self, game_id: &str) -> u64 {
+        self.token_balance(escrow_pda(game_id)).await
+    }
+
+    pub async fn treasury_balance(&mut self) -> u64 {
+        self.token_balance(self.treasury_token_account()).await
+    }
+
+    pub async fn player_balance(&mut self, index: usize) -> u64 {
+        let token_account = self.players[index].token_account;
+        self.token_balance(token_account).await
+    }
+
+    pub fn treasury_token_account(&self) -> Pubkey {
+        get_associated_token_address(&treasury_pda(), &self.mwor_mint)
+    }
+
+    // ----- clock -----
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+    }
+
+    /// Move the cluster clock forward by `seconds`
+    pub async fn warp_seconds(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    // ----- token fixtures -----
+
+    /// Create and initialize a token account for `owner` on `mint`
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let rent = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+        let instructions = [
+            system_instruction::create_account(
+                &self.payer.pubkey(),
+                &account.pubkey(),
+                rent,
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(
+                &spl_token::ID,
+                &account.pubkey(),
+                mint,
+                owner,
+            )
+            .unwrap(),
+        ];
+        self.send(&instructions, &[&account]).await.unwrap();
+        account.pubkey()
+    }
+
+    /// Create a mint that is not on the config allow list
+    pub async fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let rent = Rent::default().minimum_balance(spl_token::state::Mint::LEN);
+        let instructions = [
+            system_instruction::create_account(
+                &self.payer.pubkey(),
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.mint_authority.pubkey(),
+                None,
+                MWOR_DECIMALS,
+            )
+            .unwrap(),
+        ];
+        self.send(&instructions, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    // ----- game flows -----
+
+    pub fn game_args(&self, game_id: &str, max_players: u8, winner_count: u8) -> GameArgs {
+        GameArgs {
+            game_id: game_id.to_string(),
+            entry_fee: ENTRY_FEE,
+            max_players,
+            winner_count,
+            payment_deadline_minutes: PAYMENT_DEADLINE_MINUTES,
+            randomness_provider: RandomnessProvider::Orao,
+            token_mint: self.mwor_mint,
+            vrf_oracle: self.oracle.pubkey(),
+        }
+    }
+
+    pub async fn create_game(&mut self, args: &GameArgs) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.create_game_ix(&operator.pubkey(), args);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    pub async fn join(&mut self, game_id: &str, player: usize) {
+        let keypair = self.clone_keypair(&self.players[player].keypair);
+        let instruction = self.join_game_ix(game_id, player);
+        self.send(&[instruction], &[&keypair]).await.unwrap();
+    }
+
+    pub async fn select(&mut self, game_id: &str, player: usize, number: u8) {
+        let keypair = self.clone_keypair(&self.players[player].keypair);
+        let instruction = self.select_number_ix(game_id, player, number);
+        self.send(&[instruction], &[&keypair]).await.unwrap();
+    }
+
+    pub async fn start_game(&mut self, game_id: &str) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.start_game_ix(&operator.pubkey(), game_id);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    /// Create a game, fill it with the first `numbers.len()` players, have
+    /// player `i` select `numbers[i]` and start it
+    pub async fn start_playing(&mut self, args: &GameArgs, numbers: &[u8]) {
+        assert_eq!(numbers.len(), args.max_players as usize);
+        self.create_game(args).await;
+        for player in 0..numbers.len() {
+            self.join(&args.game_id, player).await;
+        }
+        for (player, number) in numbers.iter().enumerate() {
+            self.select(&args.game_id, player, *number).await;
+        }
+        self.start_game(&args.game_id).await;
+    }
+
+    /// Open an ORAO request for `round`, paid by the operator
+    pub async fn request_round(&mut self, game_id: &str, round: u8) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.request_orao_vrf_ix(&operator.pubkey(), game_id, round);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    /// Have the mock ORAO network fulfill `round` with `randomness`
+    pub async fn orao_fulfill(&mut self, game_id: &str, round: u8, randomness: [u8; 64]) {
+        let oracle = self.clone_keypair(&self.oracle);
+        let instruction =
+            mock_orao::client::fulfill(oracle.pubkey(), round_seed(game_id, round), randomness);
+        self.send(&[instruction], &[&oracle]).await.unwrap();
+    }
+
+    pub async fn fulfill_round(&mut self, game_id: &str, round: u8) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.fulfill_orao_vrf_ix(&operator.pubkey(), game_id, round);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    pub async fn eliminate(&mut self, game_id: &str, round: u8) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.process_elimination_ix(&operator.pubkey(), game_id, round);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    /// Run a full ORAO round that draws `number`
+    pub async fn draw(&mut self, game_id: &str, round: u8, number: u8) {
+        let range = self.game(game_id).await.number_range;
+        self.request_round(game_id, round).await;
+        self.orao_fulfill(game_id, round, randomness_for(number, range.min, range.max))
+            .await;
+        self.fulfill_round(game_id, round).await;
+        self.eliminate(game_id, round).await;
+    }
+
+    pub async fn complete(&mut self, game_id: &str) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.complete_game_ix(&operator.pubkey(), game_id);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    pub async fn claim(&mut self, game_id: &str, player: usize) {
+        let keypair = self.clone_keypair(&self.players[player].keypair);
+        let instruction = self.claim_prize_ix(game_id, player);
+        self.send(&[instruction], &[&keypair]).await.unwrap();
+    }
+
+    pub async fn cancel(&mut self, game_id: &str, reason: &str) {
+        let operator = self.clone_keypair(&self.operator);
+        let instruction = self.cancel_game_ix(&operator.pubkey(), game_id, reason);
+        self.send(&[instruction], &[&operator]).await.unwrap();
+    }
+
+    pub async fn refund(&mut self, game_id: &str, player: usize) {
+        let keypair = self.clone_keypair(&self.players[player].keypair);
+        let instruction = self.request_refund_ix(game_id, player);
+        self.send(&[instruction], &[&keypair]).await.unwrap();
+    }
+
+    // ----- admin instructions -----
+
+    pub fn initialize_ix(&self, fee_percentage: u8) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::Initialize {
+                authority: self.admin.pubkey(),
+                treasury_state: treasury_pda(),
+                treasury_token_account: self.treasury_token_account(),
+                token_mint: self.mwor_mint,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                rent: sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::Initialize {
+                treasury_authority: self.admin.pubkey(),
+                fee_percentage,
+            }
+            .data(),
+        }
+    }
+
+    pub fn initialize_roles_ix(&self, authority: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::InitializeRoles {
+                authority: *authority,
+                treasury_state: treasury_pda(),
+                role_registry: roles_pda(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::InitializeRoles {}.data(),
+        }
+    }
+
+    pub fn grant_role_ix(&self, admin: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::GrantRole {
+                admin: *admin,
+                role_registry: roles_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::GrantRole { role, member }.data(),
+        }
+    }
+
+    pub fn revoke_role_ix(&self, admin: &Pubkey, role: Role, member: Pubkey) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::RevokeRole {
+                admin: *admin,
+                role_registry: roles_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::RevokeRole { role, member }.data(),
+        }
+    }
+
+    pub fn update_fee_percentage_ix(
+        &self,
+        fee_manager: &Pubkey,
+        fee_percentage: u8,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::UpdateFeePercentage {
+                fee_manager: *fee_manager,
+                role_registry: roles_pda(),
+                treasury_state: treasury_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::UpdateFeePercentage { fee_percentage }.data(),
+        }
+    }
+
+    pub fn initialize_config_ix(
+        &self,
+        admin: &Pubkey,
+        allowed_mints: Vec<Pubkey>,
+        max_entry_fee: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::InitializeConfig {
+                admin: *admin,
+                role_registry: roles_pda(),
+                config: config_pda(),
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::InitializeConfig {
+                allowed_mints,
+                max_entry_fee,
+            }
+            .data(),
+        }
+    }
+
+    pub fn update_config_ix(
+        &self,
+        admin: &Pubkey,
+        allowed_mints: Option<Vec<Pubkey>>,
+        max_entry_fee: Option<u64>,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::UpdateConfig {
+                admin: *admin,
+                config: config_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::UpdateConfig {
+                allowed_mints,
+                max_entry_fee,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_pause_flags_ix(&self, pauser: &Pubkey, pause_flags: u8) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::SetPauseFlags {
+                pauser: *pauser,
+                role_registry: roles_pda(),
+                config: config_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::SetPauseFlags { pause_flags }.data(),
+        }
+    }
+
+    pub fn initialize_oracle_registry_ix(&self, admin: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::InitializeOracleRegistry {
+                admin: *admin,
+                config: config_pda(),
+                oracle_registry: oracles_pda(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::InitializeOracleRegistry {}.data(),
+        }
+    }
+
+    pub fn register_oracle_ix(&self, admin: &Pubkey, oracle: Pubkey) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::RegisterOracle {
+                admin: *admin,
+                config: config_pda(),
+                oracle_registry: oracles_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::RegisterOracle { oracle }.data(),
+        }
+    }
+
+    pub fn deregister_oracle_ix(&self, admin: &Pubkey, oracle: Pubkey) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::DeregisterOracle {
+                admin: *admin,
+                config: config_pda(),
+                oracle_registry: oracles_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::DeregisterOracle { oracle }.data(),
+        }
+    }
+
+    // ----- game instructions -----
+
+    pub fn create_game_ix(&self, authority: &Pubkey, args: &GameArgs) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::CreateGame {
+                authority: *authority,
+                role_registry: roles_pda(),
+                game_state: game_pda(&args.game_id),
+                config: config_pda(),
+                player_list: player_list_pda(&args.game_id),
+                treasury_state: treasury_pda(),
+                token_mint: args.token_mint,
+                escrow_account: escrow_pda(&args.game_id),
+                oracle_registry: oracles_pda(),
+                vrf_oracle: args.vrf_oracle,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                rent: sysvar::rent::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::CreateGame {
+                game_id: args.game_id.clone(),
+                entry_fee: args.entry_fee,
+                max_players: args.max_players,
+                winner_count: args.winner_count,
+                payment_deadline_minutes: args.payment_deadline_minutes,
+                randomness_provider: args.randomness_provider,
+            }
+            .data(),
+        }
+    }
+
+    pub fn join_game_ix(&self, game_id: &str, player: usize) -> Instruction {
+        let player = &self.players[player];
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::JoinGame {
+                player: player.pubkey(),
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                player_list: player_list_pda(game_id),
+                player_token_account: player.token_account,
+                escrow_account: escrow_pda(game_id),
+                token_program: spl_token::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::JoinGame {
+                game_id: game_id.to_string(),
+                telegram_id: player.telegram_id.clone(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn select_number_ix(&self, game_id: &str, player: usize, number: u8) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::SelectNumber {
+                player: self.players[player].pubkey(),
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                player_list: player_list_pda(game_id),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::SelectNumber {
+                game_id: game_id.to_string(),
+                number,
+            }
+            .data(),
+        }
+    }
+
+    pub fn start_game_ix(&self, authority: &Pubkey, game_id: &str) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::StartGame {
+                authority: *authority,
+                role_registry: roles_pda(),
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                player_list: player_list_pda(game_id),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::StartGame {
+                game_id: game_id.to_string(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn request_orao_vrf_ix(&self, payer: &Pubkey, game_id: &str, round: u8) -> Instruction {
+        self.request_orao_vrf_ix_with_program(payer, game_id, round, mock_orao::ID)
+    }
+
+    pub fn request_orao_vrf_ix_with_program(
+        &self,
+        payer: &Pubkey,
+        game_id: &str,
+        round: u8,
+        orao_vrf: Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::RequestOraoVrf {
+                player: *payer,
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                network_state: mock_orao::client::network_state_address(),
+                treasury: self.orao_treasury,
+                randomness: mock_orao::client::randomness_address(&round_seed(game_id, round)),
+                orao_vrf,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::RequestOraoVrf {
+                game_id: game_id.to_string(),
+                round,
+            }
+            .data(),
+        }
+    }
+
+    pub fn fulfill_orao_vrf_ix(&self, authority: &Pubkey, game_id: &str, round: u8) -> Instruction {
+        self.fulfill_orao_vrf_ix_with_randomness(
+            authority,
+            game_id,
+            round,
+            mock_orao::client::randomness_address(&round_seed(game_id, round)),
+        )
+    }
+
+    pub fn fulfill_orao_vrf_ix_with_randomness(
+        &self,
+        authority: &Pubkey,
+        game_id: &str,
+        round: u8,
+        randomness: Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::FulfillOraoVrf {
+                authority: *authority,
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                randomness,
+                vrf_result: vrf_pda(game_id, round),
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::FulfillOraoVrf {
+                game_id: game_id.to_string(),
+                round,
+            }
+            .data(),
+        }
+    }
+
+    /// Ed25519 signature of `signer` over the round seed followed by `submit_vrf`
+    pub fn submit_vrf_ixs(
+        &self,
+        oracle: &Pubkey,
+        signer: &Keypair,
+        game_id: &str,
+        round: u8,
+    ) -> Vec<Instruction> {
+        let dalek = ed25519_dalek::Keypair::from_bytes(&signer.to_bytes()).unwrap();
+        let signature = solana_sdk::ed25519_instruction::new_ed25519_instruction(
+            &dalek,
+            &round_seed(game_id, round),
+        );
+        vec![signature, self.submit_vrf_ix(oracle, game_id, round)]
+    }
+
+    pub fn submit_vrf_ix(&self, oracle: &Pubkey, game_id: &str, round: u8) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::SubmitVrf {
+                vrf_oracle: *oracle,
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                oracle_registry: oracles_pda(),
+                vrf_result: vrf_pda(game_id, round),
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::SubmitVrf {
+                game_id: game_id.to_string(),
+                round,
+            }
+            .data(),
+        }
+    }
+
+    pub fn process_elimination_ix(
+        &self,
+        authority: &Pubkey,
+        game_id: &str,
+        round: u8,
+    ) -> Instruction {
+        self.process_elimination_ix_with_result(authority, game_id, round, round)
+    }
+
+    /// `process_elimination` for `round` reading the VRF result of `result_round`
+    pub fn process_elimination_ix_with_result(
+        &self,
+        authority: &Pubkey,
+        game_id: &str,
+        round: u8,
+        result_round: u8,
+    ) -> Instruction {
+        let mut instruction = Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::ProcessElimination {
+                authority: *authority,
+                role_registry: roles_pda(),
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                player_list: player_list_pda(game_id),
+                vrf_result: vrf_pda(game_id, round),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::ProcessElimination {
+                game_id: game_id.to_string(),
+                round,
+            }
+            .data(),
+        };
+        instruction.accounts[5].pubkey = vrf_pda(game_id, result_round);
+        instruction
+    }
+
+    pub fn complete_game_ix(&self, authority: &Pubkey, game_id: &str) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::CompleteGame {
+                authority: *authority,
+                role_registry: roles_pda(),
+                game_state: game_pda(game_id),
+                config: config_pda(),
+                player_list: player_list_pda(game_id),
+                treasury_state: treasury_pda(),
+                escrow_account: escrow_pda(game_id),
+                treasury_token_account: self.treasury_token_account(),
+                token_program: spl_token::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::CompleteGame {
+                game_id: game_id.to_string(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn claim_prize_ix(&self, game_id: &str, player: usize) -> Instruction {
+        let player = &self.players[player];
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::ClaimPrize {
+                winner: player.pubkey(),
+                game_state: game_pda(game_id),
+                player_list: player_list_pda(game_id),
+                escrow_account: escrow_pda(game_id),
+                winner_token_account: player.token_account,
+                token_program: spl_token::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::ClaimPrize {
+                game_id: game_id.to_string(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn request_refund_ix(&self, game_id: &str, player: usize) -> Instruction {
+        let player = &self.players[player];
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::RequestRefund {
+                player: player.pubkey(),
+                game_state: game_pda(game_id),
+                player_list: player_list_pda(game_id),
+                escrow_account: escrow_pda(game_id),
+                player_token_account: player.token_account,
+                token_program: spl_token::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::RequestRefund {
+                game_id: game_id.to_string(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn cancel_game_ix(&self, authority: &Pubkey, game_id: &str, reason: &str) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::CancelGame {
+                authority: *authority,
+                role_registry: roles_pda(),
+                game_state: game_pda(game_id),
+                player_list: player_list_pda(game_id),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::CancelGame {
+                game_id: game_id.to_string(),
+                reason: reason.to_string(),
+            }
+            .data(),
+        }
+    }
+
+    // ----- treasury instructions -----
+
+    pub fn configure_withdrawals_ix(
+        &self,
+        admin: &Pubkey,
+        approval_threshold: u8,
+        withdrawal_delay: i64,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::ConfigureWithdrawals {
+                admin: *admin,
+                role_registry: roles_pda(),
+                treasury_state: treasury_pda(),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::ConfigureWithdrawals {
+                approval_threshold,
+                withdrawal_delay,
+            }
+            .data(),
+        }
+    }
+
+    /// Propose a withdrawal; `proposal_id` must be the treasury's next proposal id
+    pub fn propose_withdrawal_ix(
+        &self,
+        proposer: &Pubkey,
+        proposal_id: u64,
+        amount: u64,
+        destination: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::ProposeWithdrawal {
+                proposer: *proposer,
+                role_registry: roles_pda(),
+                treasury_state: treasury_pda(),
+                proposal: withdrawal_pda(proposal_id),
+                destination_token_account: *destination,
+                treasury_token_account: self.treasury_token_account(),
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::ProposeWithdrawal { amount }.data(),
+        }
+    }
+
+    pub fn approve_withdrawal_ix(&self, approver: &Pubkey, proposal_id: u64) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::ApproveWithdrawal {
+                approver: *approver,
+                role_registry: roles_pda(),
+                proposal: withdrawal_pda(proposal_id),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::ApproveWithdrawal { proposal_id }.data(),
+        }
+    }
+
+    pub fn cancel_withdrawal_ix(&self, authority: &Pubkey, proposal_id: u64) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::CancelWithdrawal {
+                authority: *authority,
+                role_registry: roles_pda(),
+                proposal: withdrawal_pda(proposal_id),
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::CancelWithdrawal { proposal_id }.data(),
+        }
+    }
+
+    pub fn withdraw_treasury_ix(
+        &self,
+        authority: &Pubkey,
+        proposal_id: u64,
+        destination: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: telegram_lottery::ID,
+            accounts: telegram_lottery::accounts::WithdrawTreasury {
+                authority: *authority,
+                role_registry: roles_pda(),
+                treasury_state: treasury_pda(),
+                proposal: withdrawal_pda(proposal_id),
+                treasury_token_account: self.treasury_token_account(),
+                destination_token_account: *destination,
+                token_program: spl_token::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: telegram_lottery::instruction::WithdrawTreasury { proposal_id }.data(),
+        }
+    }
+}
+
+fn system_account(lamports: u64) -> Account {
+    Account {
+        lamports,
+        data: vec![],
+        owner: system_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn mint_account(mint_authority: &Pubkey, supply: u64) -> Account {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(*mint_authority),
+        supply,
+        decimals: MWOR_DECIMALS,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,683 @@
+//! Every reachable `LotteryError`, triggered through the program
+
+mod common;
+
+use common::*;
+use randomness::RandomnessError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use telegram_lottery::errors::LotteryError;
+use telegram_lottery::state::{
+    LotteryConfig, OracleRegistry, RandomnessProvider, Role, RoleRegistry, TreasuryState,
+};
+
+/// Where each error is exercised. The match is exhaustive, so a new variant
+/// does not compile until it is either tested or explained here.
+fn coverage(error: LotteryError) -> Result<&'static str, &'static str> {
+    use LotteryError::*;
+    match error {
+        GameIdTooLong
+        | InvalidEntryFee
+        | InvalidTokenMint
+        | InvalidMaxPlayers
+        | InvalidWinnerCount
+        | RandomnessProviderDisabled => Ok("test_create_game_errors"),
+        InvalidGameState
+        | PlayerAlreadyJoined
+        | NumberOutOfRange
+        | PlayerAlreadySelectedNumber
+        | NumberAlreadyTaken
+        | PlayerNotInGame
+        | NumbersNotSelected => Ok("test_join_and_select_errors"),
+        PaymentDeadlineExpired
+        | CannotCancelActiveGame
+        | GameNotCancelled
+        | RefundAlreadyProcessed
+        | CannotCancelGame => Ok("cancel_refund suite"),
+        Unauthorized | InvalidFeePercentage | TooManyAllowedMints => {
+            Ok("test_initialize_errors, test_role_errors, test_config_errors")
+        }
+        MissingRole | RoleAlreadyGranted | RoleNotGranted | RoleMemberLimitReached => {
+            Ok("test_role_errors")
+        }
+        OperationPaused | InvalidPauseFlags => Ok("test_config_errors"),
+        OracleAlreadyRegistered | OracleNotRegistered | OracleRegistryFull | InvalidVrfOracle => {
+            Ok("test_oracle_registry_errors")
+        }
+        NoVrfRequestPending
+        | InvalidRound
+        | InvalidOraoVrfProgram
+        | VrfRequestAlreadyPending
+        | VrfNotFulfilled
+        | VrfAlreadyUsed
+        | WrongRandomnessProvider => Ok("test_orao_round_errors"),
+        InvalidVrfProof => Ok("test_signed_oracle_errors"),
+        NoWinnersFound | NotAWinner | PrizeAlreadyClaimed | ReasonTooLong => {
+            Ok("test_settlement_errors")
+        }
+        InvalidApprovalThreshold
+        | InvalidWithdrawalDelay
+        | NoFundsToWithdraw
+        | InsufficientTreasuryBalance
+        | AlreadyApproved
+        | InsufficientApprovals
+        | WithdrawalDelayNotElapsed
+        | DestinationMismatch
+        | ProposalClosed
+        | WithdrawalSafeguardLowered => Ok("test_treasury_withdrawal_errors"),
+        GameFull => Err("a full game moves to number selection, so joins fail on its state first"),
+        PlayerEliminated => Err("nobody is eliminated while numbers are being selected"),
+        EscrowAccountMismatch => Err("the escrow seeds constraint rejects other accounts first"),
+        NoPrizeToCliam => Err("winners always split a non-zero distributable pool"),
+        ArithmeticOverflow => Err("entry fees are capped far below u64 overflow"),
+        GameAlreadyStarted
+        | PlayerNotFound
+        | NumberAlreadySelected
+        | InsufficientPrizePool
+        | VrfVerificationFailed
+        | VrfAlreadySubmitted
+        | InvalidTreasuryAuthority
+        | GameNotReadyForElimination
+        | AllPlayersEliminated
+        | InvalidRoundNumber
+        | MinimumPlayersNotMet
+        | TokenTransferFailed
+        | InvalidCancelReason
+        | TreasuryNotInitialized => Err("not raised by any instruction"),
+    }
+}
+
+#[test]
+fn test_unreachable_errors_are_explained() {
+    use LotteryError::*;
+    for error in [
+        GameFull,
+        PlayerEliminated,
+        EscrowAccountMismatch,
+        NoPrizeToCliam,
+    ] {
+        assert!(coverage(error).is_err(), "{:?}", error);
+    }
+    for error in [
+        InvalidGameState,
+        MissingRole,
+        InvalidVrfProof,
+        ProposalClosed,
+    ] {
+        assert!(coverage(error).is_ok(), "{:?}", error);
+    }
+}
+
+#[tokio::test]
+async fn test_initialize_errors() {
+    let mut test = LotteryTest::boot(0).await;
+    let admin = test.clone_keypair(&test.admin);
+    let operator = test.clone_keypair(&test.operator);
+
+    for fee in [0, 51] {
+        let initialize = test.initialize_ix(fee);
+        test.expect_error(&[initialize], &[&admin], LotteryError::InvalidFeePercentage)
+            .await;
+    }
+    let initialize = test.initialize_ix(FEE_PERCENTAGE);
+    test.send(&[initialize], &[&admin]).await.unwrap();
+
+    // Only the treasury authority becomes role admin
+    let roles = test.initialize_roles_ix(&operator.pubkey());
+    test.expect_error(&[roles], &[&operator], LotteryError::Unauthorized)
+        .await;
+    let roles = test.initialize_roles_ix(&admin.pubkey());
+    test.send(&[roles], &[&admin]).await.unwrap();
+
+    let mwor = test.mwor_mint;
+    let config = test.initialize_config_ix(&operator.pubkey(), vec![mwor], MAX_ENTRY_FEE);
+    test.expect_error(&[config], &[&operator], LotteryError::Unauthorized)
+        .await;
+    let mints = vec![Pubkey::new_unique(); LotteryConfig::MAX_ALLOWED_MINTS + 1];
+    let config = test.initialize_config_ix(&admin.pubkey(), mints, MAX_ENTRY_FEE);
+    test.expect_error(&[config], &[&admin], LotteryError::TooManyAllowedMints)
+        .await;
+    let config = test.initialize_config_ix(&admin.pubkey(), vec![mwor], MAX_ENTRY_FEE);
+    test.send(&[config], &[&admin]).await.unwrap();
+
+    let registry = test.initialize_oracle_registry_ix(&operator.pubkey());
+    test.expect_error(&[registry], &[&operator], LotteryError::Unauthorized)
+        .await;
+}
+
+#[tokio::test]
+async fn test_role_errors() {
+    let mut test = LotteryTest::start(1).await;
+    let admin = test.clone_keypair(&test.admin);
+    let operator = test.clone_keypair(&test.operator);
+
+    let grant = test.grant_role_ix(&admin.pubkey(), Role::Operator, operator.pubkey());
+    test.expect_error(&[grant], &[&admin], LotteryError::RoleAlreadyGranted)
+        .await;
+    let grant = test.grant_role_ix(&operator.pubkey(), Role::Pauser, operator.pubkey());
+    test.expect_error(&[grant], &[&operator], LotteryError::Unauthorized)
+        .await;
+    let revoke = test.revoke_role_ix(&admin.pubkey(), Role::Pauser, operator.pubkey());
+    test.expect_error(&[revoke], &[&admin], LotteryError::RoleNotGranted)
+        .await;
+    let revoke = test.revoke_role_ix(&operator.pubkey(), Role::Operator, operator.pubkey());
+    test.expect_error(&[revoke], &[&operator], LotteryError::Unauthorized)
+        .await;
+
+    // The harness operator holds the first of the operator slots
+    let grants: Vec<_> = (1..RoleRegistry::MAX_MEMBERS_PER_ROLE)
+        .map(|_| test.grant_role_ix(&admin.pubkey(), Role::Operator, Pubkey::new_unique()))
+        .collect();
+    test.send(&grants, &[&admin]).await.unwrap();
+    let grant = test.grant_role_ix(&admin.pubkey(), Role::Operator, Pubkey::new_unique());
+    test.expect_error(&[grant], &[&admin], LotteryError::RoleMemberLimitReached)
+        .await;
+
+    // Operator-only and fee-manager-only instructions
+    let player = test.clone_keypair(&test.players[0].keypair);
+    let args = test.game_args("no-role", 2, 1);
+    let create = test.create_game_ix(&player.pubkey(), &args);
+    test.expect_error(&[create], &[&player], LotteryError::MissingRole)
+        .await;
+
+    let update = test.update_fee_percentage_ix(&admin.pubkey(), 20);
+    test.expect_error(&[update], &[&admin], LotteryError::MissingRole)
+        .await;
+    let grant = test.grant_role_ix(&admin.pubkey(), Role::FeeManager, admin.pubkey());
+    test.send(&[grant], &[&admin]).await.unwrap();
+    for fee in [0, 51] {
+        let update = test.update_fee_percentage_ix(&admin.pubkey(), fee);
+        test.expect_error(&[update], &[&admin], LotteryError::InvalidFeePercentage)
+            .await;
+    }
+    let update = test.update_fee_percentage_ix(&admin.pubkey(), 20);
+    test.send(&[update], &[&admin]).await.unwrap();
+    assert_eq!(test.treasury_state().await.fee_percentage, 20);
+}
+
+#[tokio::test]
+async fn test_config_errors() {
+    let mut test = LotteryTest::start(1).await;
+    let admin = test.clone_keypair(&test.admin);
+    let operator = test.clone_keypair(&test.operator);
+
+    let update = test.update_config_ix(&operator.pubkey(), None, Some(1));
+    test.expect_error(&[update], &[&operator], LotteryError::Unauthorized)
+        .await;
+    let mints = vec![Pubkey::new_unique(); LotteryConfig::MAX_ALLOWED_MINTS + 1];
+    let update = test.update_config_ix(&admin.pubkey(), Some(mints), None);
+    test.expect_error(&[update], &[&admin], LotteryError::TooManyAllowedMints)
+        .await;
+
+    let pause = test.set_pause_flags_ix(&admin.pubkey(), LotteryConfig::PAUSE_ALL);
+    test.expect_error(&[pause], &[&admin], LotteryError::MissingRole)
+        .await;
+    let grant = test.grant_role_ix(&admin.pubkey(), Role::Pauser, admin.pubkey());
+    test.send(&[grant], &[&admin]).await.unwrap();
+    let pause = test.set_pause_flags_ix(&admin.pubkey(), 1 << 7);
+    test.expect_error(&[pause], &[&admin], LotteryError::InvalidPauseFlags)
+        .await;
+
+    // Creation is paused
+    let pause = test.set_pause_flags_ix(&admin.pubkey(), LotteryConfig::PAUSE_CREATE);
+    test.send(&[pause], &[&admin]).await.unwrap();
+    let args = test.game_args("paused", 2, 1);
+    let create = test.create_game_ix(&operator.pubkey(), &args);
+    test.expect_error(&[create], &[&operator], LotteryError::OperationPaused)
+        .await;
+
+    // Joining is paused
+    let pause = test.set_pause_flags_ix(&admin.pubkey(), LotteryConfig::PAUSE_JOIN);
+    test.send(&[pause], &[&admin]).await.unwrap();
+    test.create_game(&args).await;
+    let player = test.clone_keypair(&test.players[0].keypair);
+    let join = test.join_game_ix("paused", 0);
+    test.expect_error(&[join], &[&player], LotteryError::OperationPaused)
+        .await;
+    assert_eq!(test.player_balance(0).await, STARTING_BALANCE);
+    assert_eq!(test.escrow_balance("paused").await, 0);
+}
+
+#[tokio::test]
+async fn test_oracle_registry_errors() {
+    let mut test = LotteryTest::start(1).await;
+    let admin = test.clone_keypair(&test.admin);
+    let operator = test.clone_keypair(&test.operator);
+    let oracle = test.oracle.pubkey();
+
+    let register = test.register_oracle_ix(&admin.pubkey(), oracle);
+    test.expect_error(
+        &[register],
+        &[&admin],
+        LotteryError::OracleAlreadyRegistered,
+    )
+    .await;
+    let register = test.register_oracle_ix(&operator.pubkey(), Pubkey::new_unique());
+    test.expect_error(&[register], &[&operator], LotteryError::Unauthorized)
+        .await;
+    let deregister = test.deregister_oracle_ix(&admin.pubkey(), Pubkey::new_unique());
+    test.expect_error(&[deregister], &[&admin], LotteryError::OracleNotRegistered)
+        .await;
+    let deregister = test.deregister_oracle_ix(&operator.pubkey(), oracle);
+    test.expect_error(&[deregister], &[&operator], LotteryError::Unauthorized)
+        .await;
+
+    // Games must name a registered oracle
+    let mut args = test.game_args("rogue", 2, 1);
+    args.vrf_oracle = Pubkey::new_unique();
+    let create = test.create_game_ix(&operator.pubkey(), &args);
+    test.expect_error(&[create], &[&operator], LotteryError::InvalidVrfOracle)
+        .await;
+
+    let registers: Vec<_> = (1..OracleRegistry::MAX_ORACLES)
+        .map(|_| test.register_oracle_ix(&admin.pubkey(), Pubkey::new_unique()))
+        .collect();
+    test.send(&registers, &[&admin]).await.unwrap();
+    let register = test.register_oracle_ix(&admin.pubkey(), Pubkey::new_unique());
+    test.expect_error(&[register], &[&admin], LotteryError::OracleRegistryFull)
+        .await;
+}
+
+#[tokio::test]
+async fn test_create_game_errors() {
+    let mut test = LotteryTest::start(1).await;
+    let operator = test.clone_keypair(&test.operator);
+    let valid = test.game_args("create", 4, 2);
+
+    let mut args = valid.clone();
+    args.game_id = "g".repeat(17);
+    let create = test.create_game_ix(&operator.pubkey(), &args);
+    test.expect_error(&[create], &[&operator], LotteryError::GameIdTooLong)
+        .await;
+
+    for entry_fee in [0, MAX_ENTRY_FEE + 1] {
+        let mut args = valid.clone();
+        args.entry_fee = entry_fee;
+        let create = test.create_game_ix(&operator.pubkey(), &args);
+        test.expect_error(&[create], &[&operator], LotteryError::InvalidEntryFee)
+            .await;
+    }
+
+    let mut args = valid.clone();
+    args.token_mint = test.create_mint().await;
+    let create = test.create_game_ix(&operator.pubkey(), &args);
+    test.expect_error(&[create], &[&operator], LotteryError::InvalidTokenMint)
+        .await;
+
+    for max_players in [1, 101] {
+        let mut args = valid.clone();
+        args.max_players = max_players;
+        args.winner_count = 1;
+        let create = test.create_game_ix(&operator.pubkey(), &args);
+        test.expect_error(&[create], &[&operator], LotteryError::InvalidMaxPlayers)
+            .await;
+    }
+    for winner_count in [0, 4] {
+        let mut args = valid.clone();
+        args.winner_count = winner_count;
+        let create = test.create_game_ix(&operator.pubkey(), &args);
+        test.expect_error(&[create], &[&operator], LotteryError::InvalidWinnerCount)
+            .await;
+    }
+
+    #[cfg(not(feature = "slot-hash"))]
+    {
+        let mut args = valid.clone();
+        args.randomness_provider = RandomnessProvider::SlotHash;
+        let create = test.create_game_ix(&operator.pubkey(), &args);
+        test.expect_error(
+            &[create],
+            &[&operator],
+            LotteryError::RandomnessProviderDisabled,
+        )
+        .await;
+    }
+
+    test.create_game(&valid).await;
+    assert!(test.exists(escrow_pda("create")).await);
+}
+
+#[tokio::test]
+async fn test_join_and_select_errors() {
+    let mut test = LotteryTest::start(4).await;
+    let game_id = "select";
+    let args = test.game_args(game_id, 3, 1);
+    test.create_game(&args).await;
+
+    test.join(game_id, 0).await;
+    let first = test.clone_keypair(&test.players[0].keypair);
+    let join = test.join_game_ix(game_id, 0);
+    test.expect_error(&[join], &[&first], LotteryError::PlayerAlreadyJoined)
+        .await;
+    assert_eq!(test.escrow_balance(game_id).await, ENTRY_FEE);
+
+    // Picking numbers before the game fills
+    let select = test.select_number_ix(game_id, 0, 1);
+    test.expect_error(&[select], &[&first], LotteryError::InvalidGameState)
+        .await;
+
+    test.join(game_id, 1).await;
+    test.join(game_id, 2).await;
+    let outsider = test.clone_keypair(&test.players[3].keypair);
+    let join = test.join_game_ix(game_id, 3);
+    test.expect_error(&[join], &[&outsider], LotteryError::InvalidGameState)
+        .await;
+    assert_eq!(test.escrow_balance(game_id).await, 3 * ENTRY_FEE);
+    assert_eq!(test.player_balance(3).await, STARTING_BALANCE);
+
+    // Range is 1..=6 for three players
+    for number in [0, 7] {
+        let select = test.select_number_ix(game_id, 0, number);
+        test.expect_error(&[select], &[&first], LotteryError::NumberOutOfRange)
+            .await;
+    }
+    test.select(game_id, 0, 1).await;
+    let select = test.select_number_ix(game_id, 0, 2);
+    test.expect_error(
+        &[select],
+        &[&first],
+        LotteryError::PlayerAlreadySelectedNumber,
+    )
+    .await;
+    let second = test.clone_keypair(&test.players[1].keypair);
+    let select = test.select_number_ix(game_id, 1, 1);
+    test.expect_error(&[select], &[&second], LotteryError::NumberAlreadyTaken)
+        .await;
+    let select = test.select_number_ix(game_id, 3, 5);
+    test.expect_error(&[select], &[&outsider], LotteryError::PlayerNotInGame)
+        .await;
+
+    let operator = test.clone_keypair(&test.operator);
+    let start = test.start_game_ix(&operator.pubkey(), game_id);
+    test.expect_error(&[start], &[&operator], LotteryError::NumbersNotSelected)
+        .await;
+    let start = test.start_game_ix(&first.pubkey(), game_id);
+    test.expect_error(&[start], &[&first], LotteryError::MissingRole)
+        .await;
+}
+
+#[tokio::test]
+async fn test_orao_round_errors() {
+    let mut test = LotteryTest::start(3).await;
+    let game_id = "orao-errs";
+    let args = test.game_args(game_id, 3, 1);
+    test.start_playing(&args, &[1, 2, 3]).await;
+    let operator = test.clone_keypair(&test.operator);
+    let key = operator.pubkey();
+
+    let fulfill = test.fulfill_orao_vrf_ix(&key, game_id, 1);
+    test.expect_error(&[fulfill], &[&operator], LotteryError::NoVrfRequestPending)
+        .await;
+    let request = test.request_orao_vrf_ix(&key, game_id, 2);
+    test.expect_error(&[request], &[&operator], LotteryError::InvalidRound)
+        .await;
+    let request = test.request_orao_vrf_ix_with_program(&key, game_id, 1, Pubkey::new_unique());
+    test.expect_error(
+        &[request],
+        &[&operator],
+        LotteryError::InvalidOraoVrfProgram,
+    )
+    .await;
+    let submit = test.submit_vrf_ixs(&test.oracle.pubkey(), &test.oracle, game_id, 1);
+    let oracle = test.clone_keypair(&test.oracle);
+    test.expect_error(&submit, &[&oracle], LotteryError::WrongRandomnessProvider)
+        .await;
+
+    test.request_round(game_id, 1).await;
+    let request = test.request_orao_vrf_ix(&key, game_id, 1);
+    test.expect_error(
+        &[request],
+        &[&operator],
+        LotteryError::VrfRequestAlreadyPending,
+    )
+    .await;
+
+    // The mock network has not answered yet
+    let fulfill = test.fulfill_orao_vrf_ix(&key, game_id, 1);
+    test.expect_error(&[fulfill], &[&operator], LotteryError::VrfNotFulfilled)
+        .await;
+    let range = test.game(game_id).await.number_range;
+    test.orao_fulfill(game_id, 1, randomness_for(2, range.min, range.max))
+        .await;
+    let fulfill = test.fulfill_orao_vrf_ix(&key, game_id, 2);
+    test.expect_error(&[fulfill], &[&operator], LotteryError::InvalidRound)
+        .await;
+    test.fulfill_round(game_id, 1).await;
+
+    test.eliminate(game_id, 1).await;
+    let eliminate = test.process_elimination_ix(&key, game_id, 1);
+    test.expect_error(&[eliminate], &[&operator], LotteryError::VrfAlreadyUsed)
+        .await;
+    assert_eq!(test.escrow_balance(game_id).await, 3 * ENTRY_FEE);
+}
+
+#[tokio::test]
+async fn test_signed_oracle_errors() {
+    let mut test = LotteryTest::start(3).await;
+    let game_id = "signed-errs";
+    let mut args = test.game_args(game_id, 2, 1);
+    args.randomness_provider = RandomnessProvider::SignedOracle;
+    test.start_playing(&args, &[1, 2]).await;
+    let oracle = test.clone_keypair(&test.oracle);
+    let operator = test.clone_keypair(&test.operator);
+    // Funded but unregistered, so it can pay for the result account
+    let stranger = test.clone_keypair(&test.players[2].keypair);
+
+    let request = test.request_orao_vrf_ix(&operator.pubkey(), game_id, 1);
+    test.expect_error(
+        &[request],
+        &[&operator],
+        LotteryError::WrongRandomnessProvider,
+    )
+    .await;
+
+    // Only the game's oracle may submit, and only its own signature counts
+    let submit = test.submit_vrf_ixs(&stranger.pubkey(), &stranger, game_id, 1);
+    test.expect_error(&submit, &[&stranger], LotteryError::Unauthorized)
+        .await;
+    let submit = test.submit_vrf_ixs(&oracle.pubkey(), &stranger, game_id, 1);
+    test.expect_error(&submit, &[&oracle], LotteryError::InvalidVrfProof)
+        .await;
+    let submit = test.submit_vrf_ix(&oracle.pubkey(), game_id, 1);
+    test.expect_error(&[submit], &[&oracle], RandomnessError::MissingSignature)
+        .await;
+    let submit = test.submit_vrf_ixs(&oracle.pubkey(), &oracle, game_id, 2);
+    test.expect_error(&submit, &[&oracle], LotteryError::InvalidRound)
+        .await;
+
+    // Deregistered oracles lose access to their running games
+    let admin = test.clone_keypair(&test.admin);
+    let deregister = test.deregister_oracle_ix(&admin.pubkey(), oracle.pubkey());
+    test.send(&[deregister], &[&admin]).await.unwrap();
+    let submit = test.submit_vrf_ixs(&oracle.pubkey(), &oracle, game_id, 1);
+    test.expect_error(&submit, &[&oracle], LotteryError::InvalidVrfOracle)
+        .await;
+}
+
+#[tokio::test]
+async fn test_settlement_errors() {
+    let mut test = LotteryTest::start(3).await;
+    let operator = test.clone_keypair(&test.operator);
+
+    let settled = "settle";
+    let args = test.game_args(settled, 2, 1);
+    test.start_playing(&args, &[1, 2]).await;
+    let loser = test.clone_keypair(&test.players[0].keypair);
+    let winner = test.clone_keypair(&test.players[1].keypair);
+    let claim = test.claim_prize_ix(settled, 1);
+    test.expect_error(&[claim], &[&winner], LotteryError::InvalidGameState)
+        .await;
+
+    let cancel = test.cancel_game_ix(&operator.pubkey(), settled, &"x".repeat(201));
+    test.expect_error(&[cancel], &[&operator], LotteryError::ReasonTooLong)
+        .await;
+    let cancel = test.cancel_game_ix(&loser.pubkey(), settled, "not an operator");
+    test.expect_error(&[cancel], &[&loser], LotteryError::MissingRole)
+        .await;
+
+    test.draw(settled, 1, 1).await;
+    let complete = test.complete_game_ix(&loser.pubkey(), settled);
+    test.expect_error(&[complete], &[&loser], LotteryError::MissingRole)
+        .await;
+    test.complete(settled).await;
+
+    let claim = test.claim_prize_ix(settled, 0);
+    test.expect_error(&[claim], &[&loser], LotteryError::NotAWinner)
+        .await;
+    let outsider = test.clone_keypair(&test.players[2].keypair);
+    let claim = test.claim_prize_ix(settled, 2);
+    test.expect_error(&[claim], &[&outsider], LotteryError::PlayerNotInGame)
+        .await;
+    test.claim(settled, 1).await;
+    let claim = test.claim_prize_ix(settled, 1);
+    test.expect_error(&[claim], &[&winner], LotteryError::PrizeAlreadyClaimed)
+        .await;
+    assert_eq!(test.escrow_balance(settled).await, 0);
+
+    // Completion never pays out to an empty winner list
+    let wiped = "wiped";
+    let args = test.game_args(wiped, 2, 1);
+    test.start_playing(&args, &[1, 2]).await;
+    test.draw(wiped, 1, 1).await;
+    test.draw(wiped, 2, 2).await;
+    let complete = test.complete_game_ix(&operator.pubkey(), wiped);
+    test.expect_error(&[complete], &[&operator], LotteryError::NoWinnersFound)
+        .await;
+    assert_eq!(test.escrow_balance(wiped).await, 2 * ENTRY_FEE);
+}
+
+#[tokio::test]
+async fn test_treasury_withdrawal_errors() {
+    let mut test = LotteryTest::start(2).await;
+    let game_id = "fees";
+    let args = test.game_args(game_id, 2, 1);
+    test.start_playing(&args, &[1, 2]).await;
+    test.draw(game_id, 1, 1).await;
+    test.complete(game_id).await;
+    let fee = 2 * ENTRY_FEE / 10;
+    assert_eq!(test.treasury_balance().await, fee);
+
+    let admin = test.clone_keypair(&test.admin);
+    let operator = test.clone_keypair(&test.operator);
+    let outsider = test.clone_keypair(&test.players[0].keypair);
+
+    let configure = test.configure_withdrawals_ix(&operator.pubkey(), 2, 0);
+    test.expect_error(&[configure], &[&operator], LotteryError::Unauthorized)
+        .await;
+    for threshold in [0, 9] {
+        let configure = test.configure_withdrawals_ix(&admin.pubkey(), threshold, 0);
+        test.expect_error(
+            &[configure],
+            &[&admin],
+            LotteryError::InvalidApprovalThreshold,
+        )
+        .await;
+    }
+    for delay in [-1, 7 * 24 * 60 * 60 + 1] {
+        let configure = test.configure_withdrawals_ix(&admin.pubkey(), 2, delay);
+        test.expect_error(
+            &[configure],
+            &[&admin],
+            LotteryError::InvalidWithdrawalDelay,
+        )
+        .await;
+    }
+
+    // The admin alone can tighten the defaults but never loosen them
+    let treasury = test.treasury_state().await;
+    assert_eq!(treasury.approval_threshold, TreasuryState::DEFAULT_APPROVAL_THRESHOLD);
+    assert_eq!(treasury.withdrawal_delay, TreasuryState::DEFAULT_WITHDRAWAL_DELAY);
+    let delay = TreasuryState::DEFAULT_WITHDRAWAL_DELAY;
+    for (threshold, delay) in [(1, delay), (2, 0), (2, delay - 1)] {
+        let configure = test.configure_withdrawals_ix(&admin.pubkey(), threshold, delay);
+        test.expect_error(
+            &[configure],
+            &[&admin],
+            LotteryError::WithdrawalSafeguardLowered,
+        )
+        .await;
+    }
+    let configure = test.configure_withdrawals_ix(&admin.pubkey(), 2, delay + 3600);
+    let grants = [
+        test.grant_role_ix(&admin.pubkey(), Role::TreasuryWithdrawer, admin.pubkey()),
+        test.grant_role_ix(&admin.pubkey(), Role::TreasuryWithdrawer, operator.pubkey()),
+    ];
+    test.send(
+        &[configure, grants[0].clone(), grants[1].clone()],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let mwor = test.mwor_mint;
+    let destination = test.create_token_account(&mwor, &admin.pubkey()).await;
+    let elsewhere = test.create_token_account(&mwor, &operator.pubkey()).await;
+
+    let propose = test.propose_withdrawal_ix(&admin.pubkey(), 0, 0, &destination);
+    test.expect_error(&[propose], &[&admin], LotteryError::NoFundsToWithdraw)
+        .await;
+    let propose = test.propose_withdrawal_ix(&admin.pubkey(), 0, fee + 1, &destination);
+    test.expect_error(
+        &[propose],
+        &[&admin],
+        LotteryError::InsufficientTreasuryBalance,
+    )
+    .await;
+    let propose = test.propose_withdrawal_ix(&outsider.pubkey(), 0, fee, &destination);
+    test.expect_error(&[propose], &[&outsider], LotteryError::MissingRole)
+        .await;
+
+    let proposals = [
+        test.propose_withdrawal_ix(&admin.pubkey(), 0, fee, &destination),
+        test.propose_withdrawal_ix(&admin.pubkey(), 1, 1, &destination),
+    ];
+    test.send(&proposals, &[&admin]).await.unwrap();
+
+    // Cancellation is for the proposer or the role admin
+    let cancel = test.cancel_withdrawal_ix(&outsider.pubkey(), 1);
+    test.expect_error(&[cancel], &[&outsider], LotteryError::Unauthorized)
+        .await;
+    let cancel = test.cancel_withdrawal_ix(&admin.pubkey(), 1);
+    test.send(std::slice::from_ref(&cancel), &[&admin])
+        .await
+        .unwrap();
+    test.expect_error(&[cancel], &[&admin], LotteryError::ProposalClosed)
+        .await;
+
+    let approve = test.approve_withdrawal_ix(&admin.pubkey(), 0);
+    test.expect_error(&[approve], &[&admin], LotteryError::AlreadyApproved)
+        .await;
+    let withdraw = test.withdraw_treasury_ix(&admin.pubkey(), 0, &destination);
+    test.expect_error(
+        std::slice::from_ref(&withdraw),
+        &[&admin],
+        LotteryError::InsufficientApprovals,
+    )
+    .await;
+    let approve = test.approve_withdrawal_ix(&operator.pubkey(), 0);
+    test.send(std::slice::from_ref(&approve), &[&operator])
+        .await
+        .unwrap();
+    test.expect_error(
+        std::slice::from_ref(&withdraw),
+        &[&admin],
+        LotteryError::WithdrawalDelayNotElapsed,
+    )
+    .await;
+
+    test.warp_seconds(delay + 3600).await;
+    let redirect = test.withdraw_treasury_ix(&admin.pubkey(), 0, &elsewhere);
+    test.expect_error(&[redirect], &[&admin], LotteryError::DestinationMismatch)
+        .await;
+    assert_eq!(test.treasury_balance().await, fee);
+
+    test.send(std::slice::from_ref(&withdraw), &[&admin])
+        .await
+        .unwrap();
+    assert_eq!(test.treasury_balance().await, 0);
+    assert_eq!(test.token_balance(destination).await, fee);
+    test.expect_error(&[withdraw], &[&admin], LotteryError::ProposalClosed)
+        .await;
+    test.expect_error(&[approve], &[&operator], LotteryError::ProposalClosed)
+        .await;
+}
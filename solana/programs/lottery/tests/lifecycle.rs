@@ -0,0 +1,204 @@
+//! Full game lifecycle against the mock ORAO VRF and the fixture MWOR mint
+
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+use telegram_lottery::state::{GameStatus, RandomnessProvider, Role, TreasuryState};
+
+#[tokio::test]
+async fn test_orao_game_lifecycle() {
+    let mut test = LotteryTest::start(4).await;
+    let game_id = "orao-life";
+    let args = test.game_args(game_id, 4, 2);
+
+    // Create: empty escrow owned by the game PDA
+    test.create_game(&args).await;
+    let game = test.game(game_id).await;
+    assert_eq!(game.state, GameStatus::Joining);
+    assert_eq!(game.number_range.min, 1);
+    assert_eq!(game.number_range.max, 8);
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+    assert_eq!(test.treasury_balance().await, 0);
+
+    // Joins: every entry fee moves from the player into escrow
+    for player in 0..4 {
+        test.join(game_id, player).await;
+        assert_eq!(
+            test.player_balance(player).await,
+            STARTING_BALANCE - ENTRY_FEE
+        );
+        assert_eq!(
+            test.escrow_balance(game_id).await,
+            ENTRY_FEE * (player as u64 + 1)
+        );
+        assert_eq!(test.treasury_balance().await, 0);
+    }
+    let game = test.game(game_id).await;
+    assert_eq!(game.state, GameStatus::NumberSelection);
+    assert_eq!(game.prize_pool, 4 * ENTRY_FEE);
+    assert_eq!(game.treasury_fee, 4 * ENTRY_FEE / 10);
+
+    // Number selection moves no tokens
+    for player in 0..4 {
+        test.select(game_id, player, player as u8 + 1).await;
+        assert_eq!(test.escrow_balance(game_id).await, 4 * ENTRY_FEE);
+    }
+    test.start_game(game_id).await;
+    assert_eq!(test.game(game_id).await.state, GameStatus::Playing);
+
+    // Round 1 draws player 1's number
+    let orao_treasury = test.orao_treasury;
+    let treasury_lamports = test.lamports(orao_treasury).await;
+    test.draw(game_id, 1, 2).await;
+    assert_eq!(
+        test.lamports(orao_treasury).await,
+        treasury_lamports + ORAO_REQUEST_FEE
+    );
+    let result = test.vrf_result(game_id, 1).await;
+    assert_eq!(result.drawn_number, 2);
+    assert!(result.used);
+    let players = test.player_list(game_id).await.players;
+    assert_eq!(players[1].eliminated_round, Some(1));
+    assert_eq!(test.escrow_balance(game_id).await, 4 * ENTRY_FEE);
+
+    // Round 2 draws an unselected number
+    test.draw(game_id, 2, 7).await;
+    let players = test.player_list(game_id).await.players;
+    assert_eq!(
+        players
+            .iter()
+            .filter(|p| p.eliminated_round.is_some())
+            .count(),
+        1
+    );
+    assert_eq!(test.escrow_balance(game_id).await, 4 * ENTRY_FEE);
+
+    // Round 3 leaves exactly two players
+    test.draw(game_id, 3, 4).await;
+    let game = test.game(game_id).await;
+    assert_eq!(game.current_round, 3);
+    assert_eq!(game.drawn_numbers, vec![2, 7, 4]);
+    let players = test.player_list(game_id).await.players;
+    assert_eq!(players[3].eliminated_round, Some(3));
+    assert_eq!(test.escrow_balance(game_id).await, 4 * ENTRY_FEE);
+
+    // Complete: the fee moves to the treasury, the rest stays for winners
+    let fee = 4 * ENTRY_FEE / 10;
+    let prize = (4 * ENTRY_FEE - fee) / 2;
+    test.complete(game_id).await;
+    assert_eq!(test.game(game_id).await.state, GameStatus::Distributing);
+    assert_eq!(test.treasury_balance().await, fee);
+    assert_eq!(test.escrow_balance(game_id).await, 4 * ENTRY_FEE - fee);
+    let treasury = test.treasury_state().await;
+    assert_eq!(treasury.total_collected, fee);
+    assert_eq!(treasury.pending_withdrawal, fee);
+    let players = test.player_list(game_id).await.players;
+    let winners: Vec<bool> = players.iter().map(|p| p.is_winner).collect();
+    assert_eq!(winners, vec![true, false, true, false]);
+    assert!(players
+        .iter()
+        .filter(|p| p.is_winner)
+        .all(|p| p.prize_amount == prize));
+
+    // Claims drain the escrow
+    test.claim(game_id, 0).await;
+    assert_eq!(
+        test.player_balance(0).await,
+        STARTING_BALANCE - ENTRY_FEE + prize
+    );
+    assert_eq!(test.escrow_balance(game_id).await, prize);
+    test.claim(game_id, 2).await;
+    assert_eq!(
+        test.player_balance(2).await,
+        STARTING_BALANCE - ENTRY_FEE + prize
+    );
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+    assert_eq!(test.player_balance(1).await, STARTING_BALANCE - ENTRY_FEE);
+    assert_eq!(test.player_balance(3).await, STARTING_BALANCE - ENTRY_FEE);
+    assert_eq!(test.treasury_balance().await, fee);
+
+    // Treasury withdrawal with the default two-approval, one-day policy
+    let admin = test.clone_keypair(&test.admin);
+    let operator = test.clone_keypair(&test.operator);
+    let mint = test.mwor_mint;
+    let destination = test.create_token_account(&mint, &admin.pubkey()).await;
+    let grants = [
+        test.grant_role_ix(&admin.pubkey(), Role::TreasuryWithdrawer, admin.pubkey()),
+        test.grant_role_ix(&admin.pubkey(), Role::TreasuryWithdrawer, operator.pubkey()),
+    ];
+    let propose = test.propose_withdrawal_ix(&admin.pubkey(), 0, fee, &destination);
+    test.send(&[grants[0].clone(), grants[1].clone(), propose], &[&admin])
+        .await
+        .unwrap();
+    let approve = test.approve_withdrawal_ix(&operator.pubkey(), 0);
+    test.send(&[approve], &[&operator]).await.unwrap();
+    test.warp_seconds(TreasuryState::DEFAULT_WITHDRAWAL_DELAY).await;
+    let withdraw = test.withdraw_treasury_ix(&admin.pubkey(), 0, &destination);
+    test.send(&[withdraw], &[&admin]).await.unwrap();
+    assert_eq!(test.treasury_balance().await, 0);
+    assert_eq!(test.token_balance(destination).await, fee);
+    let treasury = test.treasury_state().await;
+    assert_eq!(treasury.pending_withdrawal, 0);
+    assert_eq!(treasury.total_distributed, fee);
+    assert!(test.proposal(0).await.executed);
+}
+
+#[tokio::test]
+async fn test_signed_oracle_round() {
+    let mut test = LotteryTest::start(3).await;
+    let game_id = "signed-life";
+    let mut args = test.game_args(game_id, 3, 1);
+    args.randomness_provider = RandomnessProvider::SignedOracle;
+    test.start_playing(&args, &[1, 2, 3]).await;
+
+    let oracle = test.clone_keypair(&test.oracle);
+    let instructions = test.submit_vrf_ixs(&oracle.pubkey(), &oracle, game_id, 1);
+    test.send(&instructions, &[&oracle]).await.unwrap();
+
+    let result = test.vrf_result(game_id, 1).await;
+    assert_eq!(result.round, 1);
+    assert!((1..=6).contains(&result.drawn_number));
+    assert_eq!(test.game(game_id).await.current_round, 1);
+
+    test.eliminate(game_id, 1).await;
+    let players = test.player_list(game_id).await.players;
+    for player in &players {
+        let drawn = player.selected_number == Some(result.drawn_number);
+        assert_eq!(player.eliminated_round.is_some(), drawn);
+    }
+    assert_eq!(test.escrow_balance(game_id).await, 3 * ENTRY_FEE);
+    assert_eq!(test.treasury_balance().await, 0);
+}
+
+#[tokio::test]
+async fn test_uneven_split_remainder_goes_to_treasury() {
+    let mut test = LotteryTest::start(3).await;
+    let game_id = "dust";
+    let mut args = test.game_args(game_id, 3, 2);
+    args.entry_fee = ENTRY_FEE + 1;
+    test.start_playing(&args, &[1, 2, 3]).await;
+    test.draw(game_id, 1, 2).await;
+
+    // The pool left after the rounded-down fee is odd, so two winners leave dust
+    let pool = 3 * args.entry_fee;
+    let fee = 3 * (args.entry_fee / 10);
+    let prize = (pool - fee) / 2;
+    let dust = (pool - fee) % 2;
+    assert_eq!(dust, 1);
+    test.complete(game_id).await;
+
+    let game = test.game(game_id).await;
+    assert_eq!(game.treasury_fee, fee + dust);
+    assert_eq!(test.treasury_balance().await, fee + dust);
+    assert_eq!(test.treasury_state().await.total_collected, fee + dust);
+    assert_eq!(test.escrow_balance(game_id).await, 2 * prize);
+
+    test.claim(game_id, 0).await;
+    test.claim(game_id, 2).await;
+    assert_eq!(test.escrow_balance(game_id).await, 0);
+    assert_eq!(
+        test.player_balance(0).await,
+        STARTING_BALANCE - args.entry_fee + prize
+    );
+}
@@ -0,0 +1,474 @@
+//! Mock ORAO VRF program
+//!
+//! A stand-in for the ORAO VRF program for localnet and `solana-program-test`
+//! draws. It keeps ORAO's program id, `NetworkState` and `Randomness` account
+//! layouts and `request` instruction encoding, so the lottery and raffle
+//! programs CPI into it unchanged. Randomness is never generated: tests inject
+//! it through `fulfill`. Never deploy this program outside a test validator.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+// Deployed at the ORAO VRF program id so the address checks in both programs pass
+declare_id!("VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y");
+
+/// Seed of the network state PDA
+pub const CONFIG_ACCOUNT_SEED: &[u8] = b"orao-vrf-network-configuration";
+
+/// Seed prefix of randomness request PDAs
+pub const RANDOMNESS_ACCOUNT_SEED: &[u8] = b"orao-vrf-randomness-request";
+
+/// Maximum fulfillment authorities held by the network state
+pub const MAX_FULFILLMENT_AUTHORITIES: usize = 4;
+
+#[program]
+pub mod mock_orao {
+    use super::*;
+
+    /// Create the network state
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        treasury: Pubkey,
+        request_fee: u64,
+        fulfillment_authorities: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            fulfillment_authorities.len() <= MAX_FULFILLMENT_AUTHORITIES,
+            MockOraoError::TooManyFulfillmentAuthorities
+        );
+
+        let network_state = &mut ctx.accounts.network_state;
+        network_state.config = NetworkConfiguration {
+            authority: ctx.accounts.authority.key(),
+            treasury,
+            request_fee,
+            fulfillment_authorities,
+            token_fee_config: None,
+        };
+        network_state.num_received = 0;
+
+        msg!("Mock ORAO network initialized, fee {}", request_fee);
+        Ok(())
+    }
+
+    /// Open a randomness request, charging the network fee
+    pub fn request(ctx: Context<Request>, seed: [u8; 32]) -> Result<()> {
+        let fee = ctx.accounts.network_state.config.request_fee;
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        let request = &mut ctx.accounts.request;
+        request.seed = seed;
+        request.randomness = [0u8; 64];
+        request.responses = Vec::new();
+
+        let network_state = &mut ctx.accounts.network_state;
+        network_state.num_received = network_state
+            .num_received
+            .checked_add(1)
+            .ok_or(MockOraoError::Overflow)?;
+
+        msg!("Mock ORAO randomness requested");
+        Ok(())
+    }
+
+    /// Fulfill a pending request with chosen randomness
+    pub fn fulfill(ctx: Context<Fulfill>, seed: [u8; 32], randomness: [u8; 64]) -> Result<()> {
+        let request = &mut ctx.accounts.request;
+        require!(request.randomness == [0u8; 64], MockOraoError::AlreadyFulfilled);
+        require!(randomness != [0u8; 64], MockOraoError::ZeroRandomness);
+
+        request.randomness = randomness;
+        request.responses.push(RandomnessResponse {
+            pubkey: ctx.accounts.authority.key(),
+            randomness,
+        });
+
+        msg!("Mock ORAO request {:?} fulfilled", &seed[..4]);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + NetworkState::SIZE,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account order matches ORAO's `request` so callers encode the CPI the same way
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct Request<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+
+    /// CHECK: Must be the treasury recorded in the network state
+    #[account(
+        mut,
+        address = network_state.config.treasury @ MockOraoError::TreasuryMismatch
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Randomness::SIZE,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, seed.as_ref()],
+        bump
+    )]
+    pub request: Account<'info, Randomness>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct Fulfill<'info> {
+    #[account(
+        constraint = network_state.config.fulfillment_authorities.contains(&authority.key())
+            @ MockOraoError::UnknownFulfillmentAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, seed.as_ref()],
+        bump
+    )]
+    pub request: Account<'info, Randomness>,
+}
+
+/// ORAO token fee configuration (kept for layout compatibility, always unset)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct OraoTokenFeeConfig {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub fee: u64,
+}
+
+impl OraoTokenFeeConfig {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/// ORAO network configuration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct NetworkConfiguration {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub request_fee: u64,
+    pub fulfillment_authorities: Vec<Pubkey>,
+    pub token_fee_config: Option<OraoTokenFeeConfig>,
+}
+
+impl NetworkConfiguration {
+    pub const SIZE: usize = 32 + // authority
+        32 + // treasury
+        8 + // request_fee
+        4 + 32 * MAX_FULFILLMENT_AUTHORITIES + // fulfillment_authorities
+        1 + OraoTokenFeeConfig::SIZE; // token_fee_config
+}
+
+/// ORAO network state
+#[account]
+pub struct NetworkState {
+    pub config: NetworkConfiguration,
+    pub num_received: u64,
+}
+
+impl NetworkState {
+    pub const SIZE: usize = NetworkConfiguration::SIZE + // config
+        8; // num_received
+}
+
+/// One fulfillment authority's contribution to a request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RandomnessResponse {
+    pub pubkey: Pubkey,
+    pub randomness: [u8; 64],
+}
+
+impl RandomnessResponse {
+    pub const SIZE: usize = 32 + 64;
+}
+
+/// ORAO randomness request account
+#[account]
+pub struct Randomness {
+    pub seed: [u8; 32],
+    /// Aggregated randomness, all zero until fulfilled
+    pub randomness: [u8; 64],
+    pub responses: Vec<RandomnessResponse>,
+}
+
+impl Randomness {
+    /// The mock is fulfilled by a single authority
+    pub const SIZE: usize = 32 + // seed
+        64 + // randomness
+        4 + RandomnessResponse::SIZE; // responses
+}
+
+#[error_code]
+pub enum MockOraoError {
+    #[msg("Too many fulfillment authorities")]
+    TooManyFulfillmentAuthorities,
+    #[msg("Treasury does not match the network state")]
+    TreasuryMismatch,
+    #[msg("Signer is not a fulfillment authority")]
+    UnknownFulfillmentAuthority,
+    #[msg("Request already fulfilled")]
+    AlreadyFulfilled,
+    #[msg("Randomness must not be all zero")]
+    ZeroRandomness,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
+
+/// Instruction builders for tests driving the mock directly
+pub mod client {
+    use super::*;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::InstructionData;
+
+    /// Network state address
+    pub fn network_state_address() -> Pubkey {
+        Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &ID).0
+    }
+
+    /// Randomness account address for `seed`
+    pub fn randomness_address(seed: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(&[RANDOMNESS_ACCOUNT_SEED, seed], &ID).0
+    }
+
+    /// Build an `initialize` instruction
+    pub fn initialize(
+        authority: Pubkey,
+        treasury: Pubkey,
+        request_fee: u64,
+        fulfillment_authorities: Vec<Pubkey>,
+    ) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Initialize {
+                authority,
+                network_state: network_state_address(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Initialize {
+                treasury,
+                request_fee,
+                fulfillment_authorities,
+            }
+            .data(),
+        }
+    }
+
+    /// Build a `request` instruction
+    pub fn request(payer: Pubkey, treasury: Pubkey, seed: [u8; 32]) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Request {
+                payer,
+                network_state: network_state_address(),
+                treasury,
+                request: randomness_address(&seed),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Request { seed }.data(),
+        }
+    }
+
+    /// Build a `fulfill` instruction
+    pub fn fulfill(authority: Pubkey, seed: [u8; 32], randomness: [u8; 64]) -> Instruction {
+        Instruction {
+            program_id: ID,
+            accounts: accounts::Fulfill {
+                authority,
+                network_state: network_state_address(),
+                request: randomness_address(&seed),
+            }
+            .to_account_metas(None),
+            data: instruction::Fulfill { seed, randomness }.data(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use randomness::orao::OraoRandomness;
+    use solana_program_test::*;
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    // Anchor's entrypoint ties the account slice to 'info, which program-test cannot provide
+    fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+        let accounts = Box::leak(Box::new(accounts.to_vec()));
+        crate::entry(program_id, accounts, data)
+    }
+
+    fn program_test() -> ProgramTest {
+        ProgramTest::new("mock_orao", ID, processor!(process_instruction))
+    }
+
+    #[test]
+    fn test_addresses_match_randomness_crate() {
+        assert_eq!(ID, randomness::orao::ID);
+        assert_eq!(client::network_state_address(), randomness::orao::network_state_address());
+
+        let seed = [7u8; 32];
+        assert_eq!(
+            client::randomness_address(&seed),
+            randomness::orao::randomness_account_address(&seed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_and_fulfill() {
+        let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+        let treasury = Keypair::new().pubkey();
+        let fulfiller = Keypair::new();
+        let seed = [42u8; 32];
+        let randomness = [9u8; 64];
+        let fee = 1_000_000;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                client::initialize(payer.pubkey(), treasury, fee, vec![fulfiller.pubkey()]),
+                client::request(payer.pubkey(), treasury, seed),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        assert_eq!(banks_client.get_balance(treasury).await.unwrap(), fee);
+
+        let account = banks_client
+            .get_account(client::randomness_address(&seed))
+            .await
+            .unwrap()
+            .unwrap();
+        let pending = OraoRandomness::try_from_data(&account.data).unwrap();
+        assert_eq!(pending.seed, seed);
+        assert_eq!(pending.randomness, [0u8; 64]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[client::fulfill(fulfiller.pubkey(), seed, randomness)],
+            Some(&payer.pubkey()),
+            &[&payer, &fulfiller],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let account = banks_client
+            .get_account(client::randomness_address(&seed))
+            .await
+            .unwrap()
+            .unwrap();
+        let fulfilled = OraoRandomness::try_from_data(&account.data).unwrap();
+        assert_eq!(fulfilled.seed, seed);
+        assert_eq!(fulfilled.randomness, randomness);
+
+        let mut data: &[u8] = &account.data;
+        let decoded = Randomness::try_deserialize(&mut data).unwrap();
+        assert_eq!(decoded.responses.len(), 1);
+        assert_eq!(decoded.responses[0].pubkey, fulfiller.pubkey());
+    }
+
+    #[tokio::test]
+    async fn test_fulfill_rejects_unknown_authority() {
+        let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+        let treasury = Keypair::new().pubkey();
+        let impostor = Keypair::new();
+        let seed = [1u8; 32];
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                client::initialize(payer.pubkey(), treasury, 0, vec![payer.pubkey()]),
+                client::request(payer.pubkey(), treasury, seed),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[client::fulfill(impostor.pubkey(), seed, [3u8; 64])],
+            Some(&payer.pubkey()),
+            &[&payer, &impostor],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fulfill_twice_fails() {
+        let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+        let treasury = Keypair::new().pubkey();
+        let seed = [2u8; 32];
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                client::initialize(payer.pubkey(), treasury, 0, vec![payer.pubkey()]),
+                client::request(payer.pubkey(), treasury, seed),
+                client::fulfill(payer.pubkey(), seed, [5u8; 64]),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(tx).await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[client::fulfill(payer.pubkey(), seed, [6u8; 64])],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        assert!(banks_client.process_transaction(tx).await.is_err());
+    }
+}
@@ -113,6 +113,120 @@ pub enum RaffleError {
     
     #[msg("Raffle creator cannot purchase own tickets")]
     CreatorCannotPurchase,
+    
+    #[msg("Ticket quantity must be between 1 and 100 per purchase")]
+    InvalidTicketQuantity,
+    
+    #[msg("Per-wallet ticket limit must be between 1 and max tickets")]
+    InvalidWalletTicketLimit,
+    
+    #[msg("Maximum number of purchases per wallet exceeded")]
+    UserPurchaseLimitExceeded,
+    
+    #[msg("Winning ticket has not been drawn yet")]
+    WinningTicketNotDrawn,
+    
+    #[msg("VRF randomness has not been fulfilled yet")]
+    RandomnessNotFulfilled,
+    
+    #[msg("Draw timeout must be between 1 hour and 7 days")]
+    InvalidDrawTimeout,
+    
+    #[msg("VRF request has not timed out yet")]
+    DrawNotStalled,
+    
+    #[msg("Maximum number of VRF requests reached")]
+    MaxDrawAttemptsReached,
+    
+    #[msg("Minimum tickets must be between 1 and max tickets")]
+    InvalidMinTickets,
+    
+    #[msg("Minimum ticket threshold has not been met")]
+    MinimumTicketsNotMet,
+    
+    #[msg("Raffle reached its minimum ticket threshold")]
+    MinimumTicketsReached,
+    
+    #[msg("Start time must be between now and 30 days from now")]
+    InvalidStartTime,
+    
+    #[msg("Raffle has not started yet")]
+    RaffleNotStarted,
+    
+    #[msg("Invalid pricing tiers")]
+    InvalidPriceTiers,
+    
+    #[msg("Remaining accounts must be (ticket, owner) pairs")]
+    InvalidRemainingAccounts,
+    
+    #[msg("Raffle is not settled - prize not distributed or refunds outstanding")]
+    RaffleNotSettled,
+    
+    #[msg("All ticket accounts must be closed before closing the raffle")]
+    TicketAccountsOpen,
+    
+    #[msg("Content URI must be at most 200 characters using https://, ipfs:// or ar://")]
+    InvalidContentUri,
+    
+    #[msg("Content hash cannot be empty")]
+    InvalidContentHash,
+    
+    #[msg("Compact raffles must keep the description off-chain")]
+    DescriptionNotAllowed,
+    
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    
+    #[msg("Only the proposed authority can accept the transfer")]
+    UnauthorizedPendingAuthority,
+    
+    #[msg("No configuration change is queued")]
+    NoPendingConfigChange,
+    
+    #[msg("Queued configuration change is still timelocked")]
+    ConfigChangeNotReady,
+    
+    #[msg("Config delay must be between 0 and 30 days")]
+    InvalidConfigDelay,
+    
+    #[msg("A configuration change is already queued")]
+    ConfigChangeAlreadyQueued,
+    
+    #[msg("Pause flags contain unknown bits")]
+    InvalidPauseFlags,
+    
+    #[msg("Raffle is frozen by the program authority")]
+    RaffleFrozen,
+    
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+    
+    #[msg("Role already granted to this key")]
+    RoleAlreadyGranted,
+    
+    #[msg("Role not granted to this key")]
+    RoleNotGranted,
+    
+    #[msg("Role member limit reached")]
+    RoleMemberLimitReached,
+    
+    #[msg("Randomness provider is not enabled in this build")]
+    RandomnessProviderDisabled,
+    
+    #[msg("Raffle uses a different randomness provider")]
+    WrongRandomnessProvider,
+    
+    #[msg("Slot-hash draws require a commitment")]
+    CommitmentRequired,
+    
+    #[msg("Slot-hash draws require the committed secret")]
+    SecretRequired,
+    
+    #[msg("Draw seed was not signed by an oracle")]
+    UnknownOracle,
+    
+    #[msg("VRF randomness is already fulfilled - draw the winner instead")]
+    RandomnessAlreadyFulfilled,
 }
 
 /// Helper trait for checked arithmetic operations
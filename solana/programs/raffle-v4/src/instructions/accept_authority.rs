@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Accept a proposed authority transfer
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    let new_authority = ctx.accounts.new_authority.key();
+    
+    let pending_authority = program_state
+        .pending_authority
+        .ok_or(RaffleError::NoPendingAuthority)?;
+    
+    // Only the proposed key can complete the transfer
+    require!(
+        pending_authority == new_authority,
+        RaffleError::UnauthorizedPendingAuthority
+    );
+    
+    let old_authority = program_state.authority;
+    program_state.authority = new_authority;
+    program_state.pending_authority = None;
+    
+    msg!(
+        "Authority transferred: {} -> {}",
+        old_authority,
+        new_authority
+    );
+    
+    emit!(AuthorityTransferredEvent {
+        old_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
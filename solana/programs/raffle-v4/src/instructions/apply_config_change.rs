@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Apply a queued configuration change once its delay has elapsed
+#[derive(Accounts)]
+pub struct ApplyConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApplyConfigChange>) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    let change = program_state
+        .pending_config
+        .ok_or(RaffleError::NoPendingConfigChange)?;
+    
+    require!(change.is_ready(current_time), RaffleError::ConfigChangeNotReady);
+    
+    let old_fee_rate = program_state.fee_rate;
+    let old_treasury = program_state.treasury;
+    
+    if let Some(fee_rate) = change.fee_rate {
+        program_state.fee_rate = fee_rate;
+    }
+    if let Some(treasury) = change.treasury {
+        program_state.treasury = treasury;
+    }
+    if let Some(config_delay) = change.config_delay {
+        program_state.config_delay = config_delay;
+    }
+    program_state.pending_config = None;
+    
+    msg!(
+        "Config change applied by {} - Fee rate: {} -> {}, Treasury: {} -> {}, Delay: {}s",
+        ctx.accounts.caller.key(),
+        old_fee_rate,
+        program_state.fee_rate,
+        old_treasury,
+        program_state.treasury,
+        program_state.config_delay
+    );
+    
+    emit!(ProgramConfigUpdatedEvent {
+        updated_by: ctx.accounts.caller.key(),
+        old_fee_rate,
+        new_fee_rate: program_state.fee_rate,
+        old_treasury,
+        new_treasury: program_state.treasury,
+        old_authority: program_state.authority,
+        new_authority: program_state.authority,
+        pause_flags: program_state.pause_flags,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_after_delay() {
+        let change = PendingConfigChange {
+            fee_rate: Some(500),
+            treasury: Some(Pubkey::new_unique()),
+            config_delay: None,
+            eta: 1_700_086_400,
+        };
+        
+        assert!(!change.is_ready(1_700_000_000));
+        assert!(change.is_ready(1_700_086_400));
+        assert!(change.is_ready(1_800_000_000));
+    }
+}
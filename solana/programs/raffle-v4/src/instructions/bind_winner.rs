@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Bind the winner to a raffle once the winning ticket number has been drawn
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct BindWinner<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// The ticket account whose range contains the winning ticket (verified by PDA)
+    #[account(
+        seeds = [
+            b"ticket",
+            raffle_id.to_le_bytes().as_ref(),
+            winning_ticket.ticket_number.to_le_bytes().as_ref()
+        ],
+        bump = winning_ticket.bump,
+        constraint = winning_ticket.raffle_id == raffle_id @ RaffleError::InvalidTicketNumber
+    )]
+    pub winning_ticket: Account<'info, TicketAccount>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<BindWinner>,
+    raffle_id: u64,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    let winning_ticket = &ctx.accounts.winning_ticket;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_DRAW), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    // Validate raffle state
+    require!(
+        raffle_account.status == RaffleStatus::Drawing,
+        RaffleError::InvalidRaffleState
+    );
+    
+    // Check if winner has already been bound
+    require!(
+        raffle_account.winner.is_none(),
+        RaffleError::WinnerAlreadySelected
+    );
+    
+    // Winning ticket number must have been drawn by fulfill_winner_selection
+    let winning_ticket_number = raffle_account.winning_ticket
+        .ok_or(RaffleError::WinningTicketNotDrawn)?;
+    
+    // Verify the supplied ticket account covers the winning ticket number
+    require!(
+        winning_ticket.contains(winning_ticket_number),
+        RaffleError::InvalidTicketNumber
+    );
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Update raffle account with winner information
+    raffle_account.status = RaffleStatus::Complete;
+    raffle_account.winner = Some(winning_ticket.owner);
+    
+    msg!(
+        "Winner selected - Raffle ID: {}, Winner: {}, Winning Ticket: {}",
+        raffle_id,
+        winning_ticket.owner,
+        winning_ticket_number
+    );
+    
+    emit!(WinnerSelectedEvent {
+        raffle_id,
+        winner: winning_ticket.owner,
+        ticket_account: winning_ticket.key(),
+        winning_ticket: winning_ticket_number,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winning_ticket_range_lookup() {
+        // Three purchases: [0, 3), [3, 4), [4, 14)
+        let ranges = [(0u32, 3u32), (3, 1), (4, 10)];
+        let tickets: Vec<TicketAccount> = ranges
+            .iter()
+            .map(|&(start, quantity)| TicketAccount {
+                raffle_id: 1,
+                owner: Pubkey::new_unique(),
+                ticket_number: start,
+                quantity,
+                amount_paid: quantity as u64 * 10_000_000,
+                refunded: false,
+                purchase_time: 1640995200,
+                bump: 255,
+            })
+            .collect();
+        
+        // Every winning index resolves to exactly one purchase
+        for winning in 0..14u32 {
+            let holders: Vec<_> = tickets.iter().filter(|t| t.contains(winning)).collect();
+            assert_eq!(holders.len(), 1);
+        }
+        
+        assert!(tickets[0].contains(2));
+        assert!(tickets[1].contains(3));
+        assert!(tickets[2].contains(13));
+        assert!(!tickets.iter().any(|t| t.contains(14)));
+    }
+}
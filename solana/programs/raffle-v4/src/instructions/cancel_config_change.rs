@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Cancel a queued configuration change (fee manager only)
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        constraint = role_registry.has_role(Role::FeeManager, &fee_manager.key()) @ RaffleError::MissingRole
+    )]
+    pub fee_manager: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let program_state = &mut ctx.accounts.program_state;
+    
+    let change = program_state
+        .pending_config
+        .take()
+        .ok_or(RaffleError::NoPendingConfigChange)?;
+    
+    msg!(
+        "Config change cancelled by {} - ETA was {}",
+        ctx.accounts.fee_manager.key(),
+        change.eta
+    );
+    
+    emit!(ConfigChangeCancelledEvent {
+        cancelled_by: ctx.accounts.fee_manager.key(),
+        change,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use settlement::escrow::transfer_lamports_from_pda;
 use crate::state::*;
 use crate::error::*;
 
@@ -52,7 +52,10 @@ pub fn handler(
     let raffle_account = &mut ctx.accounts.raffle_account;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
     
     // Validate raffle state - can only cancel active raffles
     require!(
@@ -70,27 +73,12 @@ pub fn handler(
     let total_collected = raffle_account.total_collected();
     let creator_prize_refund = raffle_account.prize_amount;
     
-    let escrow_seeds = &[
-        b"escrow",
-        raffle_id.to_le_bytes().as_ref(),
-        &[raffle_account.escrow_bump],
-    ];
-    let escrow_signer = &[&escrow_seeds[..]];
-    
     // Refund creator's prize amount from escrow
-    if creator_prize_refund > 0 {
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.escrow_account.to_account_info(),
-                    to: ctx.accounts.creator.to_account_info(),
-                },
-                escrow_signer,
-            ),
-            creator_prize_refund,
-        )?;
-    }
+    transfer_lamports_from_pda(
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        creator_prize_refund,
+    )?;
     
     let current_time = Clock::get()?.unix_timestamp;
     
@@ -107,12 +95,23 @@ pub fn handler(
         creator_prize_refund
     );
     
+    emit!(RaffleCancelledEvent {
+        raffle_id,
+        creator: raffle_account.creator,
+        cancelled_by: ctx.accounts.creator.key(),
+        tickets_sold: raffle_account.tickets_sold,
+        total_collected,
+        prize_refunded: creator_prize_refund,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils;
 
     #[test]
     fn test_cancel_raffle_validation() {
@@ -169,6 +168,7 @@ mod tests {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 50;
         raffle.ticket_price = 10_000_000; // 0.01 SOL
+        raffle.ticket_revenue = raffle.tickets_sold as u64 * raffle.ticket_price;
         raffle.prize_amount = 1_000_000_000; // 1 SOL
         
         let total_collected = raffle.total_collected();
@@ -182,6 +182,7 @@ mod tests {
     fn test_cancellation_with_no_tickets() {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 0;
+        raffle.ticket_revenue = 0;
         raffle.prize_amount = 1_000_000_000; // 1 SOL
         
         let total_collected = raffle.total_collected();
@@ -196,6 +197,7 @@ mod tests {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 25;
         raffle.ticket_price = 20_000_000; // 0.02 SOL
+        raffle.ticket_revenue = raffle.tickets_sold as u64 * raffle.ticket_price;
         raffle.prize_amount = 1_000_000_000; // 1 SOL
         
         let total_collected = raffle.total_collected();
@@ -297,26 +299,9 @@ mod tests {
 
     fn create_test_raffle() -> RaffleAccount {
         RaffleAccount {
-            id: 1,
-            creator: Pubkey::new_unique(),
-            title: "Test Raffle".to_string(),
-            description: "Test Description".to_string(),
-            prize_amount: 1_000_000_000, // 1 SOL
-            ticket_price: 10_000_000,    // 0.01 SOL
-            max_tickets: 100,
             tickets_sold: 10,
-            start_time: 0,
-            end_time: 86400,
-            status: RaffleStatus::Active,
-            escrow_bump: 255,
-            raffle_bump: 254,
-            vrf_request: None,
-            winner: None,
-            winning_ticket: None,
-            vrf_proof: None,
-            created_at: 0,
-            drawn_at: None,
-            distributed_at: None,
+            ticket_revenue: 100_000_000,
+            ..test_utils::create_test_raffle()
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use settlement::escrow::transfer_lamports_from_pda;
+use crate::state::*;
+use crate::error::*;
+use super::rerequest_winner_selection::require_orao_pending;
+
+/// Cancel a raffle whose VRF request timed out, enabling refunds (permissionless)
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct CancelStalledRaffle<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"escrow",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.escrow_bump,
+        constraint = escrow_account.raffle_id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub escrow_account: Account<'info, EscrowAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: Raffle creator receiving the prize refund - validated against raffle
+    #[account(
+        mut,
+        constraint = creator.key() == raffle_account.creator @ RaffleError::UnauthorizedCreator
+    )]
+    pub creator: AccountInfo<'info>,
+    
+    /// CHECK: Pending ORAO randomness account, required for ORAO draws and verified in handler
+    pub vrf_request: Option<AccountInfo<'info>>,
+    
+    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CancelStalledRaffle>,
+    raffle_id: u64,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // VRF request must have timed out without fulfillment
+    require!(
+        raffle_account.is_draw_stalled(current_time),
+        RaffleError::DrawNotStalled
+    );
+    
+    // An ORAO draw whose randomness has arrived must be drawn, not cancelled
+    if raffle_account.randomness_provider == RandomnessProvider::Orao {
+        let vrf_request = ctx.accounts.vrf_request
+            .as_ref()
+            .ok_or(RaffleError::VRFOracleMismatch)?;
+        require_orao_pending(raffle_account, vrf_request)?;
+    }
+    
+    let creator_prize_refund = raffle_account.prize_amount;
+    
+    // Refund creator's prize amount from escrow
+    transfer_lamports_from_pda(
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        creator_prize_refund,
+    )?;
+    
+    // Update raffle state
+    raffle_account.status = RaffleStatus::Cancelled;
+    raffle_account.drawn_at = Some(current_time); // Use drawn_at to track cancellation time
+    
+    msg!(
+        "Stalled raffle cancelled - Raffle ID: {}, VRF Request: {:?}, Attempts: {}, Tickets Sold: {}, Prize Refunded: {} lamports",
+        raffle_id,
+        raffle_account.vrf_request,
+        raffle_account.draw_attempts,
+        raffle_account.tickets_sold,
+        creator_prize_refund
+    );
+    
+    emit!(RaffleCancelledEvent {
+        raffle_id,
+        creator: raffle_account.creator,
+        cancelled_by: ctx.accounts.caller.key(),
+        tickets_sold: raffle_account.tickets_sold,
+        total_collected: raffle_account.total_collected(),
+        prize_refunded: creator_prize_refund,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_cancel_requires_stalled_draw() {
+        let mut raffle = create_test_raffle();
+        let timed_out = raffle.draw_requested_at.unwrap() + raffle.draw_timeout;
+        
+        assert!(!raffle.is_draw_stalled(timed_out - 1));
+        assert!(raffle.is_draw_stalled(timed_out));
+        
+        // Once randomness is consumed the draw is no longer stalled
+        raffle.winning_ticket = Some(4);
+        assert!(!raffle.is_draw_stalled(timed_out));
+    }
+
+    fn create_test_raffle() -> RaffleAccount {
+        RaffleAccount {
+            tickets_sold: 10,
+            ticket_revenue: 100_000_000,
+            status: RaffleStatus::Drawing,
+            vrf_request: Some(Pubkey::new_unique()),
+            draw_requested_at: Some(86400),
+            draw_attempts: RaffleAccount::MAX_DRAW_ATTEMPTS,
+            ..test_utils::create_test_raffle()
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use settlement::escrow::transfer_lamports_from_pda;
+use crate::state::*;
+use crate::error::*;
+
+/// Cancel a raffle that ended below its minimum ticket threshold (permissionless)
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct CancelUndersoldRaffle<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"escrow",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.escrow_bump,
+        constraint = escrow_account.raffle_id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub escrow_account: Account<'info, EscrowAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: Raffle creator receiving the prize refund - validated against raffle
+    #[account(
+        mut,
+        constraint = creator.key() == raffle_account.creator @ RaffleError::UnauthorizedCreator
+    )]
+    pub creator: AccountInfo<'info>,
+    
+    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CancelUndersoldRaffle>,
+    raffle_id: u64,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Raffle must have ended without reaching its minimum ticket threshold
+    require!(
+        raffle_account.status == RaffleStatus::Active,
+        RaffleError::InvalidRaffleState
+    );
+    require!(
+        raffle_account.has_ended(current_time),
+        RaffleError::RaffleNotEnded
+    );
+    require!(
+        raffle_account.is_undersold(current_time),
+        RaffleError::MinimumTicketsReached
+    );
+    
+    let creator_prize_refund = raffle_account.prize_amount;
+    
+    // Refund creator's prize amount from escrow
+    transfer_lamports_from_pda(
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        creator_prize_refund,
+    )?;
+    
+    // Update raffle state
+    raffle_account.status = RaffleStatus::Cancelled;
+    raffle_account.drawn_at = Some(current_time); // Use drawn_at to track cancellation time
+    
+    msg!(
+        "Undersold raffle cancelled - Raffle ID: {}, Tickets Sold: {}, Minimum: {}, Prize Refunded: {} lamports",
+        raffle_id,
+        raffle_account.tickets_sold,
+        raffle_account.min_tickets,
+        creator_prize_refund
+    );
+    
+    emit!(RaffleCancelledEvent {
+        raffle_id,
+        creator: raffle_account.creator,
+        cancelled_by: ctx.accounts.caller.key(),
+        tickets_sold: raffle_account.tickets_sold,
+        total_collected: raffle_account.total_collected(),
+        prize_refunded: creator_prize_refund,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_cancel_requires_undersold() {
+        let mut raffle = create_test_raffle();
+        let current_time = 1000i64;
+        
+        // Ended below threshold
+        raffle.tickets_sold = 4;
+        assert!(raffle.is_undersold(current_time));
+        
+        // Ended at threshold - must be drawn instead
+        raffle.tickets_sold = 5;
+        assert!(!raffle.is_undersold(current_time));
+        assert!(raffle.can_be_drawn(current_time));
+    }
+
+    #[test]
+    fn test_cancel_requires_ended_raffle() {
+        let mut raffle = create_test_raffle();
+        raffle.end_time = 2000;
+        
+        // Still selling
+        assert!(!raffle.has_ended(1000));
+        assert!(!raffle.is_undersold(1000));
+    }
+
+    fn create_test_raffle() -> RaffleAccount {
+        RaffleAccount {
+            prize_amount: 10_000_000_000, // 10 SOL
+            ticket_price: 1_000_000, // 0.001 SOL
+            min_tickets: 5,
+            tickets_sold: 2,
+            ticket_revenue: 2_000_000,
+            end_time: 500,
+            ..test_utils::create_test_raffle()
+        }
+    }
+}
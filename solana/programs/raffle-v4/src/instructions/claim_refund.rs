@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 use crate::state::*;
 use crate::error::*;
+use settlement::escrow::transfer_lamports_from_pda;
 
-/// Claim refund for ticket in cancelled raffle
+/// Claim refund for a ticket range in a cancelled raffle
 #[derive(Accounts)]
 #[instruction(raffle_id: u64, ticket_number: u32)]
 pub struct ClaimRefund<'info> {
     #[account(
+        mut,
         seeds = [
             b"raffle",
             raffle_id.to_le_bytes().as_ref()
@@ -25,6 +26,7 @@ pub struct ClaimRefund<'info> {
             ticket_number.to_le_bytes().as_ref()
         ],
         bump = ticket_account.bump,
+        close = ticket_holder,
         constraint = ticket_account.raffle_id == raffle_id @ RaffleError::InvalidPDA,
         constraint = ticket_account.ticket_number == ticket_number @ RaffleError::InvalidTicketNumber
     )]
@@ -53,6 +55,19 @@ pub struct ClaimRefund<'info> {
     )]
     pub ticket_holder: Signer<'info>,
     
+    /// Holder's per-wallet ticket tracking, closed with their last ticket account
+    #[account(
+        mut,
+        seeds = [
+            b"user_tickets",
+            raffle_id.to_le_bytes().as_ref(),
+            ticket_holder.key().as_ref()
+        ],
+        bump = user_tickets.bump,
+        constraint = user_tickets.raffle_id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub user_tickets: Account<'info, UserTicketsAccount>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -62,11 +77,11 @@ pub fn handler(
     ticket_number: u32,
 ) -> Result<()> {
     let program_state = &ctx.accounts.program_state;
-    let raffle_account = &ctx.accounts.raffle_account;
+    let raffle_account = &mut ctx.accounts.raffle_account;
     let ticket_account = &mut ctx.accounts.ticket_account;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
     
     // Validate raffle state - can only refund from cancelled raffles
     require!(
@@ -75,57 +90,62 @@ pub fn handler(
     );
     
     // Check if ticket has already been refunded
-    // We'll use the purchase_time field as a refund marker (set to 0 when refunded)
-    require!(
-        ticket_account.purchase_time != 0,
-        RaffleError::TicketAlreadyRefunded
-    );
+    require!(!ticket_account.refunded, RaffleError::TicketAlreadyRefunded);
     
-    // Validate ticket number is within valid range
+    // Validate the ticket range is within the tickets sold
     require!(
-        ticket_number < raffle_account.tickets_sold,
+        ticket_account.end_ticket() <= raffle_account.tickets_sold,
         RaffleError::InvalidTicketNumber
     );
     
-    let refund_amount = raffle_account.ticket_price;
+    // Refund exactly what was paid for the range (tiers may have applied)
+    let refund_amount = ticket_account.amount_paid;
     
     // Verify escrow has sufficient balance for refund
     require!(
-        ctx.accounts.escrow_account.lamports() >= refund_amount,
+        ctx.accounts.escrow_account.to_account_info().lamports() >= refund_amount,
         RaffleError::InsufficientFunds
     );
     
-    let escrow_seeds = &[
-        b"escrow",
-        raffle_id.to_le_bytes().as_ref(),
-        &[raffle_account.escrow_bump],
-    ];
-    let escrow_signer = &[&escrow_seeds[..]];
-    
     // Transfer refund amount to ticket holder
-    system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.escrow_account.to_account_info(),
-                to: ctx.accounts.ticket_holder.to_account_info(),
-            },
-            escrow_signer,
-        ),
+    transfer_lamports_from_pda(
+        &ctx.accounts.escrow_account.to_account_info(),
+        &ctx.accounts.ticket_holder.to_account_info(),
         refund_amount,
     )?;
     
-    // Mark ticket as refunded by setting purchase_time to 0
-    ticket_account.purchase_time = 0;
+    // Mark ticket as refunded; the account is closed to the holder on exit
+    ticket_account.refunded = true;
+    raffle_account.tickets_refunded = raffle_account
+        .tickets_refunded
+        .checked_add_error(ticket_account.quantity)?;
+    raffle_account.open_ticket_accounts = raffle_account.open_ticket_accounts.checked_sub_error(1)?;
+    
+    // The wallet's tracking account goes back with its last ticket account
+    let user_tickets = &mut ctx.accounts.user_tickets;
+    if user_tickets.release(ticket_number) {
+        user_tickets.close(ctx.accounts.ticket_holder.to_account_info())?;
+    }
     
     msg!(
-        "Refund claimed - Raffle ID: {}, Ticket #: {}, Holder: {}, Amount: {} lamports",
+        "Refund claimed - Raffle ID: {}, Tickets #: {}-{}, Holder: {}, Amount: {} lamports",
         raffle_id,
         ticket_number,
+        ticket_account.end_ticket() - 1,
         ctx.accounts.ticket_holder.key(),
         refund_amount
     );
     
+    emit!(RefundClaimedEvent {
+        raffle_id,
+        ticket_holder: ctx.accounts.ticket_holder.key(),
+        ticket_account: ticket_account.key(),
+        first_ticket: ticket_number,
+        quantity: ticket_account.quantity,
+        amount: refund_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
@@ -142,7 +162,7 @@ mod tests {
         assert_eq!(raffle.status, RaffleStatus::Cancelled);
         
         // Ticket must not be already refunded
-        assert_ne!(ticket.purchase_time, 0);
+        assert!(!ticket.refunded);
         
         // Ticket number must be valid
         assert!(ticket.ticket_number < raffle.tickets_sold);
@@ -184,28 +204,45 @@ mod tests {
 
     #[test]
     fn test_refund_amount_calculation() {
-        let raffle = RaffleAccount {
-            ticket_price: 15_000_000, // 0.015 SOL
-            ..create_test_cancelled_raffle()
+        let ticket = TicketAccount {
+            amount_paid: 15_000_000, // 0.015 SOL
+            ..create_test_ticket()
         };
         
-        let refund_amount = raffle.ticket_price;
+        let refund_amount = ticket.amount_paid;
         assert_eq!(refund_amount, 15_000_000);
     }
 
+    #[test]
+    fn test_batch_refund_amount() {
+        let raffle = create_test_cancelled_raffle();
+        let ticket = TicketAccount {
+            ticket_number: 10,
+            quantity: 5,
+            amount_paid: 42_000_000, // early-bird pricing on part of the range
+            ..create_test_ticket()
+        };
+        
+        // Range must lie within tickets sold
+        assert!(ticket.end_ticket() <= raffle.tickets_sold);
+        
+        // Refund repays what the buyer actually paid, not the current price
+        assert_ne!(ticket.amount_paid, raffle.ticket_price * ticket.quantity as u64);
+        assert_eq!(ticket.amount_paid, 42_000_000);
+    }
+
     #[test]
     fn test_ticket_refund_marking() {
         let mut ticket = create_test_ticket();
-        let original_purchase_time = 1640995200i64;
+        let original_purchase_time = ticket.purchase_time;
         
-        // Initially has purchase time
-        ticket.purchase_time = original_purchase_time;
-        assert_eq!(ticket.purchase_time, original_purchase_time);
-        assert_ne!(ticket.purchase_time, 0);
+        // Initially not refunded
+        assert!(!ticket.refunded);
         
-        // After refund, purchase_time set to 0
-        ticket.purchase_time = 0;
-        assert_eq!(ticket.purchase_time, 0);
+        // Refund sets the flag and leaves purchase time intact
+        ticket.refunded = true;
+        assert!(ticket.refunded);
+        assert_eq!(ticket.purchase_time, original_purchase_time);
     }
 
     #[test]
@@ -213,12 +250,27 @@ mod tests {
         let mut ticket = create_test_ticket();
         
         // Ticket not yet refunded
-        ticket.purchase_time = 1640995200;
-        assert_ne!(ticket.purchase_time, 0);
+        assert!(!ticket.refunded);
         
         // Ticket already refunded
-        ticket.purchase_time = 0;
-        assert_eq!(ticket.purchase_time, 0);
+        ticket.refunded = true;
+        assert!(ticket.refunded);
+    }
+
+    #[test]
+    fn test_refunded_ticket_count() {
+        let mut raffle = create_test_cancelled_raffle();
+        let ticket = TicketAccount {
+            quantity: 5,
+            ..create_test_ticket()
+        };
+        
+        raffle.tickets_refunded = raffle
+            .tickets_refunded
+            .checked_add_error(ticket.quantity)
+            .unwrap();
+        assert_eq!(raffle.tickets_refunded, 5);
+        assert!(raffle.tickets_refunded <= raffle.tickets_sold);
     }
 
     #[test]
@@ -234,8 +286,8 @@ mod tests {
         assert!(49 < raffle.tickets_sold);
         
         // Invalid ticket numbers
-        assert!(!(50 < raffle.tickets_sold)); // Equal to tickets_sold
-        assert!(!(100 < raffle.tickets_sold)); // Greater than tickets_sold
+        assert!(50 >= raffle.tickets_sold); // Equal to tickets_sold
+        assert!(100 >= raffle.tickets_sold); // Greater than tickets_sold
     }
 
     #[test]
@@ -280,11 +332,11 @@ mod tests {
         assert_eq!(ticket.purchase_time, purchase_time);
         
         // After refund processing
-        ticket.purchase_time = 0;
-        assert_eq!(ticket.purchase_time, 0);
+        ticket.refunded = true;
+        assert_eq!(ticket.purchase_time, purchase_time);
         
         // Cannot refund again
-        assert_eq!(ticket.purchase_time, 0);
+        assert!(ticket.refunded);
     }
 
     #[test]
@@ -317,9 +369,12 @@ mod tests {
         assert_eq!(refund_amount, 0);
         
         // Even zero refunds should process correctly
-        let mut ticket = create_test_ticket();
-        ticket.purchase_time = 1640995200;
-        assert_ne!(ticket.purchase_time, 0);
+        let ticket = TicketAccount {
+            amount_paid: 0,
+            ..create_test_ticket()
+        };
+        assert!(!ticket.refunded);
+        assert_eq!(ticket.amount_paid, 0);
     }
 
     fn create_test_cancelled_raffle() -> RaffleAccount {
@@ -328,16 +383,31 @@ mod tests {
             creator: Pubkey::new_unique(),
             title: "Test Cancelled Raffle".to_string(),
             description: "Test Description".to_string(),
+            content: None,
             prize_amount: 1_000_000_000, // 1 SOL
             ticket_price: 10_000_000,    // 0.01 SOL
+            fee_rate: 300,
+            treasury: Pubkey::new_unique(),
+            price_tiers: Vec::new(),
             max_tickets: 100,
+            min_tickets: 1,
             tickets_sold: 25,
+            ticket_revenue: 250_000_000,
+            tickets_refunded: 0,
+            open_ticket_accounts: 0,
+            max_tickets_per_wallet: None,
             start_time: 0,
             end_time: 86400,
             status: RaffleStatus::Cancelled,
+            frozen: false,
             escrow_bump: 255,
             raffle_bump: 254,
             vrf_request: None,
+            randomness_provider: RandomnessProvider::Orao,
+            randomness_request: None,
+            draw_timeout: 86400,
+            draw_requested_at: None,
+            draw_attempts: 0,
             winner: None,
             winning_ticket: None,
             vrf_proof: None,
@@ -352,7 +422,10 @@ mod tests {
             raffle_id: 1,
             owner: Pubkey::new_unique(),
             ticket_number: 15,
-            purchase_time: 1640995200, // Valid purchase time (not refunded)
+            quantity: 1,
+            amount_paid: 10_000_000,
+            refunded: false,
+            purchase_time: 1640995200,
             bump: 253,
         }
     }
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use settlement::escrow::transfer_lamports_from_pda;
+
+/// Close a settled raffle and its escrow, sweeping any escrow surplus to treasury
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct CloseRaffle<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        close = creator,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"escrow",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.escrow_bump,
+        close = creator,
+        constraint = escrow_account.raffle_id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub escrow_account: Account<'info, EscrowAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: Raffle creator who paid the rent - validated against raffle creator
+    #[account(
+        mut,
+        constraint = creator.key() == raffle_account.creator @ RaffleError::UnauthorizedCreator
+    )]
+    pub creator: AccountInfo<'info>,
+    
+    /// CHECK: Treasury snapshotted at raffle creation, receiving escrow surplus
+    #[account(
+        mut,
+        constraint = treasury.key() == raffle_account.treasury @ RaffleError::UnauthorizedAuthority
+    )]
+    pub treasury: AccountInfo<'info>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<CloseRaffle>,
+    raffle_id: u64,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &ctx.accounts.raffle_account;
+    let escrow_info = ctx.accounts.escrow_account.to_account_info();
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    // Raffle must be distributed or fully refunded
+    require!(raffle_account.is_settled(), RaffleError::RaffleNotSettled);
+    
+    // Ticket accounts validate against the raffle, so they must be closed first
+    require!(
+        raffle_account.open_ticket_accounts == 0,
+        RaffleError::TicketAccountsOpen
+    );
+    
+    // A settled escrow owes nothing, so only its rent goes back to the creator
+    let escrow_rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+    let surplus = escrow_info.lamports().saturating_sub(escrow_rent);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Sweep anything unexpected (dust, stray transfers) to treasury
+    if surplus > 0 {
+        transfer_lamports_from_pda(&escrow_info, &ctx.accounts.treasury, surplus)?;
+        
+        emit!(EscrowSweptEvent {
+            raffle_id,
+            escrow: escrow_info.key(),
+            treasury: ctx.accounts.treasury.key(),
+            amount: surplus,
+            timestamp: current_time,
+        });
+    }
+    
+    // Remaining escrow and raffle lamports are returned to the creator on close
+    let returned_to_creator = escrow_info
+        .lamports()
+        .checked_add_error(raffle_account.to_account_info().lamports())?;
+    
+    msg!(
+        "Raffle closed - Raffle ID: {}, Creator: {}, Returned: {} lamports, Swept: {} lamports",
+        raffle_id,
+        raffle_account.creator,
+        returned_to_creator,
+        surplus
+    );
+    
+    emit!(RaffleClosedEvent {
+        raffle_id,
+        creator: raffle_account.creator,
+        closed_by: ctx.accounts.caller.key(),
+        status: raffle_account.status,
+        returned_to_creator,
+        swept_to_treasury: surplus,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Close a ticket account of a settled raffle, returning rent to its owner
+///
+/// The owner's per-wallet tracking account is closed with their last ticket.
+#[derive(Accounts)]
+#[instruction(raffle_id: u64, ticket_number: u32)]
+pub struct CloseTicket<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"ticket",
+            raffle_id.to_le_bytes().as_ref(),
+            ticket_number.to_le_bytes().as_ref()
+        ],
+        bump = ticket_account.bump,
+        close = owner,
+        constraint = ticket_account.raffle_id == raffle_id @ RaffleError::InvalidPDA,
+        constraint = ticket_account.ticket_number == ticket_number @ RaffleError::InvalidTicketNumber
+    )]
+    pub ticket_account: Account<'info, TicketAccount>,
+    
+    /// CHECK: Ticket owner receiving the rent - validated against ticket owner
+    #[account(
+        mut,
+        constraint = owner.key() == ticket_account.owner @ RaffleError::UnauthorizedTicketOwner
+    )]
+    pub owner: AccountInfo<'info>,
+    
+    /// Owner's per-wallet ticket tracking, closed with their last ticket account
+    #[account(
+        mut,
+        seeds = [
+            b"user_tickets",
+            raffle_id.to_le_bytes().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = user_tickets.bump,
+        constraint = user_tickets.raffle_id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub user_tickets: Account<'info, UserTicketsAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<CloseTicket>,
+    raffle_id: u64,
+    ticket_number: u32,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    let ticket_account = &ctx.accounts.ticket_account;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    // Tickets stay open until the prize is paid out or every buyer is refunded
+    require!(raffle_account.is_settled(), RaffleError::RaffleNotSettled);
+    
+    raffle_account.open_ticket_accounts = raffle_account.open_ticket_accounts.checked_sub_error(1)?;
+    
+    let rent_returned = ticket_account.to_account_info().lamports();
+    
+    // The wallet's tracking account goes back with its last ticket account
+    let user_tickets = &mut ctx.accounts.user_tickets;
+    if user_tickets.release(ticket_number) {
+        user_tickets.close(ctx.accounts.owner.to_account_info())?;
+    }
+    
+    msg!(
+        "Ticket closed - Raffle ID: {}, Ticket #: {}, Owner: {}, Rent: {} lamports",
+        raffle_id,
+        ticket_number,
+        ctx.accounts.owner.key(),
+        rent_returned
+    );
+    
+    emit!(TicketClosedEvent {
+        raffle_id,
+        ticket_account: ticket_account.key(),
+        owner: ctx.accounts.owner.key(),
+        first_ticket: ticket_number,
+        rent_returned,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_close_after_distribution() {
+        let mut raffle = create_test_raffle();
+        
+        // Winner bound but prize not yet paid
+        assert!(!raffle.is_settled());
+        
+        raffle.distributed_at = Some(1641000000);
+        assert!(raffle.is_settled());
+    }
+
+    #[test]
+    fn test_close_after_full_refund() {
+        let mut raffle = RaffleAccount {
+            status: RaffleStatus::Cancelled,
+            winner: None,
+            tickets_refunded: 40,
+            ..create_test_raffle()
+        };
+        
+        // Outstanding refunds block closure
+        assert!(!raffle.is_settled());
+        
+        raffle.tickets_refunded = raffle.tickets_sold;
+        assert!(raffle.is_settled());
+    }
+
+    #[test]
+    fn test_active_raffle_not_settled() {
+        let raffle = RaffleAccount {
+            status: RaffleStatus::Active,
+            winner: None,
+            ..create_test_raffle()
+        };
+        assert!(!raffle.is_settled());
+        
+        let raffle = RaffleAccount {
+            status: RaffleStatus::Drawing,
+            winner: None,
+            ..create_test_raffle()
+        };
+        assert!(!raffle.is_settled());
+    }
+
+    #[test]
+    fn test_open_ticket_count() {
+        let mut raffle = create_test_raffle();
+        
+        raffle.open_ticket_accounts = raffle.open_ticket_accounts.checked_sub_error(1).unwrap();
+        assert_eq!(raffle.open_ticket_accounts, 2);
+        
+        // Cannot close more accounts than were opened
+        raffle.open_ticket_accounts = 0;
+        assert!(raffle.open_ticket_accounts.checked_sub_error(1).is_err());
+    }
+
+    fn create_test_raffle() -> RaffleAccount {
+        RaffleAccount {
+            tickets_sold: 50,
+            ticket_revenue: 500_000_000,
+            open_ticket_accounts: 3,
+            status: RaffleStatus::Complete,
+            draw_attempts: 1,
+            winner: Some(Pubkey::new_unique()),
+            winning_ticket: Some(12),
+            drawn_at: Some(1640995200),
+            ..test_utils::create_test_raffle()
+        }
+    }
+}
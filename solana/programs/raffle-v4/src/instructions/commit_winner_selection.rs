@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use randomness::signed_oracle::SignedOracleRandomness;
+use randomness::slot_hash::SlotHashRandomness;
+use randomness::RandomnessSource;
+use crate::state::*;
+use crate::error::*;
+use super::request_winner_selection::generate_vrf_seed;
+
+/// Open the draw for a raffle that uses a signed-oracle or slot-hash provider
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct CommitWinnerSelection<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<CommitWinnerSelection>,
+    raffle_id: u64,
+    commitment: Option<[u8; 32]>,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_DRAW), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    // Validate raffle state
+    require!(
+        raffle_account.status == RaffleStatus::Active,
+        RaffleError::InvalidRaffleState
+    );
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Check if the raffle has ended with enough tickets sold
+    require!(
+        raffle_account.has_ended(current_time),
+        RaffleError::RaffleNotEnded
+    );
+    require!(
+        raffle_account.tickets_sold > 0,
+        RaffleError::NoTicketsSold
+    );
+    require!(
+        raffle_account.can_be_drawn(current_time),
+        RaffleError::MinimumTicketsNotMet
+    );
+    
+    let seed = generate_vrf_seed(raffle_account)?;
+    
+    // Open the request with the raffle's provider
+    let request = match raffle_account.randomness_provider {
+        RandomnessProvider::SignedOracle => SignedOracleRandomness::request((), seed)?,
+        RandomnessProvider::SlotHash => {
+            // Only the creator can reveal, so only the creator commits
+            require_keys_eq!(
+                ctx.accounts.caller.key(),
+                raffle_account.creator,
+                RaffleError::UnauthorizedCreator
+            );
+            let commitment = commitment.ok_or(RaffleError::CommitmentRequired)?;
+            SlotHashRandomness::request(commitment, seed)?
+        }
+        RandomnessProvider::Orao => return err!(RaffleError::WrongRandomnessProvider),
+    };
+    
+    // Update raffle state
+    raffle_account.status = RaffleStatus::Drawing;
+    raffle_account.randomness_request = Some(request);
+    raffle_account.draw_requested_at = Some(current_time);
+    raffle_account.draw_attempts = 1;
+    
+    msg!(
+        "Winner selection committed - Raffle ID: {}, Provider: {:?}, Slot: {}, Tickets Sold: {}",
+        raffle_id,
+        raffle_account.randomness_provider,
+        request.slot,
+        raffle_account.tickets_sold
+    );
+    
+    emit!(DrawCommittedEvent {
+        raffle_id,
+        randomness_provider: raffle_account.randomness_provider,
+        seed: request.seed,
+        commitment: request.commitment,
+        slot: request.slot,
+        tickets_sold: raffle_account.tickets_sold,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_commit_requires_ended_raffle() {
+        let mut raffle = create_test_raffle();
+        raffle.tickets_sold = 5;
+        
+        assert!(!raffle.can_be_drawn(raffle.end_time - 1));
+        assert!(raffle.can_be_drawn(raffle.end_time + 1));
+    }
+
+    #[test]
+    fn test_commit_seed_is_request_seed() {
+        let raffle = create_test_raffle();
+        let seed = generate_vrf_seed(&raffle).unwrap();
+        
+        let request = RandomnessRequest {
+            seed,
+            commitment: randomness::slot_hash::commitment(&[9u8; 32]),
+            slot: 100,
+        };
+        assert_eq!(request.seed, generate_vrf_seed(&raffle).unwrap());
+        assert_ne!(request.commitment, [0u8; 32]);
+    }
+
+    fn create_test_raffle() -> RaffleAccount {
+        RaffleAccount {
+            end_time: 1000,
+            randomness_provider: RandomnessProvider::SlotHash,
+            ..test_utils::create_test_raffle()
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use settlement::fees::split_fee;
 use crate::state::*;
 use crate::error::*;
 
@@ -9,23 +10,44 @@ pub struct CreateRaffleParams {
     /// Unique raffle identifier
     pub raffle_id: u64,
     
-    /// Raffle title (max 200 characters)
+    /// Raffle title (max 200 characters, 64 for compact raffles)
     pub title: String,
     
-    /// Raffle description (max 1000 characters)
+    /// Raffle description (max 1000 characters, empty for compact raffles)
     pub description: String,
     
+    /// Off-chain description URI and hash (Some = compact account layout)
+    pub content: Option<OffChainContent>,
+    
     /// Prize amount in lamports
     pub prize_amount: u64,
     
     /// Price per ticket in lamports
     pub ticket_price: u64,
     
+    /// Optional early-bird pricing tiers (max 4)
+    pub price_tiers: Vec<PriceTier>,
+    
     /// Maximum number of tickets
     pub max_tickets: u32,
     
-    /// Duration in seconds from creation
+    /// Minimum tickets that must sell before a draw (default 1)
+    pub min_tickets: Option<u32>,
+    
+    /// Maximum tickets a single wallet may hold (None = unlimited)
+    pub max_tickets_per_wallet: Option<u32>,
+    
+    /// Scheduled start timestamp (None = start immediately)
+    pub start_time: Option<i64>,
+    
+    /// Duration in seconds from the start time
     pub duration: i64,
+    
+    /// Seconds to wait for VRF fulfillment before recovery (default 24 hours)
+    pub draw_timeout: Option<i64>,
+    
+    /// Randomness provider used for the draw
+    pub randomness_provider: RandomnessProvider,
 }
 
 /// Create a new raffle
@@ -35,7 +57,7 @@ pub struct CreateRaffle<'info> {
     #[account(
         init,
         payer = creator,
-        space = 8 + RaffleAccount::LEN,
+        space = 8 + RaffleAccount::space_for(&params),
         seeds = [
             b"raffle",
             params.raffle_id.to_le_bytes().as_ref()
@@ -76,17 +98,31 @@ pub fn handler(
     let program_state = &ctx.accounts.program_state;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_CREATE), RaffleError::ProgramPaused);
     
     // Validate raffle parameters
     RaffleAccount::validate_params(&params)?;
     
     let current_time = Clock::get()?.unix_timestamp;
-    let end_time = current_time.checked_add(params.duration)
+    
+    // Validate scheduled start time (if set)
+    let start_time = match params.start_time {
+        Some(start_time) => {
+            require!(
+                start_time >= current_time
+                    && start_time - current_time <= RaffleAccount::MAX_START_DELAY,
+                RaffleError::InvalidStartTime
+            );
+            start_time
+        }
+        None => current_time,
+    };
+    
+    let end_time = start_time.checked_add(params.duration)
         .ok_or(RaffleError::ArithmeticOverflow)?;
     
     // Calculate required funds (prize + platform fee + rent)
-    let platform_fee = (params.prize_amount * program_state.fee_rate as u64) / 10000;
+    let platform_fee = split_fee(params.prize_amount, program_state.fee_rate)?.fee;
     let rent_exemption = Rent::get()?.minimum_balance(8 + EscrowAccount::LEN);
     let total_required = params.prize_amount
         .checked_add(platform_fee)
@@ -118,16 +154,32 @@ pub fn handler(
     raffle_account.creator = ctx.accounts.creator.key();
     raffle_account.title = params.title;
     raffle_account.description = params.description;
+    raffle_account.content = params.content;
     raffle_account.prize_amount = params.prize_amount;
     raffle_account.ticket_price = params.ticket_price;
+    raffle_account.fee_rate = program_state.fee_rate;
+    raffle_account.treasury = program_state.treasury;
+    raffle_account.price_tiers = params.price_tiers;
     raffle_account.max_tickets = params.max_tickets;
+    raffle_account.min_tickets = params.min_tickets.unwrap_or(1);
     raffle_account.tickets_sold = 0;
-    raffle_account.start_time = current_time;
+    raffle_account.ticket_revenue = 0;
+    raffle_account.tickets_refunded = 0;
+    raffle_account.open_ticket_accounts = 0;
+    raffle_account.max_tickets_per_wallet = params.max_tickets_per_wallet;
+    raffle_account.start_time = start_time;
     raffle_account.end_time = end_time;
     raffle_account.status = RaffleStatus::Active;
+    raffle_account.frozen = false;
     raffle_account.escrow_bump = ctx.bumps.escrow_account;
     raffle_account.raffle_bump = ctx.bumps.raffle_account;
     raffle_account.vrf_request = None;
+    raffle_account.randomness_provider = params.randomness_provider;
+    raffle_account.randomness_request = None;
+    raffle_account.draw_timeout = params.draw_timeout
+        .unwrap_or(RaffleAccount::DEFAULT_DRAW_TIMEOUT);
+    raffle_account.draw_requested_at = None;
+    raffle_account.draw_attempts = 0;
     raffle_account.winner = None;
     raffle_account.winning_ticket = None;
     raffle_account.vrf_proof = None;
@@ -154,6 +206,25 @@ pub fn handler(
         params.max_tickets
     );
     
+    emit!(RaffleCreatedEvent {
+        raffle_id: params.raffle_id,
+        creator: ctx.accounts.creator.key(),
+        escrow: ctx.accounts.escrow_account.key(),
+        content: raffle_account.content.clone(),
+        prize_amount: params.prize_amount,
+        ticket_price: params.ticket_price,
+        fee_rate: raffle_account.fee_rate,
+        treasury: raffle_account.treasury,
+        price_tiers: raffle_account.price_tiers.clone(),
+        max_tickets: params.max_tickets,
+        min_tickets: raffle_account.min_tickets,
+        max_tickets_per_wallet: params.max_tickets_per_wallet,
+        start_time,
+        end_time,
+        randomness_provider: params.randomness_provider,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
@@ -168,10 +239,17 @@ mod tests {
             raffle_id: 1,
             title: "Test Raffle".to_string(),
             description: "A test raffle".to_string(),
+            content: None,
             prize_amount: 1_000_000_000, // 1 SOL
             ticket_price: 10_000_000,    // 0.01 SOL
+            price_tiers: Vec::new(),
             max_tickets: 100,
+            min_tickets: None,
+            max_tickets_per_wallet: None,
+            start_time: None,
             duration: 86400, // 24 hours
+            draw_timeout: None,
+            randomness_provider: RandomnessProvider::Orao,
         };
         assert!(RaffleAccount::validate_params(&valid_params).is_ok());
         
@@ -189,6 +267,31 @@ mod tests {
         };
         assert!(RaffleAccount::validate_params(&invalid_description).is_err());
         
+        // Compact mode: short title, off-chain description
+        let compact = CreateRaffleParams {
+            description: String::new(),
+            content: Some(OffChainContent {
+                uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+                content_hash: [7u8; 32],
+            }),
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&compact).is_ok());
+        assert!(RaffleAccount::space_for(&compact) < RaffleAccount::space_for(&valid_params));
+        
+        // Compact mode rejects inline descriptions and long titles
+        let compact_with_description = CreateRaffleParams {
+            description: "inline".to_string(),
+            ..compact.clone()
+        };
+        assert!(RaffleAccount::validate_params(&compact_with_description).is_err());
+        
+        let compact_long_title = CreateRaffleParams {
+            title: "x".repeat(65),
+            ..compact.clone()
+        };
+        assert!(RaffleAccount::validate_params(&compact_long_title).is_err());
+        
         // Prize amount too small
         let invalid_prize = CreateRaffleParams {
             prize_amount: 50_000_000, // 0.05 SOL (less than 0.1 SOL minimum)
@@ -203,6 +306,37 @@ mod tests {
         };
         assert!(RaffleAccount::validate_params(&invalid_ticket_price).is_err());
         
+        // Early-bird tiers
+        let valid_tiers = CreateRaffleParams {
+            price_tiers: vec![
+                PriceTier { limit: PriceTierLimit::BeforeTime(1_700_000_000), price: 5_000_000 },
+                PriceTier { limit: PriceTierLimit::BeforeTicketsSold(20), price: 8_000_000 },
+            ],
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&valid_tiers).is_ok());
+        
+        // Tier price below minimum
+        let cheap_tier = CreateRaffleParams {
+            price_tiers: vec![PriceTier { limit: PriceTierLimit::BeforeTicketsSold(20), price: 500_000 }],
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&cheap_tier).is_err());
+        
+        // Count tier beyond max tickets
+        let oversized_tier = CreateRaffleParams {
+            price_tiers: vec![PriceTier { limit: PriceTierLimit::BeforeTicketsSold(101), price: 5_000_000 }],
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&oversized_tier).is_err());
+        
+        // Too many tiers
+        let too_many_tiers = CreateRaffleParams {
+            price_tiers: vec![PriceTier { limit: PriceTierLimit::BeforeTicketsSold(10), price: 5_000_000 }; 5],
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&too_many_tiers).is_err());
+        
         // Max tickets too high
         let invalid_max_tickets = CreateRaffleParams {
             max_tickets: 15_000, // More than 10,000 maximum
@@ -210,6 +344,34 @@ mod tests {
         };
         assert!(RaffleAccount::validate_params(&invalid_max_tickets).is_err());
         
+        // Minimum tickets above max tickets
+        let invalid_min_tickets = CreateRaffleParams {
+            min_tickets: Some(101),
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&invalid_min_tickets).is_err());
+        
+        // Per-wallet limit within bounds
+        let valid_wallet_limit = CreateRaffleParams {
+            max_tickets_per_wallet: Some(10),
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&valid_wallet_limit).is_ok());
+        
+        // Per-wallet limit of zero
+        let zero_wallet_limit = CreateRaffleParams {
+            max_tickets_per_wallet: Some(0),
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&zero_wallet_limit).is_err());
+        
+        // Per-wallet limit above max tickets
+        let excessive_wallet_limit = CreateRaffleParams {
+            max_tickets_per_wallet: Some(101),
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&excessive_wallet_limit).is_err());
+        
         // Duration too short
         let invalid_duration_short = CreateRaffleParams {
             duration: 1800, // 30 minutes (less than 1 hour minimum)
@@ -217,12 +379,29 @@ mod tests {
         };
         assert!(RaffleAccount::validate_params(&invalid_duration_short).is_err());
         
+        // Draw timeout too short
+        let invalid_draw_timeout = CreateRaffleParams {
+            draw_timeout: Some(600), // 10 minutes
+            ..valid_params.clone()
+        };
+        assert!(RaffleAccount::validate_params(&invalid_draw_timeout).is_err());
+        
         // Duration too long
         let invalid_duration_long = CreateRaffleParams {
             duration: 3_000_000, // More than 30 days
             ..valid_params.clone()
         };
         assert!(RaffleAccount::validate_params(&invalid_duration_long).is_err());
+        
+        // Provider must be compiled into this build
+        let slot_hash_provider = CreateRaffleParams {
+            randomness_provider: RandomnessProvider::SlotHash,
+            ..valid_params.clone()
+        };
+        assert_eq!(
+            RaffleAccount::validate_params(&slot_hash_provider).is_ok(),
+            cfg!(feature = "slot-hash")
+        );
     }
 
     #[test]
@@ -242,19 +421,4 @@ mod tests {
         // PDAs should be different
         assert_ne!(raffle_pda, escrow_pda);
     }
-
-    #[test]
-    fn test_calculate_required_funds() {
-        let prize_amount = 1_000_000_000u64; // 1 SOL
-        let fee_rate = 300u16; // 3%
-        let rent_exemption = 2_039_280u64; // Typical rent exemption
-        
-        // Calculate platform fee
-        let platform_fee = (prize_amount * fee_rate as u64) / 10000;
-        assert_eq!(platform_fee, 30_000_000); // 0.03 SOL
-        
-        // Calculate total required
-        let total_required = prize_amount + platform_fee + rent_exemption;
-        assert_eq!(total_required, 1_032_039_280); // ~1.032 SOL
-    }
 }
\ No newline at end of file
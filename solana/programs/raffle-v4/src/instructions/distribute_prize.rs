@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use settlement::escrow::transfer_lamports_from_pda;
 use crate::state::*;
 use crate::error::*;
 
@@ -43,10 +43,10 @@ pub struct DistributePrize<'info> {
     )]
     pub winner: AccountInfo<'info>,
     
-    /// CHECK: Treasury account for fee collection
+    /// CHECK: Treasury snapshotted at raffle creation
     #[account(
         mut,
-        constraint = treasury.key() == program_state.treasury @ RaffleError::UnauthorizedAuthority
+        constraint = treasury.key() == raffle_account.treasury @ RaffleError::UnauthorizedAuthority
     )]
     pub treasury: AccountInfo<'info>,
     
@@ -60,10 +60,12 @@ pub fn handler(
 ) -> Result<()> {
     let program_state = &mut ctx.accounts.program_state;
     let raffle_account = &mut ctx.accounts.raffle_account;
-    let escrow_account = &ctx.accounts.escrow_account;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_DISTRIBUTE), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
     
     // Validate raffle state
     require!(
@@ -83,56 +85,25 @@ pub fn handler(
         RaffleError::PrizeAlreadyDistributed
     );
     
-    // Calculate amounts
+    // Calculate amounts on the terms the raffle was created with
     let total_collected = raffle_account.total_collected();
-    let platform_fee = raffle_account.calculate_fee(program_state.fee_rate);
-    let winner_amount = raffle_account.calculate_winner_amount(program_state.fee_rate);
+    let platform_fee = raffle_account.calculate_fee(raffle_account.fee_rate)?;
+    let winner_amount = raffle_account.calculate_winner_amount(raffle_account.fee_rate)?;
     
     // Verify escrow has sufficient balance
-    let escrow_balance = ctx.accounts.escrow_account.lamports();
-    let required_balance = total_collected;
+    let escrow_info = ctx.accounts.escrow_account.to_account_info();
+    let escrow_balance = escrow_info.lamports();
+    let required_balance = raffle_account.prize_amount
+        .checked_add_error(total_collected)?;
     
     require!(
         escrow_balance >= required_balance,
         RaffleError::InsufficientFunds
     );
     
-    let escrow_seeds = &[
-        b"escrow",
-        raffle_id.to_le_bytes().as_ref(),
-        &[raffle_account.escrow_bump],
-    ];
-    let escrow_signer = &[&escrow_seeds[..]];
-    
-    // Transfer winner amount to winner
-    if winner_amount > 0 {
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.escrow_account.to_account_info(),
-                    to: ctx.accounts.winner.to_account_info(),
-                },
-                escrow_signer,
-            ),
-            winner_amount,
-        )?;
-    }
-    
-    // Transfer platform fee to treasury
-    if platform_fee > 0 {
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.escrow_account.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                },
-                escrow_signer,
-            ),
-            platform_fee,
-        )?;
-    }
+    // The escrow carries data, so lamports are moved directly
+    transfer_lamports_from_pda(&escrow_info, &ctx.accounts.winner, winner_amount)?;
+    transfer_lamports_from_pda(&escrow_info, &ctx.accounts.treasury, platform_fee)?;
     
     let current_time = Clock::get()?.unix_timestamp;
     
@@ -153,18 +124,30 @@ pub fn handler(
         program_state.total_volume
     );
     
+    emit!(PrizeDistributedEvent {
+        raffle_id,
+        winner: ctx.accounts.winner.key(),
+        treasury: ctx.accounts.treasury.key(),
+        winner_amount,
+        fee_amount: platform_fee,
+        total_collected,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils;
 
     #[test]
     fn test_prize_distribution_calculations() {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 100;
         raffle.ticket_price = 10_000_000; // 0.01 SOL per ticket
+        raffle.ticket_revenue = raffle.tickets_sold as u64 * raffle.ticket_price;
         
         let fee_rate = 300; // 3%
         
@@ -172,14 +155,26 @@ mod tests {
         let total_collected = raffle.total_collected();
         assert_eq!(total_collected, 1_000_000_000); // 1 SOL total
         
-        let platform_fee = raffle.calculate_fee(fee_rate);
+        let platform_fee = raffle.calculate_fee(fee_rate).unwrap();
         assert_eq!(platform_fee, 30_000_000); // 0.03 SOL fee
         
-        let winner_amount = raffle.calculate_winner_amount(fee_rate);
-        assert_eq!(winner_amount, 970_000_000); // 0.97 SOL to winner
+        let winner_amount = raffle.calculate_winner_amount(fee_rate).unwrap();
+        assert_eq!(winner_amount, raffle.prize_amount + 970_000_000); // prize + 0.97 SOL to winner
         
         // Verify amounts add up
-        assert_eq!(winner_amount + platform_fee, total_collected);
+        assert_eq!(winner_amount + platform_fee, raffle.prize_amount + total_collected);
+    }
+
+    #[test]
+    fn test_fee_uses_raffle_snapshot() {
+        let mut raffle = create_test_raffle();
+        raffle.ticket_revenue = 1_000_000_000;
+        raffle.fee_rate = 300;
+        
+        // A later program-wide fee change does not affect this raffle
+        let program_fee_rate = 1000u16;
+        assert_eq!(raffle.calculate_fee(raffle.fee_rate).unwrap(), 30_000_000);
+        assert_ne!(raffle.calculate_fee(raffle.fee_rate).unwrap(), raffle.calculate_fee(program_fee_rate).unwrap());
     }
 
     #[test]
@@ -226,21 +221,22 @@ mod tests {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 1000;
         raffle.ticket_price = 1_000_000; // 0.001 SOL per ticket
+        raffle.ticket_revenue = raffle.tickets_sold as u64 * raffle.ticket_price;
         
         let total_collected = raffle.total_collected();
         assert_eq!(total_collected, 1_000_000_000); // 1 SOL total
         
         // Test different fee rates
-        let fee_0 = raffle.calculate_fee(0); // 0%
+        let fee_0 = raffle.calculate_fee(0).unwrap(); // 0%
         assert_eq!(fee_0, 0);
         
-        let fee_100 = raffle.calculate_fee(100); // 1%
+        let fee_100 = raffle.calculate_fee(100).unwrap(); // 1%
         assert_eq!(fee_100, 10_000_000); // 0.01 SOL
         
-        let fee_500 = raffle.calculate_fee(500); // 5%
+        let fee_500 = raffle.calculate_fee(500).unwrap(); // 5%
         assert_eq!(fee_500, 50_000_000); // 0.05 SOL
         
-        let fee_1000 = raffle.calculate_fee(1000); // 10%
+        let fee_1000 = raffle.calculate_fee(1000).unwrap(); // 10%
         assert_eq!(fee_1000, 100_000_000); // 0.1 SOL
     }
 
@@ -249,22 +245,23 @@ mod tests {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 200;
         raffle.ticket_price = 5_000_000; // 0.005 SOL per ticket
+        raffle.ticket_revenue = raffle.tickets_sold as u64 * raffle.ticket_price;
         
         let total_collected = raffle.total_collected();
         assert_eq!(total_collected, 1_000_000_000); // 1 SOL total
         
         // Test different fee rates
-        let winner_0 = raffle.calculate_winner_amount(0); // 0% fee
-        assert_eq!(winner_0, 1_000_000_000); // Full amount
+        let winner_0 = raffle.calculate_winner_amount(0).unwrap(); // 0% fee
+        assert_eq!(winner_0, raffle.prize_amount + 1_000_000_000); // Full amount
         
-        let winner_250 = raffle.calculate_winner_amount(250); // 2.5% fee
-        assert_eq!(winner_250, 975_000_000); // 0.975 SOL
+        let winner_250 = raffle.calculate_winner_amount(250).unwrap(); // 2.5% fee
+        assert_eq!(winner_250, raffle.prize_amount + 975_000_000); // prize + 0.975 SOL
         
-        let winner_500 = raffle.calculate_winner_amount(500); // 5% fee
-        assert_eq!(winner_500, 950_000_000); // 0.95 SOL
+        let winner_500 = raffle.calculate_winner_amount(500).unwrap(); // 5% fee
+        assert_eq!(winner_500, raffle.prize_amount + 950_000_000); // prize + 0.95 SOL
         
-        let winner_1000 = raffle.calculate_winner_amount(1000); // 10% fee
-        assert_eq!(winner_1000, 900_000_000); // 0.9 SOL
+        let winner_1000 = raffle.calculate_winner_amount(1000).unwrap(); // 10% fee
+        assert_eq!(winner_1000, raffle.prize_amount + 900_000_000); // prize + 0.9 SOL
     }
 
     #[test]
@@ -272,15 +269,16 @@ mod tests {
         let mut raffle = create_test_raffle();
         raffle.tickets_sold = 0;
         raffle.ticket_price = 10_000_000;
+        raffle.ticket_revenue = raffle.tickets_sold as u64 * raffle.ticket_price;
         
         let total_collected = raffle.total_collected();
         assert_eq!(total_collected, 0);
         
-        let platform_fee = raffle.calculate_fee(300);
+        let platform_fee = raffle.calculate_fee(300).unwrap();
         assert_eq!(platform_fee, 0);
         
-        let winner_amount = raffle.calculate_winner_amount(300);
-        assert_eq!(winner_amount, 0);
+        let winner_amount = raffle.calculate_winner_amount(300).unwrap();
+        assert_eq!(winner_amount, raffle.prize_amount);
     }
 
     #[test]
@@ -340,26 +338,15 @@ mod tests {
 
     fn create_test_raffle() -> RaffleAccount {
         RaffleAccount {
-            id: 1,
-            creator: Pubkey::new_unique(),
-            title: "Test Raffle".to_string(),
-            description: "Test Description".to_string(),
-            prize_amount: 1_000_000_000, // 1 SOL
-            ticket_price: 10_000_000,    // 0.01 SOL
-            max_tickets: 100,
             tickets_sold: 50,
-            start_time: 0,
-            end_time: 86400,
+            ticket_revenue: 500_000_000,
             status: RaffleStatus::Complete,
-            escrow_bump: 255,
-            raffle_bump: 254,
             vrf_request: Some(Pubkey::new_unique()),
             winner: Some(Pubkey::new_unique()),
             winning_ticket: Some(25),
             vrf_proof: Some([1u8; 64]),
-            created_at: 0,
             drawn_at: Some(1640995200),
-            distributed_at: None,
+            ..test_utils::create_test_raffle()
         }
     }
 }
\ No newline at end of file
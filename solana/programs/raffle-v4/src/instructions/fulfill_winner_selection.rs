@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use orao_solana_vrf::state::Randomness;
+use randomness::orao::OraoRandomness;
+use randomness::RandomnessSource;
+use settlement::range::uniform_index;
 use crate::state::*;
 use crate::error::*;
 
-/// Fulfill VRF request and select winner based on randomness
+/// Fulfill VRF request and record the winning ticket number derived from randomness
 #[derive(Accounts)]
 #[instruction(raffle_id: u64)]
 pub struct FulfillWinnerSelection<'info> {
@@ -18,14 +20,10 @@ pub struct FulfillWinnerSelection<'info> {
     )]
     pub raffle_account: Account<'info, RaffleAccount>,
     
-    /// CHECK: The VRF request account containing randomness
+    /// CHECK: ORAO randomness account recorded by request_winner_selection
     #[account(
-        seeds = [
-            b"vrf_request",
-            raffle_id.to_le_bytes().as_ref()
-        ],
-        bump,
-        constraint = vrf_request.key() == raffle_account.vrf_request.unwrap() @ RaffleError::VRFOracleMismatch
+        owner = randomness::orao::ID @ RaffleError::VRFOracleMismatch,
+        constraint = Some(vrf_request.key()) == raffle_account.vrf_request @ RaffleError::VRFOracleMismatch
     )]
     pub vrf_request: AccountInfo<'info>,
     
@@ -35,40 +33,22 @@ pub struct FulfillWinnerSelection<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
     
-    /// The winner's ticket account (will be verified by PDA)
-    #[account(
-        seeds = [
-            b"ticket",
-            raffle_id.to_le_bytes().as_ref(),
-            winning_ticket_number.to_le_bytes().as_ref()
-        ],
-        bump,
-        constraint = winning_ticket.raffle_id == raffle_id @ RaffleError::InvalidTicketNumber,
-        constraint = winning_ticket.ticket_number == winning_ticket_number @ RaffleError::InvalidTicketNumber
-    )]
-    pub winning_ticket: Account<'info, TicketAccount>,
-    
     pub caller: Signer<'info>,
 }
 
-/// External parameter for the winning ticket number (calculated off-chain for efficiency)
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct FulfillParams {
-    pub winning_ticket_number: u32,
-}
-
 pub fn handler(
     ctx: Context<FulfillWinnerSelection>,
     raffle_id: u64,
-    winning_ticket_number: u32,
 ) -> Result<()> {
     let program_state = &ctx.accounts.program_state;
     let raffle_account = &mut ctx.accounts.raffle_account;
     let vrf_request = &ctx.accounts.vrf_request;
-    let winning_ticket = &ctx.accounts.winning_ticket;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_DRAW), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
     
     // Validate raffle state
     require!(
@@ -82,88 +62,65 @@ pub fn handler(
         RaffleError::VRFNotRequested
     );
     
-    // Check if winner has already been selected
+    // Check if the winning ticket has already been drawn
     require!(
-        raffle_account.winner.is_none(),
+        raffle_account.winning_ticket.is_none() && raffle_account.winner.is_none(),
         RaffleError::WinnerAlreadySelected
     );
     
-    // Deserialize and validate VRF randomness
-    let randomness_account = Randomness::try_deserialize(&mut vrf_request.data.borrow().as_ref())
-        .map_err(|_| RaffleError::InvalidVRFProof)?;
+    // Load the ORAO request derived from the recorded seed
+    let request = raffle_account.randomness_request.ok_or(RaffleError::VRFNotRequested)?;
+    let randomness_account = OraoRandomness::load(vrf_request, &request)?;
     
-    // Verify the randomness is fulfilled
-    require!(
-        randomness_account.seed.len() == 32,
-        RaffleError::InvalidVRFProof
-    );
-    
-    // Extract randomness value
-    let randomness = randomness_account.randomness;
-    require!(
-        randomness != [0u8; 64], // Ensure randomness is not empty
-        RaffleError::InvalidVRFProof
-    );
+    // Only accept randomness once ORAO reports the request as fulfilled
+    let randomness = randomness_account
+        .read_64()
+        .ok_or(RaffleError::RandomnessNotFulfilled)?;
     
     // Calculate winning ticket number from randomness
-    let calculated_winning_ticket = calculate_winning_ticket(&randomness, raffle_account.tickets_sold)?;
-    
-    // Verify the provided winning ticket number matches calculation
-    require!(
-        winning_ticket_number == calculated_winning_ticket,
-        RaffleError::InvalidTicketNumber
-    );
-    
-    // Verify winning ticket is within valid range
-    require!(
-        winning_ticket_number < raffle_account.tickets_sold,
-        RaffleError::InvalidTicketNumber
-    );
+    let winning_ticket_number = calculate_winning_ticket(&randomness, raffle_account.tickets_sold)?;
     
     let current_time = Clock::get()?.unix_timestamp;
     
-    // Update raffle account with winner information
-    raffle_account.status = RaffleStatus::Complete;
-    raffle_account.winner = Some(winning_ticket.owner);
+    // Record the draw; the winner is bound separately via bind_winner
     raffle_account.winning_ticket = Some(winning_ticket_number);
     raffle_account.vrf_proof = Some(randomness);
     raffle_account.drawn_at = Some(current_time);
     
     msg!(
-        "Winner selected - Raffle ID: {}, Winner: {}, Winning Ticket: {}, VRF Proof: {:?}",
+        "Winning ticket drawn - Raffle ID: {}, Winning Ticket: {}, VRF Proof: {:?}",
         raffle_id,
-        winning_ticket.owner,
         winning_ticket_number,
         &randomness[0..8] // Log first 8 bytes of proof for verification
     );
     
+    emit!(WinningTicketDrawnEvent {
+        raffle_id,
+        vrf_request: vrf_request.key(),
+        winning_ticket: winning_ticket_number,
+        tickets_sold: raffle_account.tickets_sold,
+        randomness,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
 /// Calculate the winning ticket number from VRF randomness
-fn calculate_winning_ticket(randomness: &[u8; 64], total_tickets: u32) -> Result<u32> {
+/// 
+/// Delegates to the shared unbiased range mapping, which rejects 8-byte lanes
+/// landing in the over-represented zone and tries the next one.
+pub fn calculate_winning_ticket(randomness: &[u8; 64], total_tickets: u32) -> Result<u32> {
     require!(total_tickets > 0, RaffleError::NoTicketsSold);
     
-    // Use first 8 bytes of randomness to create a u64
-    let mut bytes = [0u8; 8];
-    bytes.copy_from_slice(&randomness[0..8]);
-    let random_value = u64::from_le_bytes(bytes);
-    
-    // Calculate winning ticket using modulo to ensure fair distribution
-    let winning_ticket = (random_value % total_tickets as u64) as u32;
-    
-    Ok(winning_ticket)
-}
-
-/// Verify VRF proof integrity (additional validation)
-fn verify_vrf_proof_integrity(proof: &[u8; 64]) -> bool {
-    // Check that proof is not all zeros
-    !proof.iter().all(|&x| x == 0)
+    let winning_ticket = uniform_index(randomness, total_tickets as u64)?;
+    Ok(winning_ticket as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils;
 
     #[test]
     fn test_calculate_winning_ticket() {
@@ -209,11 +166,10 @@ mod tests {
         let mut results = std::collections::HashSet::new();
         let total_tickets = 100u32;
         
-        for i in 0..64 {
+        // The mapping scales by the high bits, so spread samples across the u64 range
+        for i in 0..64u64 {
             let mut randomness = [0u8; 64];
-            randomness[0] = i as u8;
-            randomness[1] = (i * 2) as u8;
-            randomness[2] = (i * 3) as u8;
+            randomness[0..8].copy_from_slice(&i.wrapping_mul(u64::MAX / 64).to_le_bytes());
             
             let winning_ticket = calculate_winning_ticket(&randomness, total_tickets).unwrap();
             results.insert(winning_ticket);
@@ -224,19 +180,87 @@ mod tests {
     }
 
     #[test]
-    fn test_vrf_proof_integrity() {
-        // Valid proof (not all zeros)
-        let valid_proof = [1u8; 64];
-        assert!(verify_vrf_proof_integrity(&valid_proof));
+    fn test_wide_multiply_mapping() {
+        // Top of the u64 range maps to the last ticket, bottom to the first
+        let mut randomness = [0u8; 64];
+        randomness[0..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(calculate_winning_ticket(&randomness, 10).unwrap(), 9);
+        
+        randomness[0..8].copy_from_slice(&1u64.to_le_bytes());
+        assert_eq!(calculate_winning_ticket(&randomness, 10).unwrap(), 0);
+        
+        // Just past the midpoint lands in the middle of the range
+        randomness[0..8].copy_from_slice(&((1u64 << 63) + (1u64 << 60)).to_le_bytes());
+        assert_eq!(calculate_winning_ticket(&randomness, 10).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_biased_lane_is_rejected() {
+        // With 3 tickets the threshold is 2^64 mod 3 = 1, so a zero lane is rejected
+        let mut randomness = [0u8; 64];
+        randomness[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(calculate_winning_ticket(&randomness, 3).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_mapping_distribution_is_uniform() {
+        use anchor_lang::solana_program::hash::hashv;
+        
+        let total_tickets = 7u32;
+        let samples = 70_000u32;
+        let mut distribution = vec![0u32; total_tickets as usize];
         
-        // Invalid proof (all zeros)
-        let invalid_proof = [0u8; 64];
-        assert!(!verify_vrf_proof_integrity(&invalid_proof));
+        for i in 0..samples {
+            let first = hashv(&[&i.to_le_bytes(), b"a"]).to_bytes();
+            let second = hashv(&[&i.to_le_bytes(), b"b"]).to_bytes();
+            let mut randomness = [0u8; 64];
+            randomness[..32].copy_from_slice(&first);
+            randomness[32..].copy_from_slice(&second);
+            
+            let ticket = calculate_winning_ticket(&randomness, total_tickets).unwrap();
+            distribution[ticket as usize] += 1;
+        }
         
-        // Mixed proof
-        let mut mixed_proof = [0u8; 64];
-        mixed_proof[0] = 1;
-        assert!(verify_vrf_proof_integrity(&mixed_proof));
+        // Each bucket should be within 5% of the expected count
+        let expected = samples / total_tickets;
+        for count in &distribution {
+            assert!(count.abs_diff(expected) < expected / 20);
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_winning_ticket_in_range(
+            randomness in proptest::collection::vec(proptest::num::u8::ANY, 64),
+            total_tickets in 1u32..=10_000,
+        ) {
+            let mut bytes = [0u8; 64];
+            bytes.copy_from_slice(&randomness);
+            let ticket = calculate_winning_ticket(&bytes, total_tickets).unwrap();
+            proptest::prop_assert!(ticket < total_tickets);
+        }
+
+        #[test]
+        fn prop_mapping_is_monotonic_in_first_lane(
+            a in proptest::num::u64::ANY,
+            b in proptest::num::u64::ANY,
+            total_tickets in 1u32..=10_000,
+        ) {
+            // Accepted first lanes preserve ordering
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            let threshold = (total_tickets as u64).wrapping_neg() % total_tickets as u64;
+            let accepted = |v: u64| (v as u128 * total_tickets as u128) as u64 >= threshold;
+            proptest::prop_assume!(accepted(low) && accepted(high));
+            
+            let mut lo_bytes = [0u8; 64];
+            lo_bytes[0..8].copy_from_slice(&low.to_le_bytes());
+            let mut hi_bytes = [0u8; 64];
+            hi_bytes[0..8].copy_from_slice(&high.to_le_bytes());
+            
+            let lo_ticket = calculate_winning_ticket(&lo_bytes, total_tickets).unwrap();
+            let hi_ticket = calculate_winning_ticket(&hi_bytes, total_tickets).unwrap();
+            proptest::prop_assert!(lo_ticket <= hi_ticket);
+        }
     }
 
     #[test]
@@ -322,26 +346,11 @@ mod tests {
 
     fn create_test_raffle() -> RaffleAccount {
         RaffleAccount {
-            id: 1,
-            creator: Pubkey::new_unique(),
-            title: "Test Raffle".to_string(),
-            description: "Test Description".to_string(),
-            prize_amount: 1_000_000_000, // 1 SOL
-            ticket_price: 10_000_000,    // 0.01 SOL
-            max_tickets: 100,
             tickets_sold: 50,
-            start_time: 0,
-            end_time: 86400,
+            ticket_revenue: 500_000_000,
             status: RaffleStatus::Drawing,
-            escrow_bump: 255,
-            raffle_bump: 254,
             vrf_request: Some(Pubkey::new_unique()),
-            winner: None,
-            winning_ticket: None,
-            vrf_proof: None,
-            created_at: 0,
-            drawn_at: None,
-            distributed_at: None,
+            ..test_utils::create_test_raffle()
         }
     }
 }
\ No newline at end of file
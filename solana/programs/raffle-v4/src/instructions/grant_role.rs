@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Grant a role (authority only)
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        constraint = authority.key() == program_state.authority @ RaffleError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<GrantRole>,
+    role: Role,
+    member: Pubkey,
+) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    
+    require!(
+        !role_registry.has_role(role, &member),
+        RaffleError::RoleAlreadyGranted
+    );
+    
+    let members = role_registry.members_mut(role);
+    require!(
+        members.len() < RoleRegistry::MAX_MEMBERS_PER_ROLE,
+        RaffleError::RoleMemberLimitReached
+    );
+    members.push(member);
+    
+    msg!(
+        "Role granted - Role: {:?}, Member: {}, By: {}",
+        role,
+        member,
+        ctx.accounts.authority.key()
+    );
+    
+    emit!(RoleGrantedEvent {
+        role,
+        member,
+        granted_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_respects_member_limit() {
+        let mut registry = RoleRegistry {
+            pausers: Vec::new(),
+            fee_managers: Vec::new(),
+            oracles: Vec::new(),
+            bump: 255,
+        };
+        
+        for _ in 0..RoleRegistry::MAX_MEMBERS_PER_ROLE {
+            registry.members_mut(Role::Pauser).push(Pubkey::new_unique());
+        }
+        
+        assert_eq!(registry.members(Role::Pauser).len(), RoleRegistry::MAX_MEMBERS_PER_ROLE);
+        assert!(registry.members(Role::FeeManager).is_empty());
+    }
+}
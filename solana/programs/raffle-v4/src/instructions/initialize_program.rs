@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
-use crate::error::*;
 
 /// Initialize the program with global configuration
 #[derive(Accounts)]
@@ -35,8 +34,11 @@ pub fn handler(
     program_state.fee_rate = fee_rate;
     program_state.total_raffles = 0;
     program_state.total_volume = 0;
-    program_state.is_paused = false;
+    program_state.pause_flags = 0;
     program_state.bump = ctx.bumps.program_state;
+    program_state.pending_authority = None;
+    program_state.config_delay = ProgramState::DEFAULT_CONFIG_DELAY;
+    program_state.pending_config = None;
     
     msg!(
         "Program initialized - Authority: {}, Treasury: {}, Fee Rate: {}bp",
@@ -45,28 +47,42 @@ pub fn handler(
         program_state.fee_rate
     );
     
+    emit!(ProgramInitializedEvent {
+        authority: program_state.authority,
+        treasury: program_state.treasury,
+        fee_rate: program_state.fee_rate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use anchor_lang::prelude::*;
     use solana_program_test::*;
     use solana_sdk::{
         account::Account as SolanaAccount,
         signature::{Keypair, Signer},
-        transaction::Transaction,
     };
 
+    /// Run the program in-process; program-test needs accounts that outlive the call
+    fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+        let accounts = Box::leak(Box::new(accounts.to_vec()));
+        crate::entry(program_id, accounts, data)
+    }
+
     #[tokio::test]
     async fn test_initialize_program_success() {
         let program_id = crate::ID;
-        let mut program_test = ProgramTest::new("raffle_v4", program_id, None);
+        let mut program_test = ProgramTest::new("raffle_v4", program_id, processor!(process_instruction));
         
         // Create test accounts
         let authority = Keypair::new();
-        let treasury = Keypair::new();
         
         // Add some SOL to authority for rent
         program_test.add_account(
@@ -80,7 +96,7 @@ mod tests {
             },
         );
         
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_banks_client, _payer, _recent_blockhash) = program_test.start().await;
         
         // Test valid initialization
         let fee_rate = 300u16; // 3%
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Create the role registry (authority only)
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleRegistry::LEN,
+        seeds = [b"roles"],
+        bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == program_state.authority @ RaffleError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeRoles>) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    role_registry.pausers = Vec::new();
+    role_registry.fee_managers = Vec::new();
+    role_registry.oracles = Vec::new();
+    role_registry.bump = ctx.bumps.role_registry;
+    
+    msg!("Role registry initialized by {}", ctx.accounts.authority.key());
+    
+    Ok(())
+}
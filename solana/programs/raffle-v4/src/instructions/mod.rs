@@ -3,17 +3,51 @@ pub mod create_raffle;
 pub mod purchase_ticket;
 pub mod request_winner_selection;
 pub mod fulfill_winner_selection;
+pub mod bind_winner;
+pub mod commit_winner_selection;
+pub mod reveal_winner_selection;
+pub mod rerequest_winner_selection;
+pub mod cancel_stalled_raffle;
+pub mod cancel_undersold_raffle;
 pub mod distribute_prize;
 pub mod cancel_raffle;
 pub mod claim_refund;
+pub mod refund_batch;
+pub mod close_ticket;
+pub mod close_raffle;
 pub mod update_program_config;
+pub mod accept_authority;
+pub mod apply_config_change;
+pub mod cancel_config_change;
+pub mod set_raffle_frozen;
+pub mod initialize_roles;
+pub mod grant_role;
+pub mod revoke_role;
 
+// Every instruction module exports a `handler`; lib.rs calls them by path
+#[allow(ambiguous_glob_reexports)]
 pub use initialize_program::*;
 pub use create_raffle::*;
 pub use purchase_ticket::*;
 pub use request_winner_selection::*;
 pub use fulfill_winner_selection::*;
+pub use bind_winner::*;
+pub use commit_winner_selection::*;
+pub use reveal_winner_selection::*;
+pub use rerequest_winner_selection::*;
+pub use cancel_stalled_raffle::*;
+pub use cancel_undersold_raffle::*;
 pub use distribute_prize::*;
 pub use cancel_raffle::*;
 pub use claim_refund::*;
-pub use update_program_config::*;
\ No newline at end of file
+pub use refund_batch::*;
+pub use close_ticket::*;
+pub use close_raffle::*;
+pub use update_program_config::*;
+pub use accept_authority::*;
+pub use apply_config_change::*;
+pub use cancel_config_change::*;
+pub use set_raffle_frozen::*;
+pub use initialize_roles::*;
+pub use grant_role::*;
+pub use revoke_role::*;
\ No newline at end of file
@@ -3,7 +3,7 @@ use anchor_lang::system_program;
 use crate::state::*;
 use crate::error::*;
 
-/// Purchase a ticket for an active raffle
+/// Purchase one or more consecutive tickets for an active raffle
 #[derive(Accounts)]
 #[instruction(raffle_id: u64)]
 pub struct PurchaseTicket<'info> {
@@ -31,6 +31,19 @@ pub struct PurchaseTicket<'info> {
     )]
     pub ticket_account: Account<'info, TicketAccount>,
     
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + UserTicketsAccount::LEN,
+        seeds = [
+            b"user_tickets",
+            raffle_id.to_le_bytes().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub user_tickets: Account<'info, UserTicketsAccount>,
+    
     #[account(
         mut,
         seeds = [
@@ -57,13 +70,23 @@ pub struct PurchaseTicket<'info> {
 pub fn handler(
     ctx: Context<PurchaseTicket>,
     raffle_id: u64,
+    quantity: u32,
 ) -> Result<()> {
     let program_state = &ctx.accounts.program_state;
     let raffle_account = &mut ctx.accounts.raffle_account;
     let buyer = &ctx.accounts.buyer;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_PURCHASE), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    // Validate requested quantity
+    require!(
+        quantity > 0 && quantity <= TicketAccount::MAX_TICKETS_PER_PURCHASE,
+        RaffleError::InvalidTicketQuantity
+    );
     
     // Validate raffle state
     require!(
@@ -73,15 +96,22 @@ pub fn handler(
     
     let current_time = Clock::get()?.unix_timestamp;
     
+    // Check if ticket sales have opened
+    require!(
+        raffle_account.has_started(current_time),
+        RaffleError::RaffleNotStarted
+    );
+    
     // Check if raffle has ended
     require!(
         !raffle_account.has_ended(current_time),
         RaffleError::RaffleEnded
     );
     
-    // Check if raffle is full
+    // Check if enough tickets remain for the whole range
+    let end_ticket = raffle_account.tickets_sold.checked_add_error(quantity)?;
     require!(
-        raffle_account.tickets_sold < raffle_account.max_tickets,
+        end_ticket <= raffle_account.max_tickets,
         RaffleError::RaffleFull
     );
     
@@ -91,9 +121,24 @@ pub fn handler(
         RaffleError::CreatorCannotPurchase
     );
     
+    // Enforce per-wallet limits
+    let user_tickets = &mut ctx.accounts.user_tickets;
+    require!(
+        raffle_account.within_wallet_limit(user_tickets.tickets_owned, quantity),
+        RaffleError::UserTicketLimitExceeded
+    );
+    require!(
+        raffle_account.max_tickets_per_wallet.is_none()
+            || user_tickets.ticket_starts.len() < UserTicketsAccount::MAX_PURCHASES,
+        RaffleError::UserPurchaseLimitExceeded
+    );
+    
+    // Price each ticket in the range against the active tiers
+    let total_price = raffle_account.purchase_cost(quantity, current_time)?;
+    
     // Check buyer has sufficient funds
     require!(
-        buyer.lamports() >= raffle_account.ticket_price,
+        buyer.lamports() >= total_price,
         RaffleError::InsufficientFundsForTicket
     );
     
@@ -106,37 +151,82 @@ pub fn handler(
                 to: ctx.accounts.escrow_account.to_account_info(),
             },
         ),
-        raffle_account.ticket_price,
+        total_price,
     )?;
     
-    // Initialize ticket account
+    // Initialize ticket account covering [tickets_sold, tickets_sold + quantity)
     let ticket_account = &mut ctx.accounts.ticket_account;
     ticket_account.raffle_id = raffle_id;
     ticket_account.owner = buyer.key();
     ticket_account.ticket_number = raffle_account.tickets_sold;
+    ticket_account.quantity = quantity;
+    ticket_account.amount_paid = total_price;
+    ticket_account.refunded = false;
     ticket_account.purchase_time = current_time;
     ticket_account.bump = ctx.bumps.ticket_account;
     
+    // Record the purchase against the buyer's wallet
+    if user_tickets.ticket_starts.is_empty() {
+        user_tickets.raffle_id = raffle_id;
+        user_tickets.owner = buyer.key();
+        user_tickets.bump = ctx.bumps.user_tickets;
+    }
+    user_tickets.tickets_owned = user_tickets.tickets_owned.checked_add_error(quantity)?;
+    user_tickets.ticket_starts.push(ticket_account.ticket_number);
+    
+    // Raffles without a wallet limit grow the purchase list past its initial
+    // capacity; the buyer funds the extra rent
+    let user_tickets_info = user_tickets.to_account_info();
+    let required_len = 8 + UserTicketsAccount::space_for(user_tickets.ticket_starts.len());
+    if required_len > user_tickets_info.data_len() {
+        let rent_top_up = Rent::get()?
+            .minimum_balance(required_len)
+            .saturating_sub(user_tickets_info.lamports());
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: buyer.to_account_info(),
+                    to: user_tickets_info.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+        user_tickets_info.realloc(required_len, false)?;
+    }
+    
     // Update raffle state
-    raffle_account.tickets_sold = raffle_account.tickets_sold
-        .checked_add(1)
-        .ok_or(RaffleError::ArithmeticOverflow)?;
+    raffle_account.tickets_sold = end_ticket;
+    raffle_account.ticket_revenue = raffle_account.ticket_revenue.checked_add_error(total_price)?;
+    raffle_account.open_ticket_accounts = raffle_account.open_ticket_accounts.checked_add_error(1)?;
     
     msg!(
-        "Ticket purchased - Raffle ID: {}, Buyer: {}, Ticket #: {}, Price: {} lamports",
+        "Tickets purchased - Raffle ID: {}, Buyer: {}, Tickets #: {}-{}, Price: {} lamports",
         raffle_id,
         buyer.key(),
         ticket_account.ticket_number,
-        raffle_account.ticket_price
+        end_ticket - 1,
+        total_price
     );
     
+    emit!(TicketPurchasedEvent {
+        raffle_id,
+        buyer: buyer.key(),
+        ticket_account: ticket_account.key(),
+        first_ticket: ticket_account.ticket_number,
+        quantity,
+        amount: total_price,
+        tickets_sold: raffle_account.tickets_sold,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use anchor_lang::prelude::*;
+    use crate::test_utils::create_test_raffle;
 
     #[test]
     fn test_purchase_ticket_validation() {
@@ -199,6 +289,60 @@ mod tests {
         assert_eq!(next_ticket_number, 1);
     }
 
+    #[test]
+    fn test_batch_ticket_range_assignment() {
+        let mut raffle = create_test_raffle();
+        raffle.tickets_sold = 7;
+        let quantity = 5u32;
+        
+        // Range starts at the current tickets_sold count
+        let ticket = TicketAccount {
+            raffle_id: raffle.id,
+            owner: Pubkey::new_unique(),
+            ticket_number: raffle.tickets_sold,
+            quantity,
+            amount_paid: 50_000_000,
+            refunded: false,
+            purchase_time: 0,
+            bump: 255,
+        };
+        raffle.tickets_sold = raffle.tickets_sold.checked_add(quantity).unwrap();
+        
+        assert_eq!(ticket.ticket_number, 7);
+        assert_eq!(ticket.end_ticket(), raffle.tickets_sold);
+        assert!(ticket.contains(11));
+        assert!(!ticket.contains(12));
+        
+        // Price scales with quantity
+        let total_price = raffle.purchase_cost(quantity, 0).unwrap();
+        assert_eq!(total_price, 50_000_000);
+    }
+
+    #[test]
+    fn test_wallet_limit_enforcement() {
+        let mut raffle = create_test_raffle();
+        raffle.max_tickets_per_wallet = Some(10);
+        
+        let mut user_tickets = UserTicketsAccount {
+            raffle_id: raffle.id,
+            owner: Pubkey::new_unique(),
+            tickets_owned: 0,
+            ticket_starts: Vec::new(),
+            bump: 255,
+        };
+        
+        // First purchase of 6 is allowed
+        assert!(raffle.within_wallet_limit(user_tickets.tickets_owned, 6));
+        user_tickets.tickets_owned += 6;
+        user_tickets.ticket_starts.push(0);
+        
+        // Second purchase of 5 would exceed the limit, 4 fits exactly
+        assert!(!raffle.within_wallet_limit(user_tickets.tickets_owned, 5));
+        assert!(raffle.within_wallet_limit(user_tickets.tickets_owned, 4));
+        
+        assert_eq!(user_tickets.ticket_starts, vec![0]);
+    }
+
     #[test]
     fn test_ticket_pda_generation() {
         let raffle_id = 12345u64;
@@ -271,28 +415,4 @@ mod tests {
         assert!(insufficient_balance < ticket_price);
     }
 
-    fn create_test_raffle() -> RaffleAccount {
-        RaffleAccount {
-            id: 1,
-            creator: Pubkey::new_unique(),
-            title: "Test Raffle".to_string(),
-            description: "Test Description".to_string(),
-            prize_amount: 1_000_000_000, // 1 SOL
-            ticket_price: 10_000_000,    // 0.01 SOL
-            max_tickets: 100,
-            tickets_sold: 0,
-            start_time: 0,
-            end_time: 86400, // 24 hours
-            status: RaffleStatus::Active,
-            escrow_bump: 255,
-            raffle_bump: 254,
-            vrf_request: None,
-            winner: None,
-            winning_ticket: None,
-            vrf_proof: None,
-            created_at: 0,
-            drawn_at: None,
-            distributed_at: None,
-        }
-    }
 }
\ No newline at end of file
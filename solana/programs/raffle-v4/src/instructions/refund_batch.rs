@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use settlement::escrow::transfer_lamports_from_pda;
+
+/// Refund many ticket ranges of a cancelled raffle in one transaction
+/// 
+/// Ticket accounts are passed as remaining accounts in
+/// `(ticket_account, owner, user_tickets)` triples, where `user_tickets` is
+/// the owner's per-wallet tracking account; it is closed to the owner with
+/// their last ticket account. Anyone may crank this instruction.
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct RefundBatch<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"escrow",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.escrow_bump,
+        constraint = escrow_account.raffle_id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub escrow_account: Account<'info, EscrowAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+    raffle_id: u64,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    let escrow_info = ctx.accounts.escrow_account.to_account_info();
+    let remaining = ctx.remaining_accounts;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_REFUND), RaffleError::ProgramPaused);
+    
+    // Validate raffle state - can only refund from cancelled raffles
+    require!(
+        raffle_account.status == RaffleStatus::Cancelled,
+        RaffleError::CannotRefundActiveRaffle
+    );
+    
+    require!(
+        !remaining.is_empty() && remaining.chunks_exact(3).remainder().is_empty(),
+        RaffleError::InvalidRemainingAccounts
+    );
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut total_refunded = 0u64;
+    
+    for accounts in remaining.chunks(3) {
+        let ticket_info = &accounts[0];
+        let owner_info = &accounts[1];
+        let user_tickets_info = &accounts[2];
+        
+        let mut ticket_account = Account::<TicketAccount>::try_from(ticket_info)?;
+        
+        // Verify the ticket PDA belongs to this raffle
+        let expected = Pubkey::create_program_address(
+            &[
+                b"ticket",
+                raffle_id.to_le_bytes().as_ref(),
+                ticket_account.ticket_number.to_le_bytes().as_ref(),
+                &[ticket_account.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| RaffleError::InvalidPDA)?;
+        require!(ticket_info.key() == expected, RaffleError::InvalidPDA);
+        require!(ticket_account.raffle_id == raffle_id, RaffleError::InvalidPDA);
+        require!(
+            owner_info.key() == ticket_account.owner,
+            RaffleError::UnauthorizedTicketOwner
+        );
+        require!(!ticket_account.refunded, RaffleError::TicketAlreadyRefunded);
+        require!(
+            ticket_account.end_ticket() <= raffle_account.tickets_sold,
+            RaffleError::InvalidTicketNumber
+        );
+        
+        let refund_amount = ticket_account.amount_paid;
+        require!(
+            escrow_info.lamports() >= refund_amount,
+            RaffleError::InsufficientFunds
+        );
+        
+        transfer_lamports_from_pda(&escrow_info, owner_info, refund_amount)?;
+        
+        ticket_account.refunded = true;
+        raffle_account.tickets_refunded = raffle_account
+            .tickets_refunded
+            .checked_add_error(ticket_account.quantity)?;
+        raffle_account.open_ticket_accounts = raffle_account.open_ticket_accounts.checked_sub_error(1)?;
+        total_refunded = total_refunded.checked_add_error(refund_amount)?;
+        
+        emit!(RefundClaimedEvent {
+            raffle_id,
+            ticket_holder: owner_info.key(),
+            ticket_account: ticket_info.key(),
+            first_ticket: ticket_account.ticket_number,
+            quantity: ticket_account.quantity,
+            amount: refund_amount,
+            timestamp: current_time,
+        });
+        
+        // Return the ticket rent to its owner
+        ticket_account.close(owner_info.clone())?;
+        
+        // Verify and release the owner's tracking account
+        let mut user_tickets = Account::<UserTicketsAccount>::try_from(user_tickets_info)?;
+        let expected = Pubkey::create_program_address(
+            &[
+                b"user_tickets",
+                raffle_id.to_le_bytes().as_ref(),
+                owner_info.key().as_ref(),
+                &[user_tickets.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| RaffleError::InvalidPDA)?;
+        require!(user_tickets_info.key() == expected, RaffleError::InvalidPDA);
+        
+        // The wallet's tracking account goes back with its last ticket account
+        if user_tickets.release(ticket_account.ticket_number) {
+            user_tickets.close(owner_info.clone())?;
+        } else {
+            user_tickets.exit(ctx.program_id)?;
+        }
+    }
+    
+    msg!(
+        "Batch refund - Raffle ID: {}, Tickets: {}, Amount: {} lamports, Caller: {}",
+        raffle_id,
+        remaining.len() / 3,
+        total_refunded,
+        ctx.accounts.caller.key()
+    );
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_pda_matches_bump() {
+        let raffle_id = 7u64;
+        let ticket_number = 12u32;
+        let (pda, bump) = TicketAccount::find_pda(raffle_id, ticket_number);
+        
+        let expected = Pubkey::create_program_address(
+            &[
+                b"ticket",
+                raffle_id.to_le_bytes().as_ref(),
+                ticket_number.to_le_bytes().as_ref(),
+                &[bump],
+            ],
+            &crate::ID,
+        )
+        .unwrap();
+        assert_eq!(pda, expected);
+    }
+}
@@ -1,9 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use orao_solana_vrf::program::OraoVrf;
-use orao_solana_vrf::state::NetworkState;
-use orao_solana_vrf::cpi::accounts::Request;
-use orao_solana_vrf::cpi::request;
+use randomness::orao::{randomness_account_address, OraoRandomness, OraoRequest};
+use randomness::RandomnessSource;
 use crate::state::*;
 use crate::error::*;
 
@@ -28,23 +25,19 @@ pub struct RequestWinnerSelection<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
     
-    /// CHECK: This is the VRF request account that will be created by ORAO VRF
+    /// CHECK: ORAO randomness account for the request seed, verified in handler
     #[account(
         mut,
-        seeds = [
-            b"vrf_request",
-            raffle_id.to_le_bytes().as_ref()
-        ],
-        bump,
         constraint = vrf_request.data_is_empty() @ RaffleError::VRFAlreadyRequested
     )]
     pub vrf_request: AccountInfo<'info>,
     
     /// CHECK: ORAO VRF network state account
     #[account(
-        constraint = network_state.key() == orao_solana_vrf::network_state_account_address() @ RaffleError::VRFOracleMismatch
+        mut,
+        constraint = network_state.key() == randomness::orao::network_state_address() @ RaffleError::VRFOracleMismatch
     )]
-    pub network_state: Account<'info, NetworkState>,
+    pub network_state: AccountInfo<'info>,
     
     /// CHECK: ORAO VRF treasury account for fee payment
     #[account(mut)]
@@ -53,7 +46,9 @@ pub struct RequestWinnerSelection<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     
-    pub vrf_program: Program<'info, OraoVrf>,
+    /// CHECK: ORAO VRF program
+    #[account(address = randomness::orao::ID @ RaffleError::VRFOracleMismatch)]
+    pub vrf_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -65,7 +60,10 @@ pub fn handler(
     let raffle_account = &mut ctx.accounts.raffle_account;
     
     // Check if program is paused
-    require!(!program_state.is_paused, RaffleError::ProgramPaused);
+    require!(!program_state.is_paused(ProgramState::PAUSE_DRAW), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
     
     // Validate raffle state
     require!(
@@ -81,12 +79,24 @@ pub fn handler(
         RaffleError::RaffleNotEnded
     );
     
-    // Check if raffle can be drawn (has tickets sold)
+    // Check if raffle has tickets sold
     require!(
-        raffle_account.can_be_drawn(current_time),
+        raffle_account.tickets_sold > 0,
         RaffleError::NoTicketsSold
     );
     
+    // Check if the minimum ticket threshold was reached
+    require!(
+        raffle_account.can_be_drawn(current_time),
+        RaffleError::MinimumTicketsNotMet
+    );
+    
+    // This instruction only serves ORAO raffles
+    require!(
+        raffle_account.randomness_provider == RandomnessProvider::Orao,
+        RaffleError::WrongRandomnessProvider
+    );
+    
     // Check if VRF request already exists
     require!(
         raffle_account.vrf_request.is_none(),
@@ -94,28 +104,34 @@ pub fn handler(
     );
     
     // Generate seed for VRF request using raffle data
-    let seed = generate_vrf_seed(raffle_account, current_time)?;
-    
-    // Request randomness from ORAO VRF
-    let request_accounts = Request {
-        payer: ctx.accounts.payer.to_account_info(),
-        network_state: ctx.accounts.network_state.to_account_info(),
-        treasury: ctx.accounts.treasury.to_account_info(),
-        request: ctx.accounts.vrf_request.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-    };
+    let seed = generate_vrf_seed(raffle_account)?;
     
-    let request_ctx = CpiContext::new(
-        ctx.accounts.vrf_program.to_account_info(),
-        request_accounts,
+    // The request account must be the one ORAO derives from this seed
+    require_keys_eq!(
+        ctx.accounts.vrf_request.key(),
+        randomness_account_address(&seed),
+        RaffleError::VRFOracleMismatch
     );
     
-    // Make VRF request with generated seed
-    request(request_ctx, seed)?;
+    // Request randomness from ORAO VRF
+    let request = OraoRandomness::request(
+        OraoRequest {
+            payer: ctx.accounts.payer.to_account_info(),
+            network_state: ctx.accounts.network_state.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            request: ctx.accounts.vrf_request.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            orao_program: ctx.accounts.vrf_program.to_account_info(),
+        },
+        seed,
+    )?;
     
     // Update raffle state
     raffle_account.status = RaffleStatus::Drawing;
     raffle_account.vrf_request = Some(ctx.accounts.vrf_request.key());
+    raffle_account.randomness_request = Some(request);
+    raffle_account.draw_requested_at = Some(current_time);
+    raffle_account.draw_attempts = 1;
     
     msg!(
         "VRF winner selection requested - Raffle ID: {}, VRF Request: {}, Tickets Sold: {}",
@@ -124,11 +140,23 @@ pub fn handler(
         raffle_account.tickets_sold
     );
     
+    emit!(DrawRequestedEvent {
+        raffle_id,
+        vrf_request: ctx.accounts.vrf_request.key(),
+        payer: ctx.accounts.payer.key(),
+        tickets_sold: raffle_account.tickets_sold,
+        attempt: raffle_account.draw_attempts,
+        timestamp: current_time,
+    });
+    
     Ok(())
 }
 
 /// Generate a deterministic seed for VRF request
-fn generate_vrf_seed(raffle_account: &RaffleAccount, current_time: i64) -> Result<[u8; 32]> {
+/// 
+/// Only state fixed before the request is sent goes in, so callers can
+/// derive the ORAO request account without guessing when it will land.
+pub fn generate_vrf_seed(raffle_account: &RaffleAccount) -> Result<[u8; 32]> {
     let mut seed_data = Vec::new();
     
     // Include raffle-specific data for uniqueness
@@ -136,7 +164,8 @@ fn generate_vrf_seed(raffle_account: &RaffleAccount, current_time: i64) -> Resul
     seed_data.extend_from_slice(raffle_account.creator.as_ref());
     seed_data.extend_from_slice(&raffle_account.tickets_sold.to_le_bytes());
     seed_data.extend_from_slice(&raffle_account.end_time.to_le_bytes());
-    seed_data.extend_from_slice(&current_time.to_le_bytes());
+    seed_data.extend_from_slice(&raffle_account.created_at.to_le_bytes());
+    seed_data.push(raffle_account.draw_attempts);
     
     // Hash the seed data to create a 32-byte seed
     use anchor_lang::solana_program::hash::hash;
@@ -148,6 +177,7 @@ fn generate_vrf_seed(raffle_account: &RaffleAccount, current_time: i64) -> Resul
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils;
 
     #[test]
     fn test_raffle_can_be_drawn() {
@@ -178,24 +208,29 @@ mod tests {
 
     #[test]
     fn test_vrf_seed_generation() {
-        let current_time = 1640995200i64; // Fixed timestamp for testing
         let raffle = create_test_raffle();
         
         // Generate seed
-        let seed = generate_vrf_seed(&raffle, current_time).unwrap();
+        let seed = generate_vrf_seed(&raffle).unwrap();
         
         // Seed should be 32 bytes
         assert_eq!(seed.len(), 32);
         
         // Same inputs should produce same seed
-        let seed2 = generate_vrf_seed(&raffle, current_time).unwrap();
+        let seed2 = generate_vrf_seed(&raffle).unwrap();
         assert_eq!(seed, seed2);
         
         // Different inputs should produce different seeds
         let mut different_raffle = raffle.clone();
         different_raffle.id = 999;
-        let seed3 = generate_vrf_seed(&different_raffle, current_time).unwrap();
+        let seed3 = generate_vrf_seed(&different_raffle).unwrap();
         assert_ne!(seed, seed3);
+        
+        // A retry produces a fresh seed
+        let mut retried_raffle = raffle.clone();
+        retried_raffle.draw_attempts = 1;
+        let seed4 = generate_vrf_seed(&retried_raffle).unwrap();
+        assert_ne!(seed, seed4);
     }
 
     #[test]
@@ -256,61 +291,46 @@ mod tests {
     }
 
     #[test]
-    fn test_vrf_request_pda() {
-        let raffle_id = 12345u64;
-        let program_id = crate::ID;
+    fn test_vrf_request_address() {
+        let raffle = create_test_raffle();
+        let seed = generate_vrf_seed(&raffle).unwrap();
         
-        let (pda, bump) = Pubkey::find_program_address(
-            &[b"vrf_request", raffle_id.to_le_bytes().as_ref()],
-            &program_id,
-        );
+        // Request account is derived by ORAO from the seed
+        let address = randomness_account_address(&seed);
+        assert_ne!(address, Pubkey::default());
+        assert_eq!(address, randomness_account_address(&seed));
         
-        assert!(bump > 0);
-        assert_ne!(pda, Pubkey::default());
+        // A different seed yields a different request account
+        let retried = RaffleAccount { draw_attempts: 1, ..raffle };
+        let other_seed = generate_vrf_seed(&retried).unwrap();
+        assert_ne!(address, randomness_account_address(&other_seed));
     }
 
     #[test]
-    fn test_deterministic_seed_components() {
+    fn test_seed_ignores_clock() {
         let raffle = create_test_raffle();
-        let current_time = 1640995200i64;
-        
-        // Verify seed components are included
-        let mut expected_data = Vec::new();
-        expected_data.extend_from_slice(&raffle.id.to_le_bytes());
-        expected_data.extend_from_slice(raffle.creator.as_ref());
-        expected_data.extend_from_slice(&raffle.tickets_sold.to_le_bytes());
-        expected_data.extend_from_slice(&raffle.end_time.to_le_bytes());
-        expected_data.extend_from_slice(&current_time.to_le_bytes());
+        let seed = generate_vrf_seed(&raffle).unwrap();
         
-        // Should include raffle ID
-        assert!(expected_data.contains(&raffle.id.to_le_bytes()[0]));
+        // Nothing that changes between send and landing feeds the seed
+        let later = RaffleAccount {
+            draw_requested_at: Some(raffle.end_time + 3600),
+            ..raffle.clone()
+        };
+        assert_eq!(generate_vrf_seed(&later).unwrap(), seed);
         
-        // Should include tickets sold
-        assert!(expected_data.contains(&raffle.tickets_sold.to_le_bytes()[0]));
+        // A raffle re-created under the same id draws with a new seed
+        let recreated = RaffleAccount {
+            created_at: raffle.created_at + 1,
+            ..raffle
+        };
+        assert_ne!(generate_vrf_seed(&recreated).unwrap(), seed);
     }
 
     fn create_test_raffle() -> RaffleAccount {
         RaffleAccount {
-            id: 1,
-            creator: Pubkey::new_unique(),
-            title: "Test Raffle".to_string(),
-            description: "Test Description".to_string(),
-            prize_amount: 1_000_000_000, // 1 SOL
-            ticket_price: 10_000_000,    // 0.01 SOL
-            max_tickets: 100,
             tickets_sold: 10,
-            start_time: 0,
-            end_time: 86400, // 24 hours
-            status: RaffleStatus::Active,
-            escrow_bump: 255,
-            raffle_bump: 254,
-            vrf_request: None,
-            winner: None,
-            winning_ticket: None,
-            vrf_proof: None,
-            created_at: 0,
-            drawn_at: None,
-            distributed_at: None,
+            ticket_revenue: 100_000_000,
+            ..test_utils::create_test_raffle()
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use randomness::orao::{randomness_account_address, OraoRandomness, OraoRequest};
+use randomness::RandomnessSource;
+use crate::state::*;
+use crate::error::*;
+use super::request_winner_selection::generate_vrf_seed;
+
+/// Re-request VRF for a raffle whose previous request timed out (permissionless)
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct RerequestWinnerSelection<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: ORAO randomness account of the timed-out request, verified in handler
+    pub previous_request: AccountInfo<'info>,
+    
+    /// CHECK: ORAO randomness account for the new request seed, verified in handler
+    #[account(
+        mut,
+        constraint = vrf_request.data_is_empty() @ RaffleError::VRFAlreadyRequested
+    )]
+    pub vrf_request: AccountInfo<'info>,
+    
+    /// CHECK: ORAO VRF network state account
+    #[account(
+        mut,
+        constraint = network_state.key() == randomness::orao::network_state_address() @ RaffleError::VRFOracleMismatch
+    )]
+    pub network_state: AccountInfo<'info>,
+    
+    /// CHECK: ORAO VRF treasury account for fee payment
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: ORAO VRF program
+    #[account(address = randomness::orao::ID @ RaffleError::VRFOracleMismatch)]
+    pub vrf_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RerequestWinnerSelection>,
+    raffle_id: u64,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_DRAW), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Previous request must have timed out without fulfillment
+    require!(
+        raffle_account.is_draw_stalled(current_time),
+        RaffleError::DrawNotStalled
+    );
+    
+    // Only ORAO requests can be re-issued; other providers recover by cancelling
+    require!(
+        raffle_account.randomness_provider == RandomnessProvider::Orao,
+        RaffleError::WrongRandomnessProvider
+    );
+    
+    // A request ORAO has already answered must be drawn, not replaced
+    require_orao_pending(raffle_account, &ctx.accounts.previous_request)?;
+    
+    // Bound the number of retries; after that the raffle can only be cancelled
+    require!(
+        raffle_account.draw_attempts < RaffleAccount::MAX_DRAW_ATTEMPTS,
+        RaffleError::MaxDrawAttemptsReached
+    );
+    
+    // Generate a fresh seed (includes the attempt counter)
+    let seed = generate_vrf_seed(raffle_account)?;
+    
+    // The request account must be the one ORAO derives from this seed
+    require_keys_eq!(
+        ctx.accounts.vrf_request.key(),
+        randomness_account_address(&seed),
+        RaffleError::VRFOracleMismatch
+    );
+    
+    // Request randomness from ORAO VRF
+    let request = OraoRandomness::request(
+        OraoRequest {
+            payer: ctx.accounts.payer.to_account_info(),
+            network_state: ctx.accounts.network_state.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            request: ctx.accounts.vrf_request.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            orao_program: ctx.accounts.vrf_program.to_account_info(),
+        },
+        seed,
+    )?;
+    
+    // Point the raffle at the new request; the stale one is ignored from now on
+    let previous_request = raffle_account.vrf_request;
+    raffle_account.vrf_request = Some(ctx.accounts.vrf_request.key());
+    raffle_account.randomness_request = Some(request);
+    raffle_account.draw_requested_at = Some(current_time);
+    raffle_account.draw_attempts = raffle_account.draw_attempts
+        .checked_add(1)
+        .ok_or(RaffleError::ArithmeticOverflow)?;
+    
+    msg!(
+        "VRF winner selection re-requested - Raffle ID: {}, Previous Request: {:?}, VRF Request: {}, Attempt: {}",
+        raffle_id,
+        previous_request,
+        ctx.accounts.vrf_request.key(),
+        raffle_account.draw_attempts
+    );
+    
+    emit!(DrawRequestedEvent {
+        raffle_id,
+        vrf_request: ctx.accounts.vrf_request.key(),
+        payer: ctx.accounts.payer.key(),
+        tickets_sold: raffle_account.tickets_sold,
+        attempt: raffle_account.draw_attempts,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+/// Require `vrf_request` to be the raffle's recorded ORAO request and still
+/// unfulfilled, so a draw only counts as stalled while ORAO has not answered
+pub fn require_orao_pending(raffle_account: &RaffleAccount, vrf_request: &AccountInfo) -> Result<()> {
+    require!(
+        raffle_account.vrf_request == Some(vrf_request.key()),
+        RaffleError::VRFOracleMismatch
+    );
+    
+    let request = raffle_account.randomness_request.ok_or(RaffleError::VRFNotRequested)?;
+    let randomness = OraoRandomness::load(vrf_request, &request)?;
+    require!(!randomness.is_fulfilled(), RaffleError::RandomnessAlreadyFulfilled);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_rerequest_requires_timeout() {
+        let mut raffle = create_test_raffle();
+        raffle.draw_requested_at = Some(1000);
+        
+        // Before the timeout elapses
+        assert!(!raffle.is_draw_stalled(1000 + raffle.draw_timeout - 1));
+        
+        // After the timeout elapses
+        assert!(raffle.is_draw_stalled(1000 + raffle.draw_timeout));
+    }
+
+    #[test]
+    fn test_rerequest_attempt_limit() {
+        let mut raffle = create_test_raffle();
+        
+        raffle.draw_attempts = 1;
+        assert!(raffle.draw_attempts < RaffleAccount::MAX_DRAW_ATTEMPTS);
+        
+        raffle.draw_attempts = RaffleAccount::MAX_DRAW_ATTEMPTS;
+        assert!(raffle.draw_attempts >= RaffleAccount::MAX_DRAW_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_rerequest_uses_new_seed() {
+        let mut raffle = create_test_raffle();
+        
+        let first_seed = generate_vrf_seed(&raffle).unwrap();
+        raffle.draw_attempts += 1;
+        let second_seed = generate_vrf_seed(&raffle).unwrap();
+        
+        assert_ne!(first_seed, second_seed);
+        assert_ne!(
+            randomness_account_address(&first_seed),
+            randomness_account_address(&second_seed)
+        );
+    }
+
+    fn create_test_raffle() -> RaffleAccount {
+        RaffleAccount {
+            tickets_sold: 10,
+            ticket_revenue: 100_000_000,
+            status: RaffleStatus::Drawing,
+            vrf_request: Some(Pubkey::new_unique()),
+            draw_requested_at: Some(86400),
+            draw_attempts: 1,
+            ..test_utils::create_test_raffle()
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use randomness::signed_oracle::SignedOracleRandomness;
+use randomness::slot_hash::SlotHashRandomness;
+use randomness::RandomnessSource;
+use crate::state::*;
+use crate::error::*;
+use super::fulfill_winner_selection::calculate_winning_ticket;
+
+/// Draw the winning ticket for a signed-oracle or slot-hash raffle (permissionless)
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct RevealWinnerSelection<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    /// CHECK: Instructions sysvar holding the oracle's Ed25519 signature instruction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    /// CHECK: Slot hashes sysvar
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<RevealWinnerSelection>,
+    raffle_id: u64,
+    secret: Option<[u8; 32]>,
+) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    
+    // Check if program is paused
+    require!(!program_state.is_paused(ProgramState::PAUSE_DRAW), RaffleError::ProgramPaused);
+    
+    // Check if raffle is frozen
+    require!(!raffle_account.frozen, RaffleError::RaffleFrozen);
+    
+    // Validate raffle state
+    require!(
+        raffle_account.status == RaffleStatus::Drawing,
+        RaffleError::InvalidRaffleState
+    );
+    
+    // Check if the winning ticket has already been drawn
+    require!(
+        raffle_account.winning_ticket.is_none() && raffle_account.winner.is_none(),
+        RaffleError::WinnerAlreadySelected
+    );
+    
+    let request = raffle_account.randomness_request.ok_or(RaffleError::VRFNotRequested)?;
+    
+    // Read randomness from the raffle's provider
+    let randomness = match raffle_account.randomness_provider {
+        RandomnessProvider::SignedOracle => {
+            let signed = SignedOracleRandomness::load(&ctx.accounts.instructions, &request)?;
+            require!(
+                ctx.accounts.role_registry.has_role(Role::Oracle, &signed.oracle),
+                RaffleError::UnknownOracle
+            );
+            signed.read_64()
+        }
+        RandomnessProvider::SlotHash => {
+            let secret = secret.ok_or(RaffleError::SecretRequired)?;
+            SlotHashRandomness::reveal(&ctx.accounts.slot_hashes, &request, &secret)?.read_64()
+        }
+        RandomnessProvider::Orao => return err!(RaffleError::WrongRandomnessProvider),
+    }
+    .ok_or(RaffleError::RandomnessNotFulfilled)?;
+    
+    // Calculate winning ticket number from randomness
+    let winning_ticket_number = calculate_winning_ticket(&randomness, raffle_account.tickets_sold)?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Record the draw; the winner is bound separately via bind_winner
+    raffle_account.winning_ticket = Some(winning_ticket_number);
+    raffle_account.vrf_proof = Some(randomness);
+    raffle_account.drawn_at = Some(current_time);
+    
+    msg!(
+        "Winning ticket drawn - Raffle ID: {}, Provider: {:?}, Winning Ticket: {}",
+        raffle_id,
+        raffle_account.randomness_provider,
+        winning_ticket_number
+    );
+    
+    emit!(WinningTicketDrawnEvent {
+        raffle_id,
+        vrf_request: raffle_account.key(),
+        winning_ticket: winning_ticket_number,
+        tickets_sold: raffle_account.tickets_sold,
+        randomness,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oracle_role_required() {
+        let oracle = Pubkey::new_unique();
+        let mut registry = RoleRegistry {
+            pausers: Vec::new(),
+            fee_managers: Vec::new(),
+            oracles: Vec::new(),
+            bump: 255,
+        };
+        assert!(!registry.has_role(Role::Oracle, &oracle));
+        
+        registry.members_mut(Role::Oracle).push(oracle);
+        assert!(registry.has_role(Role::Oracle, &oracle));
+        assert!(!registry.has_role(Role::Pauser, &oracle));
+    }
+
+    #[test]
+    fn test_revealed_randomness_picks_sold_ticket() {
+        let request = RandomnessRequest {
+            seed: [1u8; 32],
+            commitment: randomness::slot_hash::commitment(&[2u8; 32]),
+            slot: 100,
+        };
+        let randomness = randomness::expand_64(&[&request.seed, &[2u8; 32], &[3u8; 32]]);
+        
+        let winning_ticket = calculate_winning_ticket(&randomness, 25).unwrap();
+        assert!(winning_ticket < 25);
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Revoke a role (authority only)
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        seeds = [b"program_state"],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        constraint = authority.key() == program_state.authority @ RaffleError::UnauthorizedAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<RevokeRole>,
+    role: Role,
+    member: Pubkey,
+) -> Result<()> {
+    let role_registry = &mut ctx.accounts.role_registry;
+    
+    let members = role_registry.members_mut(role);
+    let index = members
+        .iter()
+        .position(|key| *key == member)
+        .ok_or(RaffleError::RoleNotGranted)?;
+    members.remove(index);
+    
+    msg!(
+        "Role revoked - Role: {:?}, Member: {}, By: {}",
+        role,
+        member,
+        ctx.accounts.authority.key()
+    );
+    
+    emit!(RoleRevokedEvent {
+        role,
+        member,
+        revoked_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
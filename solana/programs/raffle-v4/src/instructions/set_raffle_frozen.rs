@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+/// Freeze or unfreeze a single raffle (pauser only)
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct SetRaffleFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"raffle",
+            raffle_id.to_le_bytes().as_ref()
+        ],
+        bump = raffle_account.raffle_bump,
+        constraint = raffle_account.id == raffle_id @ RaffleError::InvalidPDA
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+    
+    #[account(
+        seeds = [b"roles"],
+        bump = role_registry.bump
+    )]
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    #[account(
+        constraint = role_registry.has_role(Role::Pauser, &pauser.key()) @ RaffleError::MissingRole
+    )]
+    pub pauser: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetRaffleFrozen>,
+    raffle_id: u64,
+    frozen: bool,
+) -> Result<()> {
+    let raffle_account = &mut ctx.accounts.raffle_account;
+    raffle_account.frozen = frozen;
+    
+    msg!(
+        "Raffle {} - ID: {}, Pauser: {}",
+        if frozen { "frozen" } else { "unfrozen" },
+        raffle_id,
+        ctx.accounts.pauser.key()
+    );
+    
+    emit!(RaffleFreezeUpdatedEvent {
+        raffle_id,
+        pauser: ctx.accounts.pauser.key(),
+        frozen,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
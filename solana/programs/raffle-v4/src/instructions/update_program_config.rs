@@ -3,22 +3,32 @@ use crate::state::*;
 use crate::error::*;
 
 /// Update program configuration (authority only)
+/// 
+/// Fee rate, treasury and delay changes are queued behind `config_delay`;
+/// authority changes must be accepted by the new authority.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct UpdateConfigParams {
-    /// New fee rate (optional)
+    /// New fee rate (optional, timelocked)
     pub new_fee_rate: Option<u16>,
     
-    /// New treasury address (optional)
+    /// New treasury address (optional, timelocked)
     pub new_treasury: Option<Pubkey>,
     
-    /// New authority (optional)
+    /// Proposed new authority (optional, must be accepted)
     pub new_authority: Option<Pubkey>,
     
-    /// New pause state (optional)
-    pub new_pause_state: Option<bool>,
+    /// New pause bitmask (optional, immediate; see `ProgramState::PAUSE_*`)
+    pub new_pause_flags: Option<u8>,
+    
+    /// New config delay in seconds (optional, timelocked)
+    pub new_config_delay: Option<i64>,
 }
 
 /// Update global program configuration
+/// 
+/// Each change is authorized by role: fee managers queue fee/treasury/delay
+/// changes, pausers set pause flags and the program authority proposes a
+/// new authority.
 #[derive(Accounts)]
 pub struct UpdateProgramConfig<'info> {
     #[account(
@@ -29,9 +39,12 @@ pub struct UpdateProgramConfig<'info> {
     pub program_state: Account<'info, ProgramState>,
     
     #[account(
-        constraint = authority.key() == program_state.authority @ RaffleError::UnauthorizedAuthority
+        seeds = [b"roles"],
+        bump = role_registry.bump
     )]
-    pub authority: Signer<'info>,
+    pub role_registry: Account<'info, RoleRegistry>,
+    
+    pub caller: Signer<'info>,
 }
 
 pub fn handler(
@@ -39,52 +52,110 @@ pub fn handler(
     params: UpdateConfigParams,
 ) -> Result<()> {
     let program_state = &mut ctx.accounts.program_state;
+    let role_registry = &ctx.accounts.role_registry;
+    let caller = ctx.accounts.caller.key();
+    let current_time = Clock::get()?.unix_timestamp;
     let mut changes_made = Vec::new();
     
-    // Update fee rate if provided
-    if let Some(new_fee_rate) = params.new_fee_rate {
-        // Validate new fee rate
-        ProgramState::validate_fee_rate(new_fee_rate)?;
+    // Queue fee rate, treasury and delay changes
+    if params.new_fee_rate.is_some()
+        || params.new_treasury.is_some()
+        || params.new_config_delay.is_some()
+    {
+        require!(
+            role_registry.has_role(Role::FeeManager, &caller),
+            RaffleError::MissingRole
+        );
+        require!(
+            program_state.pending_config.is_none(),
+            RaffleError::ConfigChangeAlreadyQueued
+        );
         
-        let old_fee_rate = program_state.fee_rate;
-        program_state.fee_rate = new_fee_rate;
+        if let Some(new_fee_rate) = params.new_fee_rate {
+            ProgramState::validate_fee_rate(new_fee_rate)?;
+            changes_made.push(format!("Fee rate: {} -> {} (queued)", program_state.fee_rate, new_fee_rate));
+        }
         
-        changes_made.push(format!("Fee rate: {} -> {}", old_fee_rate, new_fee_rate));
-    }
-    
-    // Update treasury if provided
-    if let Some(new_treasury) = params.new_treasury {
-        let old_treasury = program_state.treasury;
-        program_state.treasury = new_treasury;
+        if let Some(new_treasury) = params.new_treasury {
+            changes_made.push(format!("Treasury: {} -> {} (queued)", program_state.treasury, new_treasury));
+        }
         
-        changes_made.push(format!("Treasury: {} -> {}", old_treasury, new_treasury));
+        if let Some(new_config_delay) = params.new_config_delay {
+            ProgramState::validate_config_delay(new_config_delay)?;
+            changes_made.push(format!("Config delay: {} -> {} (queued)", program_state.config_delay, new_config_delay));
+        }
+        
+        let change = PendingConfigChange {
+            fee_rate: params.new_fee_rate,
+            treasury: params.new_treasury,
+            config_delay: params.new_config_delay,
+            eta: current_time.checked_add_error(program_state.config_delay)?,
+        };
+        program_state.pending_config = Some(change);
+        
+        emit!(ConfigChangeQueuedEvent {
+            queued_by: caller,
+            change,
+            timestamp: current_time,
+        });
     }
     
-    // Update pause state if provided
-    if let Some(new_pause_state) = params.new_pause_state {
-        let old_pause_state = program_state.is_paused;
-        program_state.is_paused = new_pause_state;
+    // Update pause flags if provided
+    if let Some(new_pause_flags) = params.new_pause_flags {
+        require!(
+            role_registry.has_role(Role::Pauser, &caller),
+            RaffleError::MissingRole
+        );
+        ProgramState::validate_pause_flags(new_pause_flags)?;
+        
+        let old_pause_flags = program_state.pause_flags;
+        program_state.pause_flags = new_pause_flags;
         
         changes_made.push(format!(
-            "Pause state: {} -> {}",
-            old_pause_state,
-            new_pause_state
+            "Pause flags: {:#07b} -> {:#07b}",
+            old_pause_flags,
+            new_pause_flags
         ));
+        
+        emit!(ProgramConfigUpdatedEvent {
+            updated_by: caller,
+            old_fee_rate: program_state.fee_rate,
+            new_fee_rate: program_state.fee_rate,
+            old_treasury: program_state.treasury,
+            new_treasury: program_state.treasury,
+            old_authority: program_state.authority,
+            new_authority: program_state.authority,
+            pause_flags: program_state.pause_flags,
+            timestamp: current_time,
+        });
     }
     
-    // Update authority last (if provided)
+    // Propose authority last (if provided); proposing the current authority cancels
     if let Some(new_authority) = params.new_authority {
-        let old_authority = program_state.authority;
-        program_state.authority = new_authority;
+        require!(
+            caller == program_state.authority,
+            RaffleError::UnauthorizedAuthority
+        );
+        program_state.pending_authority = if new_authority == program_state.authority {
+            None
+        } else {
+            Some(new_authority)
+        };
+        
+        changes_made.push(format!("Authority: {} -> {} (pending acceptance)", program_state.authority, new_authority));
         
-        changes_made.push(format!("Authority: {} -> {}", old_authority, new_authority));
+        emit!(AuthorityTransferProposedEvent {
+            authority: caller,
+            pending_authority: program_state.pending_authority,
+            timestamp: current_time,
+        });
     }
     
     // Log all changes made
     if !changes_made.is_empty() {
         msg!(
             "Program configuration updated by {}: {}",
-            ctx.accounts.authority.key(),
+            caller,
             changes_made.join(", ")
         );
     } else {
@@ -97,60 +168,7 @@ pub fn handler(
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_update_fee_rate() {
-        let mut program_state = create_test_program_state();
-        let original_fee_rate = program_state.fee_rate;
-        
-        // Valid fee rate update
-        let new_fee_rate = 500u16; // 5%
-        program_state.fee_rate = new_fee_rate;
-        
-        assert_eq!(program_state.fee_rate, new_fee_rate);
-        assert_ne!(program_state.fee_rate, original_fee_rate);
-    }
-
-    #[test]
-    fn test_update_treasury() {
-        let mut program_state = create_test_program_state();
-        let original_treasury = program_state.treasury;
-        let new_treasury = Pubkey::new_unique();
-        
-        program_state.treasury = new_treasury;
-        
-        assert_eq!(program_state.treasury, new_treasury);
-        assert_ne!(program_state.treasury, original_treasury);
-    }
-
-    #[test]
-    fn test_update_authority() {
-        let mut program_state = create_test_program_state();
-        let original_authority = program_state.authority;
-        let new_authority = Pubkey::new_unique();
-        
-        program_state.authority = new_authority;
-        
-        assert_eq!(program_state.authority, new_authority);
-        assert_ne!(program_state.authority, original_authority);
-    }
-
-    #[test]
-    fn test_update_pause_state() {
-        let mut program_state = create_test_program_state();
-        
-        // Initially not paused
-        program_state.is_paused = false;
-        assert!(!program_state.is_paused);
-        
-        // Pause the program
-        program_state.is_paused = true;
-        assert!(program_state.is_paused);
-        
-        // Unpause the program
-        program_state.is_paused = false;
-        assert!(!program_state.is_paused);
-    }
+    use crate::test_utils::create_test_program_state;
 
     #[test]
     fn test_fee_rate_validation() {
@@ -166,116 +184,6 @@ mod tests {
         assert!(ProgramState::validate_fee_rate(5000).is_err()); // 50%
     }
 
-    #[test]
-    fn test_multiple_updates() {
-        let mut program_state = create_test_program_state();
-        
-        let original_fee_rate = program_state.fee_rate;
-        let original_treasury = program_state.treasury;
-        let original_authority = program_state.authority;
-        let original_pause_state = program_state.is_paused;
-        
-        // Update multiple fields
-        let new_fee_rate = 750u16; // 7.5%
-        let new_treasury = Pubkey::new_unique();
-        let new_authority = Pubkey::new_unique();
-        let new_pause_state = !original_pause_state;
-        
-        program_state.fee_rate = new_fee_rate;
-        program_state.treasury = new_treasury;
-        program_state.authority = new_authority;
-        program_state.is_paused = new_pause_state;
-        
-        // Verify all changes
-        assert_eq!(program_state.fee_rate, new_fee_rate);
-        assert_eq!(program_state.treasury, new_treasury);
-        assert_eq!(program_state.authority, new_authority);
-        assert_eq!(program_state.is_paused, new_pause_state);
-        
-        // Verify changes from original
-        assert_ne!(program_state.fee_rate, original_fee_rate);
-        assert_ne!(program_state.treasury, original_treasury);
-        assert_ne!(program_state.authority, original_authority);
-        assert_ne!(program_state.is_paused, original_pause_state);
-    }
-
-    #[test]
-    fn test_partial_updates() {
-        let mut program_state = create_test_program_state();
-        
-        let original_fee_rate = program_state.fee_rate;
-        let original_treasury = program_state.treasury;
-        let original_authority = program_state.authority;
-        let original_pause_state = program_state.is_paused;
-        
-        // Update only fee rate
-        let new_fee_rate = 200u16; // 2%
-        program_state.fee_rate = new_fee_rate;
-        
-        // Verify only fee rate changed
-        assert_eq!(program_state.fee_rate, new_fee_rate);
-        assert_eq!(program_state.treasury, original_treasury);
-        assert_eq!(program_state.authority, original_authority);
-        assert_eq!(program_state.is_paused, original_pause_state);
-    }
-
-    #[test]
-    fn test_update_config_params() {
-        // Test with all parameters
-        let all_params = UpdateConfigParams {
-            new_fee_rate: Some(400),
-            new_treasury: Some(Pubkey::new_unique()),
-            new_authority: Some(Pubkey::new_unique()),
-            new_pause_state: Some(true),
-        };
-        
-        assert!(all_params.new_fee_rate.is_some());
-        assert!(all_params.new_treasury.is_some());
-        assert!(all_params.new_authority.is_some());
-        assert!(all_params.new_pause_state.is_some());
-        
-        // Test with partial parameters
-        let partial_params = UpdateConfigParams {
-            new_fee_rate: Some(300),
-            new_treasury: None,
-            new_authority: None,
-            new_pause_state: Some(false),
-        };
-        
-        assert!(partial_params.new_fee_rate.is_some());
-        assert!(partial_params.new_treasury.is_none());
-        assert!(partial_params.new_authority.is_none());
-        assert!(partial_params.new_pause_state.is_some());
-        
-        // Test with no parameters
-        let no_params = UpdateConfigParams {
-            new_fee_rate: None,
-            new_treasury: None,
-            new_authority: None,
-            new_pause_state: None,
-        };
-        
-        assert!(no_params.new_fee_rate.is_none());
-        assert!(no_params.new_treasury.is_none());
-        assert!(no_params.new_authority.is_none());
-        assert!(no_params.new_pause_state.is_none());
-    }
-
-    #[test]
-    fn test_authority_validation() {
-        let authority_key = Pubkey::new_unique();
-        let unauthorized_key = Pubkey::new_unique();
-        
-        let program_state = ProgramState {
-            authority: authority_key,
-            ..create_test_program_state()
-        };
-        
-        // Only the authority should be authorized
-        assert_eq!(program_state.authority, authority_key);
-        assert_ne!(program_state.authority, unauthorized_key);
-    }
-
     #[test]
     fn test_program_state_invariants() {
         let program_state = create_test_program_state();
@@ -284,8 +192,8 @@ mod tests {
         assert!(program_state.fee_rate <= 1000); // Max 10%
         assert_ne!(program_state.authority, Pubkey::default());
         assert_ne!(program_state.treasury, Pubkey::default());
-        assert!(program_state.total_raffles >= 0);
-        assert!(program_state.total_volume >= 0);
+        assert_eq!(program_state.total_raffles, 0);
+        assert_eq!(program_state.total_volume, 0);
     }
 
     #[test]
@@ -306,26 +214,39 @@ mod tests {
         let mut program_state = create_test_program_state();
         
         // Test normal operation
-        program_state.is_paused = false;
-        assert!(!program_state.is_paused);
+        program_state.pause_flags = 0;
+        assert!(!program_state.is_paused(ProgramState::PAUSE_CREATE));
         
-        // Test paused state
-        program_state.is_paused = true;
-        assert!(program_state.is_paused);
+        // Default pause blocks new activity only
+        program_state.pause_flags = ProgramState::PAUSE_DEFAULT;
+        assert!(program_state.is_paused(ProgramState::PAUSE_CREATE));
+        assert!(program_state.is_paused(ProgramState::PAUSE_PURCHASE));
+        assert!(program_state.is_paused(ProgramState::PAUSE_DRAW));
         
-        // When paused, other operations should be blocked
-        // This would be tested in actual instruction handlers
+        // Withdraw-type paths stay open unless paused explicitly
+        assert!(!program_state.is_paused(ProgramState::PAUSE_DISTRIBUTE));
+        assert!(!program_state.is_paused(ProgramState::PAUSE_REFUND));
+        
+        program_state.pause_flags = ProgramState::PAUSE_ALL;
+        assert!(program_state.is_paused(ProgramState::PAUSE_REFUND));
     }
 
-    fn create_test_program_state() -> ProgramState {
-        ProgramState {
-            authority: Pubkey::new_unique(),
-            treasury: Pubkey::new_unique(),
-            fee_rate: 300, // 3%
-            total_raffles: 0,
-            total_volume: 0,
-            is_paused: false,
-            bump: 255,
-        }
+    #[test]
+    fn test_pause_flags_validation() {
+        assert!(ProgramState::validate_pause_flags(0).is_ok());
+        assert!(ProgramState::validate_pause_flags(ProgramState::PAUSE_DEFAULT).is_ok());
+        assert!(ProgramState::validate_pause_flags(ProgramState::PAUSE_ALL).is_ok());
+        assert!(ProgramState::validate_pause_flags(1 << 5).is_err());
+        assert!(ProgramState::validate_pause_flags(u8::MAX).is_err());
     }
+
+    #[test]
+    fn test_config_delay_validation() {
+        assert!(ProgramState::validate_config_delay(0).is_ok());
+        assert!(ProgramState::validate_config_delay(ProgramState::DEFAULT_CONFIG_DELAY).is_ok());
+        assert!(ProgramState::validate_config_delay(ProgramState::MAX_CONFIG_DELAY).is_ok());
+        assert!(ProgramState::validate_config_delay(-1).is_err());
+        assert!(ProgramState::validate_config_delay(ProgramState::MAX_CONFIG_DELAY + 1).is_err());
+    }
+
 }
\ No newline at end of file
@@ -3,13 +3,14 @@ use anchor_lang::prelude::*;
 pub mod error;
 pub mod instructions;
 pub mod state;
-pub mod utils;
 
-use error::*;
+#[cfg(test)]
+mod test_utils;
+
 use instructions::*;
 use state::*;
 
-declare_id!("RaffLEv4HuBv4phYZ7n3RCqEGmEhyPUX9HKE8nGhLfHJKE");
+declare_id!("RafL4vkqKx7bQzmMVfTgKbDdSTPxqxhuFGq2URaYgC9");
 
 /// Raffle v4 Program - Decentralized VRF-based raffles with escrow PDAs
 #[program]
@@ -60,7 +61,24 @@ pub mod raffle_v4 {
         ctx: Context<PurchaseTicket>,
         raffle_id: u64,
     ) -> Result<()> {
-        instructions::purchase_ticket::handler(ctx, raffle_id)
+        instructions::purchase_ticket::handler(ctx, raffle_id, 1)
+    }
+
+    /// Purchase a contiguous range of tickets in a single account
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle to purchase tickets for
+    /// * `quantity` - Number of tickets to purchase (1-100)
+    /// 
+    /// # Returns
+    /// A ticket account covering `[tickets_sold, tickets_sold + quantity)`
+    pub fn purchase_tickets(
+        ctx: Context<PurchaseTicket>,
+        raffle_id: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::purchase_ticket::handler(ctx, raffle_id, quantity)
     }
 
     /// Request VRF-based winner selection for a raffle
@@ -70,9 +88,10 @@ pub mod raffle_v4 {
     /// * `raffle_id` - ID of the raffle to select winner for
     /// 
     /// # Requirements
+    /// - Raffle must use the ORAO randomness provider
     /// - Raffle must be in Active state
     /// - Raffle must have ended (current time > end_time) OR be full
-    /// - At least one ticket must be sold
+    /// - At least `min_tickets` tickets must be sold
     pub fn request_winner_selection(
         ctx: Context<RequestWinnerSelection>,
         raffle_id: u64,
@@ -80,21 +99,127 @@ pub mod raffle_v4 {
         instructions::request_winner_selection::handler(ctx, raffle_id)
     }
 
-    /// Fulfill VRF request and determine the winner
+    /// Fulfill VRF request and draw the winning ticket number on-chain
     /// 
     /// # Arguments
     /// * `ctx` - Program context with accounts
     /// * `raffle_id` - ID of the raffle
-    /// * `winning_ticket_number` - The calculated winning ticket number
     /// 
     /// # Note
-    /// This instruction is called after VRF randomness is available
+    /// This instruction is permissionless and is called after VRF randomness
+    /// is available. The winner is bound afterwards with `bind_winner`.
     pub fn fulfill_winner_selection(
         ctx: Context<FulfillWinnerSelection>,
         raffle_id: u64,
-        winning_ticket_number: u32,
     ) -> Result<()> {
-        instructions::fulfill_winner_selection::handler(ctx, raffle_id, winning_ticket_number)
+        instructions::fulfill_winner_selection::handler(ctx, raffle_id)
+    }
+
+    /// Bind the winner using the ticket account containing the drawn number
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle
+    /// 
+    /// # Requirements
+    /// - Winning ticket number must have been drawn
+    /// - Ticket account range must contain the winning ticket number
+    pub fn bind_winner(
+        ctx: Context<BindWinner>,
+        raffle_id: u64,
+    ) -> Result<()> {
+        instructions::bind_winner::handler(ctx, raffle_id)
+    }
+
+    /// Open the draw for a signed-oracle or slot-hash raffle
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle
+    /// * `commitment` - Hash of the creator's secret (slot-hash raffles only)
+    /// 
+    /// # Requirements
+    /// - Raffle must use the signed-oracle or slot-hash provider
+    /// - Raffle must be in Active state, ended, with `min_tickets` sold
+    /// - Slot-hash raffles must be committed by the creator
+    pub fn commit_winner_selection(
+        ctx: Context<CommitWinnerSelection>,
+        raffle_id: u64,
+        commitment: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::commit_winner_selection::handler(ctx, raffle_id, commitment)
+    }
+
+    /// Draw the winning ticket for a signed-oracle or slot-hash raffle
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle
+    /// * `secret` - Committed secret (slot-hash raffles only)
+    /// 
+    /// # Requirements
+    /// - Raffle must be in Drawing state without a drawn ticket
+    /// - Signed-oracle: an Ed25519 instruction in the same transaction must
+    ///   carry a signature over the draw seed by a key holding the oracle role
+    /// - Slot-hash: the secret must match the commitment, revealed after the
+    ///   commit slot and while it is still in the slot hashes sysvar
+    pub fn reveal_winner_selection(
+        ctx: Context<RevealWinnerSelection>,
+        raffle_id: u64,
+        secret: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::reveal_winner_selection::handler(ctx, raffle_id, secret)
+    }
+
+    /// Re-request VRF randomness for a raffle whose draw timed out
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the stalled raffle
+    /// 
+    /// # Requirements
+    /// - Raffle must be in Drawing state without a drawn ticket
+    /// - The draw timeout must have elapsed since the last request
+    /// - The previous ORAO request must still be unfulfilled
+    /// - Fewer than `MAX_DRAW_ATTEMPTS` requests made so far
+    pub fn rerequest_winner_selection(
+        ctx: Context<RerequestWinnerSelection>,
+        raffle_id: u64,
+    ) -> Result<()> {
+        instructions::rerequest_winner_selection::handler(ctx, raffle_id)
+    }
+
+    /// Cancel a raffle whose draw timed out so refunds can be claimed
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the stalled raffle
+    /// 
+    /// # Requirements
+    /// - Raffle must be in Drawing state without a drawn ticket
+    /// - The draw timeout must have elapsed since the last request
+    /// - For ORAO draws, the pending request must still be unfulfilled
+    pub fn cancel_stalled_raffle(
+        ctx: Context<CancelStalledRaffle>,
+        raffle_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_stalled_raffle::handler(ctx, raffle_id)
+    }
+
+    /// Cancel a raffle that ended below its minimum ticket threshold
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the undersold raffle
+    /// 
+    /// # Requirements
+    /// - Raffle must be in Active state and have ended
+    /// - Tickets sold must be below `min_tickets`
+    pub fn cancel_undersold_raffle(
+        ctx: Context<CancelUndersoldRaffle>,
+        raffle_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_undersold_raffle::handler(ctx, raffle_id)
     }
 
     /// Distribute prize to the winner and fees to treasury
@@ -131,12 +256,12 @@ pub mod raffle_v4 {
         instructions::cancel_raffle::handler(ctx, raffle_id)
     }
 
-    /// Claim refund for a ticket from a cancelled raffle
+    /// Claim refund for a ticket range from a cancelled raffle
     /// 
     /// # Arguments
     /// * `ctx` - Program context with accounts
     /// * `raffle_id` - ID of the cancelled raffle
-    /// * `ticket_number` - Number of the ticket to refund
+    /// * `ticket_number` - First ticket number of the range to refund
     /// 
     /// # Requirements
     /// - Raffle must be in Cancelled state
@@ -150,6 +275,58 @@ pub mod raffle_v4 {
         instructions::claim_refund::handler(ctx, raffle_id, ticket_number)
     }
 
+    /// Refund many ticket ranges from a cancelled raffle (permissionless)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context; remaining accounts are (ticket, owner) pairs
+    /// * `raffle_id` - ID of the cancelled raffle
+    /// 
+    /// # Requirements
+    /// - Raffle must be in Cancelled state
+    /// - Each owner account must match its ticket's owner
+    /// - Tickets must not have been refunded yet
+    pub fn refund_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+        raffle_id: u64,
+    ) -> Result<()> {
+        instructions::refund_batch::handler(ctx, raffle_id)
+    }
+
+    /// Close a ticket account of a settled raffle (permissionless)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle
+    /// * `ticket_number` - First ticket number of the range to close
+    /// 
+    /// # Requirements
+    /// - Prize must be distributed or every ticket refunded
+    /// - Rent is returned to the ticket owner
+    pub fn close_ticket(
+        ctx: Context<CloseTicket>,
+        raffle_id: u64,
+        ticket_number: u32,
+    ) -> Result<()> {
+        instructions::close_ticket::handler(ctx, raffle_id, ticket_number)
+    }
+
+    /// Close a settled raffle and its escrow (permissionless)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle to close
+    /// 
+    /// # Requirements
+    /// - Prize must be distributed or every ticket refunded
+    /// - All ticket accounts must already be closed
+    /// - Rent goes to the creator, escrow surplus to the treasury
+    pub fn close_raffle(
+        ctx: Context<CloseRaffle>,
+        raffle_id: u64,
+    ) -> Result<()> {
+        instructions::close_raffle::handler(ctx, raffle_id)
+    }
+
     /// Update program configuration (admin only)
     /// 
     /// # Arguments
@@ -157,11 +334,111 @@ pub mod raffle_v4 {
     /// * `params` - Configuration update parameters
     /// 
     /// # Requirements
-    /// - Only program authority can update configuration
+    /// - Fee rate, treasury and delay changes require the fee manager role and
+    ///   are queued behind the config delay
+    /// - Pause flag changes require the pauser role
+    /// - Authority changes require the program authority and must be accepted
     pub fn update_program_config(
         ctx: Context<UpdateProgramConfig>,
         params: UpdateConfigParams,
     ) -> Result<()> {
         instructions::update_program_config::handler(ctx, params)
     }
+
+    /// Accept a proposed authority transfer
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// 
+    /// # Requirements
+    /// - Signer must be the pending authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Apply a queued configuration change (permissionless)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// 
+    /// # Requirements
+    /// - A change must be queued and its delay elapsed
+    pub fn apply_config_change(ctx: Context<ApplyConfigChange>) -> Result<()> {
+        instructions::apply_config_change::handler(ctx)
+    }
+
+    /// Cancel a queued configuration change (fee manager only)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// 
+    /// # Requirements
+    /// - Signer must hold the fee manager role
+    /// - A change must be queued
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::cancel_config_change::handler(ctx)
+    }
+
+    /// Freeze or unfreeze a single raffle (pauser only)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `raffle_id` - ID of the raffle
+    /// * `frozen` - Whether the raffle should be frozen
+    /// 
+    /// # Requirements
+    /// - Signer must hold the pauser role
+    /// - Frozen raffles block everything except refund claims
+    pub fn set_raffle_frozen(
+        ctx: Context<SetRaffleFrozen>,
+        raffle_id: u64,
+        frozen: bool,
+    ) -> Result<()> {
+        instructions::set_raffle_frozen::handler(ctx, raffle_id, frozen)
+    }
+
+    /// Create the role registry (admin only)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// 
+    /// # Requirements
+    /// - Only program authority can initialize roles
+    pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+        instructions::initialize_roles::handler(ctx)
+    }
+
+    /// Grant a role to a key (admin only)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `role` - Role to grant
+    /// * `member` - Key receiving the role
+    /// 
+    /// # Requirements
+    /// - Only program authority can grant roles
+    pub fn grant_role(
+        ctx: Context<GrantRole>,
+        role: Role,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::grant_role::handler(ctx, role, member)
+    }
+
+    /// Revoke a role from a key (admin only)
+    /// 
+    /// # Arguments
+    /// * `ctx` - Program context with accounts
+    /// * `role` - Role to revoke
+    /// * `member` - Key losing the role
+    /// 
+    /// # Requirements
+    /// - Only program authority can revoke roles
+    pub fn revoke_role(
+        ctx: Context<RevokeRole>,
+        role: Role,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::revoke_role::handler(ctx, role, member)
+    }
 }
\ No newline at end of file
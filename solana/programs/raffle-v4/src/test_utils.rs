@@ -0,0 +1,63 @@
+//! Fixtures shared by the unit tests of every instruction module
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Active, unsold ORAO raffle: 1 SOL prize, 0.01 SOL tickets, 100 max, one-day run
+///
+/// Modules override the fields they care about with struct update syntax.
+pub fn create_test_raffle() -> RaffleAccount {
+    RaffleAccount {
+        id: 1,
+        creator: Pubkey::new_unique(),
+        title: "Test Raffle".to_string(),
+        description: "Test Description".to_string(),
+        content: None,
+        prize_amount: 1_000_000_000, // 1 SOL
+        ticket_price: 10_000_000,    // 0.01 SOL
+        fee_rate: 300,
+        treasury: Pubkey::new_unique(),
+        price_tiers: Vec::new(),
+        max_tickets: 100,
+        min_tickets: 1,
+        tickets_sold: 0,
+        ticket_revenue: 0,
+        tickets_refunded: 0,
+        open_ticket_accounts: 0,
+        max_tickets_per_wallet: None,
+        start_time: 0,
+        end_time: 86400, // 24 hours
+        status: RaffleStatus::Active,
+        frozen: false,
+        escrow_bump: 255,
+        raffle_bump: 254,
+        vrf_request: None,
+        randomness_provider: RandomnessProvider::Orao,
+        randomness_request: None,
+        draw_timeout: 86400,
+        draw_requested_at: None,
+        draw_attempts: 0,
+        winner: None,
+        winning_ticket: None,
+        vrf_proof: None,
+        created_at: 0,
+        drawn_at: None,
+        distributed_at: None,
+    }
+}
+
+/// Unpaused program state with a 3% fee and the default config delay
+pub fn create_test_program_state() -> ProgramState {
+    ProgramState {
+        authority: Pubkey::new_unique(),
+        treasury: Pubkey::new_unique(),
+        fee_rate: 300, // 3%
+        total_raffles: 0,
+        total_volume: 0,
+        pause_flags: 0,
+        bump: 255,
+        pending_authority: None,
+        config_delay: ProgramState::DEFAULT_CONFIG_DELAY,
+        pending_config: None,
+    }
+}
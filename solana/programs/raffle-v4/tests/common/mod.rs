@@ -0,0 +1,937 @@
+//! BanksClient harness shared by the raffle-v4 program test suites
+//!
+//! Boots raffle_v4 next to the mock ORAO VRF program (deployed at ORAO's
+//! program id) with a funded creator and buyers, and runs the admin setup
+//! every raffle needs: program state, role registry, role grants and the
+//! mock ORAO network.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::sync::Once;
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, system_program,
+};
+use anchor_lang::{AccountDeserialize, Event, InstructionData, ToAccountMetas};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use raffle_v4::instructions::{CreateRaffleParams, UpdateConfigParams};
+use raffle_v4::state::{
+    ProgramState, RaffleAccount, RandomnessProvider, Role, RoleRegistry, TicketAccount,
+    UserTicketsAccount,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+/// Program fee rate set at initialization, in basis points
+pub const FEE_RATE: u16 = 300;
+/// Default raffle prize
+pub const PRIZE: u64 = 1_000_000_000;
+/// Default ticket price
+pub const TICKET_PRICE: u64 = 10_000_000;
+/// Default raffle capacity
+pub const MAX_TICKETS: u32 = 100;
+/// Default raffle duration in seconds
+pub const DURATION: i64 = 86_400;
+/// Lamports charged by the mock ORAO network per request
+pub const ORAO_REQUEST_FEE: u64 = 2_000_000;
+/// Lamports preloaded into the creator and every buyer
+pub const STARTING_LAMPORTS: u64 = 100_000_000_000;
+
+// Anchor's entrypoints tie the account slice to 'info, which program-test cannot provide
+fn process_raffle(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    raffle_v4::entry(program_id, accounts, data)
+}
+
+fn process_mock_orao(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_orao::entry(program_id, accounts, data)
+}
+
+/// program-test runs programs natively, where `sol_log_data` only prints to
+/// stdout; route it through `sol_log` so emitted events reach the
+/// transaction log as `Program log: Program data: ...`
+struct EventLogStubs(Box<dyn SyscallStubs>);
+
+impl SyscallStubs for EventLogStubs {
+    fn sol_log(&self, message: &str) {
+        self.0.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.0.sol_log_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.0.sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_rent_sysvar(var_addr)
+    }
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.0.sol_memcpy(dst, src, n)
+    }
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.0.sol_memmove(dst, src, n)
+    }
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.0.sol_memcmp(s1, s2, n, result)
+    }
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.0.sol_memset(s, c, n)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.0.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.0.sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        self.0.sol_log(&format!("Program data: {}", fields.join(" ")))
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.0.sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.0.sol_get_stack_height()
+    }
+}
+
+/// Wrap program-test's syscall stubs once they are installed; every test
+/// passes through here before sending its first transaction
+fn log_event_data() {
+    static WRAP: Once = Once::new();
+    WRAP.call_once(|| {
+        let stubs = set_syscall_stubs(Box::new(NoStubs));
+        set_syscall_stubs(Box::new(EventLogStubs(stubs)));
+    });
+}
+
+/// Placeholder held only while program-test's stubs are swapped out
+struct NoStubs;
+
+impl SyscallStubs for NoStubs {}
+
+/// Anchor error code for a raffle, randomness or framework error
+pub fn code(error: impl Into<u32>) -> u32 {
+    error.into()
+}
+
+/// Custom program error carried by a failed transaction
+pub fn custom_error(error: BanksClientError) -> Option<u32> {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
+
+/// Events of type `T` emitted in a program log
+pub fn events<T: Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program log: Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(&T::DISCRIMINATOR))
+        .map(|data| T::try_from_slice(&data[8..]).unwrap())
+        .collect()
+}
+
+pub fn program_state_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"program_state"], &raffle_v4::ID).0
+}
+
+pub fn roles_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"roles"], &raffle_v4::ID).0
+}
+
+pub fn raffle_pda(raffle_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"raffle", raffle_id.to_le_bytes().as_ref()], &raffle_v4::ID).0
+}
+
+pub fn escrow_pda(raffle_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", raffle_id.to_le_bytes().as_ref()], &raffle_v4::ID).0
+}
+
+pub fn ticket_pda(raffle_id: u64, first_ticket: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"ticket",
+            raffle_id.to_le_bytes().as_ref(),
+            first_ticket.to_le_bytes().as_ref(),
+        ],
+        &raffle_v4::ID,
+    )
+    .0
+}
+
+pub fn user_tickets_pda(raffle_id: u64, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user_tickets", raffle_id.to_le_bytes().as_ref(), owner.as_ref()],
+        &raffle_v4::ID,
+    )
+    .0
+}
+
+/// Raffle_v4 and mock ORAO deployment with funded actors
+pub struct RaffleTest {
+    pub context: ProgramTestContext,
+    pub payer: Keypair,
+    /// Program authority and role admin
+    pub authority: Keypair,
+    /// Holds the fee manager role
+    pub fee_manager: Keypair,
+    /// Holds the pauser role
+    pub pauser: Keypair,
+    /// Mock ORAO fulfillment authority
+    pub oracle: Keypair,
+    pub treasury: Pubkey,
+    pub orao_treasury: Pubkey,
+    pub creator: Keypair,
+    pub buyers: Vec<Keypair>,
+    sent: HashSet<Signature>,
+}
+
+impl RaffleTest {
+    /// Boot the programs and fixtures without running any raffle setup
+    pub async fn boot(buyer_count: usize) -> Self {
+        let mut program_test =
+            ProgramTest::new("raffle_v4", raffle_v4::ID, processor!(process_raffle));
+        program_test.add_program("mock_orao", mock_orao::ID, processor!(process_mock_orao));
+
+        let authority = Keypair::new();
+        let fee_manager = Keypair::new();
+        let pauser = Keypair::new();
+        let oracle = Keypair::new();
+        let creator = Keypair::new();
+        let treasury = Pubkey::new_unique();
+        let orao_treasury = Pubkey::new_unique();
+        let buyers: Vec<Keypair> = (0..buyer_count).map(|_| Keypair::new()).collect();
+
+        for key in [
+            authority.pubkey(),
+            fee_manager.pubkey(),
+            pauser.pubkey(),
+            oracle.pubkey(),
+        ] {
+            program_test.add_account(key, system_account(10_000_000_000));
+        }
+        program_test.add_account(creator.pubkey(), system_account(STARTING_LAMPORTS));
+        for buyer in &buyers {
+            program_test.add_account(buyer.pubkey(), system_account(STARTING_LAMPORTS));
+        }
+        for key in [treasury, orao_treasury] {
+            program_test.add_account(key, system_account(Rent::default().minimum_balance(0)));
+        }
+
+        let context = program_test.start_with_context().await;
+        log_event_data();
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        Self {
+            context,
+            payer,
+            authority,
+            fee_manager,
+            pauser,
+            oracle,
+            treasury,
+            orao_treasury,
+            creator,
+            buyers,
+            sent: HashSet::new(),
+        }
+    }
+
+    /// Boot and run the full admin setup
+    pub async fn start(buyer_count: usize) -> Self {
+        let mut test = Self::boot(buyer_count).await;
+        test.setup().await;
+        test
+    }
+
+    /// Program state, roles, fee manager and pauser grants, and mock ORAO network
+    pub async fn setup(&mut self) {
+        let instructions = vec![
+            self.initialize_program_ix(FEE_RATE, self.treasury),
+            self.initialize_roles_ix(),
+            self.grant_role_ix(Role::FeeManager, self.fee_manager.pubkey()),
+            self.grant_role_ix(Role::Pauser, self.pauser.pubkey()),
+        ];
+        let authority = self.clone_keypair(&self.authority);
+        self.send(&instructions, &[&authority]).await.unwrap();
+
+        let orao = mock_orao::client::initialize(
+            self.payer.pubkey(),
+            self.orao_treasury,
+            ORAO_REQUEST_FEE,
+            vec![self.oracle.pubkey()],
+        );
+        self.send(&[orao], &[]).await.unwrap();
+    }
+
+    pub fn clone_keypair(&self, keypair: &Keypair) -> Keypair {
+        Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+    }
+
+    pub fn buyer(&self, index: usize) -> Keypair {
+        self.clone_keypair(&self.buyers[index])
+    }
+
+    // ----- transactions -----
+
+    /// Sign with the payer plus `signers` and process; identical transactions
+    /// get a fresh blockhash so they are not rejected as duplicates
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let transaction = self.sign(instructions, signers).await?;
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Process a transaction and return its program log
+    pub async fn send_with_logs(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<String>, BanksClientError> {
+        let transaction = self.sign(instructions, signers).await?;
+        let processed = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await?;
+        processed.result?;
+        Ok(processed.metadata.map(|meta| meta.log_messages).unwrap_or_default())
+    }
+
+    /// Sign with the payer plus `signers`, on a fresh blockhash if the
+    /// same transaction was already sent
+    async fn sign(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Transaction, BanksClientError> {
+        let mut keypairs: Vec<&Keypair> = vec![&self.payer];
+        for signer in signers {
+            if !keypairs.iter().any(|k| k.pubkey() == signer.pubkey()) {
+                keypairs.push(signer);
+            }
+        }
+
+        let mut blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &keypairs,
+            blockhash,
+        );
+        while self.sent.contains(&transaction.signatures[0]) {
+            blockhash = self
+                .context
+                .get_new_latest_blockhash()
+                .await
+                .expect("new blockhash");
+            transaction = Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.payer.pubkey()),
+                &keypairs,
+                blockhash,
+            );
+        }
+        self.sent.insert(transaction.signatures[0]);
+        Ok(transaction)
+    }
+
+    /// Process and assert the transaction fails with custom error `expected`
+    pub async fn expect_error(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        expected: impl Into<u32>,
+    ) {
+        let expected = expected.into();
+        let error = self
+            .send(instructions, signers)
+            .await
+            .expect_err("transaction should fail");
+        let description = format!("{:?}", error);
+        assert_eq!(
+            custom_error(error),
+            Some(expected),
+            "expected custom error {}, got {}",
+            expected,
+            description
+        );
+    }
+
+    // ----- state -----
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {} missing", address));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn exists(&mut self, address: Pubkey) -> bool {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    pub async fn program_state(&mut self) -> ProgramState {
+        self.account(program_state_pda()).await
+    }
+
+    pub async fn role_registry(&mut self) -> RoleRegistry {
+        self.account(roles_pda()).await
+    }
+
+    pub async fn raffle(&mut self, raffle_id: u64) -> RaffleAccount {
+        self.account(raffle_pda(raffle_id)).await
+    }
+
+    pub async fn ticket(&mut self, raffle_id: u64, first_ticket: u32) -> TicketAccount {
+        self.account(ticket_pda(raffle_id, first_ticket)).await
+    }
+
+    pub async fn user_tickets(&mut self, raffle_id: u64, owner: &Pubkey) -> UserTicketsAccount {
+        self.account(user_tickets_pda(raffle_id, owner)).await
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
+    // ----- clock -----
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+    }
+
+    /// Move the cluster clock forward by `seconds`
+    pub async fn warp_seconds(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    // ----- lifecycle helpers -----
+
+    /// Default ORAO raffle parameters: 1 SOL prize, 0.01 SOL tickets, one-day run
+    pub fn raffle_params(&self, raffle_id: u64) -> CreateRaffleParams {
+        CreateRaffleParams {
+            raffle_id,
+            title: format!("Raffle {}", raffle_id),
+            description: "Test raffle".to_string(),
+            content: None,
+            prize_amount: PRIZE,
+            ticket_price: TICKET_PRICE,
+            price_tiers: Vec::new(),
+            max_tickets: MAX_TICKETS,
+            min_tickets: None,
+            max_tickets_per_wallet: None,
+            start_time: None,
+            duration: DURATION,
+            draw_timeout: None,
+            randomness_provider: RandomnessProvider::Orao,
+        }
+    }
+
+    pub async fn create_raffle(&mut self, params: CreateRaffleParams) {
+        let creator = self.clone_keypair(&self.creator);
+        let instruction = self.create_raffle_ix(params);
+        self.send(&[instruction], &[&creator]).await.unwrap();
+    }
+
+    /// Buy `quantity` tickets for `buyer`, returning the first ticket number
+    pub async fn buy(&mut self, raffle_id: u64, buyer: usize, quantity: u32) -> u32 {
+        let first_ticket = self.raffle(raffle_id).await.tickets_sold;
+        let buyer = self.buyer(buyer);
+        let instruction = self.purchase_tickets_ix(raffle_id, &buyer.pubkey(), first_ticket, quantity);
+        self.send(&[instruction], &[&buyer]).await.unwrap();
+        first_ticket
+    }
+
+    /// Move past the raffle's end time
+    pub async fn end_raffle(&mut self, raffle_id: u64) {
+        let end_time = self.raffle(raffle_id).await.end_time;
+        let now = self.clock().await.unix_timestamp;
+        self.warp_seconds(end_time - now + 1).await;
+    }
+
+    /// ORAO seed the raffle's next randomness request uses
+    pub async fn draw_seed(&mut self, raffle_id: u64) -> [u8; 32] {
+        let raffle = self.raffle(raffle_id).await;
+        raffle_v4::instructions::generate_vrf_seed(&raffle).unwrap()
+    }
+
+    /// Request ORAO randomness, returning the request seed
+    pub async fn request_draw(&mut self, raffle_id: u64) -> [u8; 32] {
+        let seed = self.draw_seed(raffle_id).await;
+        let instruction = self.request_winner_selection_ix(raffle_id, &seed);
+        self.send(&[instruction], &[]).await.unwrap();
+        seed
+    }
+
+    /// Fulfill a mock ORAO request as the network's authority
+    pub async fn orao_fulfill(&mut self, seed: [u8; 32], randomness: [u8; 64]) {
+        let oracle = self.clone_keypair(&self.oracle);
+        let instruction = mock_orao::client::fulfill(oracle.pubkey(), seed, randomness);
+        self.send(&[instruction], &[&oracle]).await.unwrap();
+    }
+
+    /// First ticket of the range holding `ticket_number`, walking the
+    /// contiguous ranges from ticket 0
+    pub async fn ticket_start(&mut self, raffle_id: u64, ticket_number: u32) -> u32 {
+        let mut start = 0;
+        loop {
+            let ticket = self.ticket(raffle_id, start).await;
+            if ticket.contains(ticket_number) {
+                return start;
+            }
+            start = ticket.end_ticket();
+        }
+    }
+
+    /// End the raffle and run the ORAO draw through prize distribution
+    pub async fn draw_and_distribute(&mut self, raffle_id: u64, randomness: [u8; 64]) {
+        self.end_raffle(raffle_id).await;
+        let seed = self.request_draw(raffle_id).await;
+        self.orao_fulfill(seed, randomness).await;
+
+        let fulfill = self.fulfill_winner_selection_ix(raffle_id, &seed);
+        self.send(&[fulfill], &[]).await.unwrap();
+
+        let winning_ticket = self.raffle(raffle_id).await.winning_ticket.unwrap();
+        let start = self.ticket_start(raffle_id, winning_ticket).await;
+        let bind = self.bind_winner_ix(raffle_id, start);
+        self.send(&[bind], &[]).await.unwrap();
+
+        let raffle = self.raffle(raffle_id).await;
+        let distribute =
+            self.distribute_prize_ix(raffle_id, &raffle.winner.unwrap(), &raffle.treasury);
+        self.send(&[distribute], &[]).await.unwrap();
+    }
+
+    pub async fn set_frozen(&mut self, raffle_id: u64, frozen: bool) {
+        let pauser = self.clone_keypair(&self.pauser);
+        let instruction = self.set_raffle_frozen_ix(raffle_id, frozen);
+        self.send(&[instruction], &[&pauser]).await.unwrap();
+    }
+
+    pub async fn cancel_raffle(&mut self, raffle_id: u64) {
+        let creator = self.clone_keypair(&self.creator);
+        let instruction = self.cancel_raffle_ix(raffle_id);
+        self.send(&[instruction], &[&creator]).await.unwrap();
+    }
+
+    // ----- instruction builders -----
+
+    pub fn initialize_program_ix(&self, fee_rate: u16, treasury: Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::InitializeProgram {
+                program_state: program_state_pda(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::InitializeProgram { fee_rate, treasury }.data(),
+        }
+    }
+
+    pub fn initialize_roles_ix(&self) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::InitializeRoles {
+                role_registry: roles_pda(),
+                program_state: program_state_pda(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::InitializeRoles {}.data(),
+        }
+    }
+
+    pub fn grant_role_ix(&self, role: Role, member: Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::GrantRole {
+                role_registry: roles_pda(),
+                program_state: program_state_pda(),
+                authority: self.authority.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::GrantRole { role, member }.data(),
+        }
+    }
+
+    pub fn create_raffle_ix(&self, params: CreateRaffleParams) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CreateRaffle {
+                raffle_account: raffle_pda(params.raffle_id),
+                escrow_account: escrow_pda(params.raffle_id),
+                program_state: program_state_pda(),
+                creator: self.creator.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CreateRaffle { params }.data(),
+        }
+    }
+
+    pub fn purchase_tickets_ix(
+        &self,
+        raffle_id: u64,
+        buyer: &Pubkey,
+        first_ticket: u32,
+        quantity: u32,
+    ) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::PurchaseTicket {
+                raffle_account: raffle_pda(raffle_id),
+                ticket_account: ticket_pda(raffle_id, first_ticket),
+                user_tickets: user_tickets_pda(raffle_id, buyer),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                buyer: *buyer,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::PurchaseTickets { raffle_id, quantity }.data(),
+        }
+    }
+
+    pub fn cancel_raffle_ix(&self, raffle_id: u64) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CancelRaffle {
+                raffle_account: raffle_pda(raffle_id),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                creator: self.creator.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CancelRaffle { raffle_id }.data(),
+        }
+    }
+
+    pub fn cancel_undersold_raffle_ix(&self, raffle_id: u64) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CancelUndersoldRaffle {
+                raffle_account: raffle_pda(raffle_id),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                creator: self.creator.pubkey(),
+                caller: self.payer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CancelUndersoldRaffle { raffle_id }.data(),
+        }
+    }
+
+    pub fn set_raffle_frozen_ix(&self, raffle_id: u64, frozen: bool) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::SetRaffleFrozen {
+                raffle_account: raffle_pda(raffle_id),
+                role_registry: roles_pda(),
+                pauser: self.pauser.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::SetRaffleFrozen { raffle_id, frozen }.data(),
+        }
+    }
+
+    pub fn request_winner_selection_ix(&self, raffle_id: u64, seed: &[u8; 32]) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::RequestWinnerSelection {
+                raffle_account: raffle_pda(raffle_id),
+                program_state: program_state_pda(),
+                vrf_request: mock_orao::client::randomness_address(seed),
+                network_state: mock_orao::client::network_state_address(),
+                treasury: self.orao_treasury,
+                payer: self.payer.pubkey(),
+                vrf_program: mock_orao::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::RequestWinnerSelection { raffle_id }.data(),
+        }
+    }
+
+    /// Replace the request made with `previous_seed` by one for `seed`
+    pub fn rerequest_winner_selection_ix(
+        &self,
+        raffle_id: u64,
+        previous_seed: &[u8; 32],
+        seed: &[u8; 32],
+    ) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::RerequestWinnerSelection {
+                raffle_account: raffle_pda(raffle_id),
+                program_state: program_state_pda(),
+                previous_request: mock_orao::client::randomness_address(previous_seed),
+                vrf_request: mock_orao::client::randomness_address(seed),
+                network_state: mock_orao::client::network_state_address(),
+                treasury: self.orao_treasury,
+                payer: self.payer.pubkey(),
+                vrf_program: mock_orao::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::RerequestWinnerSelection { raffle_id }.data(),
+        }
+    }
+
+    pub fn cancel_stalled_raffle_ix(&self, raffle_id: u64, vrf_request: Option<Pubkey>) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CancelStalledRaffle {
+                raffle_account: raffle_pda(raffle_id),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                creator: self.creator.pubkey(),
+                vrf_request,
+                caller: self.payer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CancelStalledRaffle { raffle_id }.data(),
+        }
+    }
+
+    pub fn fulfill_winner_selection_ix(&self, raffle_id: u64, seed: &[u8; 32]) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::FulfillWinnerSelection {
+                raffle_account: raffle_pda(raffle_id),
+                vrf_request: mock_orao::client::randomness_address(seed),
+                program_state: program_state_pda(),
+                caller: self.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::FulfillWinnerSelection { raffle_id }.data(),
+        }
+    }
+
+    pub fn bind_winner_ix(&self, raffle_id: u64, ticket_start: u32) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::BindWinner {
+                raffle_account: raffle_pda(raffle_id),
+                program_state: program_state_pda(),
+                winning_ticket: ticket_pda(raffle_id, ticket_start),
+                caller: self.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::BindWinner { raffle_id }.data(),
+        }
+    }
+
+    pub fn distribute_prize_ix(&self, raffle_id: u64, winner: &Pubkey, treasury: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::DistributePrize {
+                raffle_account: raffle_pda(raffle_id),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                winner: *winner,
+                treasury: *treasury,
+                caller: self.payer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::DistributePrize { raffle_id }.data(),
+        }
+    }
+
+    pub fn claim_refund_ix(&self, raffle_id: u64, ticket_holder: &Pubkey, ticket_number: u32) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::ClaimRefund {
+                raffle_account: raffle_pda(raffle_id),
+                ticket_account: ticket_pda(raffle_id, ticket_number),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                ticket_holder: *ticket_holder,
+                user_tickets: user_tickets_pda(raffle_id, ticket_holder),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::ClaimRefund {
+                raffle_id,
+                ticket_number,
+            }
+            .data(),
+        }
+    }
+
+    /// Batch refund; `tickets` pairs each range's first ticket with its owner
+    pub fn refund_batch_ix(&self, raffle_id: u64, tickets: &[(u32, Pubkey)]) -> Instruction {
+        let mut accounts = raffle_v4::accounts::RefundBatch {
+            raffle_account: raffle_pda(raffle_id),
+            escrow_account: escrow_pda(raffle_id),
+            program_state: program_state_pda(),
+            caller: self.payer.pubkey(),
+        }
+        .to_account_metas(None);
+        for (ticket_number, owner) in tickets {
+            accounts.push(AccountMeta::new(ticket_pda(raffle_id, *ticket_number), false));
+            accounts.push(AccountMeta::new(*owner, false));
+            accounts.push(AccountMeta::new(user_tickets_pda(raffle_id, owner), false));
+        }
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts,
+            data: raffle_v4::instruction::RefundBatch { raffle_id }.data(),
+        }
+    }
+
+    pub fn close_ticket_ix(&self, raffle_id: u64, ticket_number: u32, owner: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CloseTicket {
+                raffle_account: raffle_pda(raffle_id),
+                ticket_account: ticket_pda(raffle_id, ticket_number),
+                owner: *owner,
+                user_tickets: user_tickets_pda(raffle_id, owner),
+                program_state: program_state_pda(),
+                caller: self.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CloseTicket {
+                raffle_id,
+                ticket_number,
+            }
+            .data(),
+        }
+    }
+
+    pub fn close_raffle_ix(&self, raffle_id: u64, creator: &Pubkey, treasury: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CloseRaffle {
+                raffle_account: raffle_pda(raffle_id),
+                escrow_account: escrow_pda(raffle_id),
+                program_state: program_state_pda(),
+                creator: *creator,
+                treasury: *treasury,
+                caller: self.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CloseRaffle { raffle_id }.data(),
+        }
+    }
+
+    pub fn update_program_config_ix(&self, caller: &Pubkey, params: UpdateConfigParams) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::UpdateProgramConfig {
+                program_state: program_state_pda(),
+                role_registry: roles_pda(),
+                caller: *caller,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::UpdateProgramConfig { params }.data(),
+        }
+    }
+
+    pub fn apply_config_change_ix(&self) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::ApplyConfigChange {
+                program_state: program_state_pda(),
+                caller: self.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::ApplyConfigChange {}.data(),
+        }
+    }
+
+    pub fn cancel_config_change_ix(&self, fee_manager: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::CancelConfigChange {
+                program_state: program_state_pda(),
+                role_registry: roles_pda(),
+                fee_manager: *fee_manager,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::CancelConfigChange {}.data(),
+        }
+    }
+
+    pub fn accept_authority_ix(&self, new_authority: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: raffle_v4::ID,
+            accounts: raffle_v4::accounts::AcceptAuthority {
+                program_state: program_state_pda(),
+                new_authority: *new_authority,
+            }
+            .to_account_metas(None),
+            data: raffle_v4::instruction::AcceptAuthority {}.data(),
+        }
+    }
+}
+
+fn system_account(lamports: u64) -> Account {
+    Account {
+        lamports,
+        data: vec![],
+        owner: system_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,162 @@
+//! Program configuration: role gating, the config timelock and two-step
+//! authority transfers
+
+mod common;
+
+use common::*;
+use raffle_v4::error::RaffleError;
+use raffle_v4::instructions::UpdateConfigParams;
+use raffle_v4::state::ProgramState;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn no_changes() -> UpdateConfigParams {
+    UpdateConfigParams {
+        new_fee_rate: None,
+        new_treasury: None,
+        new_authority: None,
+        new_pause_flags: None,
+        new_config_delay: None,
+    }
+}
+
+fn fee_change(fee_rate: u16) -> UpdateConfigParams {
+    UpdateConfigParams {
+        new_fee_rate: Some(fee_rate),
+        ..no_changes()
+    }
+}
+
+#[tokio::test]
+async fn test_config_changes_are_role_gated() {
+    let mut test = RaffleTest::start(0).await;
+    let creator = test.clone_keypair(&test.creator);
+    let fee_manager = test.clone_keypair(&test.fee_manager);
+    let pauser = test.clone_keypair(&test.pauser);
+    let authority = test.clone_keypair(&test.authority);
+
+    // Fee, treasury and delay changes need the fee manager role
+    for signer in [&creator, &pauser, &authority] {
+        let queue = test.update_program_config_ix(&signer.pubkey(), fee_change(500));
+        test.expect_error(&[queue], &[signer], RaffleError::MissingRole).await;
+    }
+    let delay = UpdateConfigParams {
+        new_config_delay: Some(0),
+        ..no_changes()
+    };
+    let queue = test.update_program_config_ix(&creator.pubkey(), delay);
+    test.expect_error(&[queue], &[&creator], RaffleError::MissingRole).await;
+
+    // Pause flags need the pauser role and apply at once
+    let pause = UpdateConfigParams {
+        new_pause_flags: Some(ProgramState::PAUSE_DEFAULT),
+        ..no_changes()
+    };
+    let denied = test.update_program_config_ix(&fee_manager.pubkey(), pause.clone());
+    test.expect_error(&[denied], &[&fee_manager], RaffleError::MissingRole).await;
+    let allowed = test.update_program_config_ix(&pauser.pubkey(), pause);
+    test.send(&[allowed], &[&pauser]).await.unwrap();
+    assert_eq!(test.program_state().await.pause_flags, ProgramState::PAUSE_DEFAULT);
+
+    // Only the authority proposes a new authority
+    let propose = UpdateConfigParams {
+        new_authority: Some(fee_manager.pubkey()),
+        ..no_changes()
+    };
+    let denied = test.update_program_config_ix(&fee_manager.pubkey(), propose);
+    test.expect_error(&[denied], &[&fee_manager], RaffleError::UnauthorizedAuthority).await;
+
+    // Queued changes are cancelled by a fee manager only
+    let queue = test.update_program_config_ix(&fee_manager.pubkey(), fee_change(500));
+    test.send(&[queue], &[&fee_manager]).await.unwrap();
+    let denied = test.cancel_config_change_ix(&pauser.pubkey());
+    test.expect_error(&[denied], &[&pauser], RaffleError::MissingRole).await;
+    let cancel = test.cancel_config_change_ix(&fee_manager.pubkey());
+    test.send(&[cancel], &[&fee_manager]).await.unwrap();
+    assert!(test.program_state().await.pending_config.is_none());
+    assert_eq!(test.program_state().await.fee_rate, FEE_RATE);
+}
+
+#[tokio::test]
+async fn test_config_change_waits_for_timelock() {
+    let mut test = RaffleTest::start(0).await;
+    let fee_manager = test.clone_keypair(&test.fee_manager);
+    let treasury = Pubkey::new_unique();
+
+    let invalid = test.update_program_config_ix(&fee_manager.pubkey(), fee_change(1001));
+    test.expect_error(&[invalid], &[&fee_manager], RaffleError::InvalidFeeRate).await;
+
+    let params = UpdateConfigParams {
+        new_fee_rate: Some(500),
+        new_treasury: Some(treasury),
+        ..no_changes()
+    };
+    let queue = test.update_program_config_ix(&fee_manager.pubkey(), params);
+    test.send(&[queue], &[&fee_manager]).await.unwrap();
+
+    let now = test.clock().await.unix_timestamp;
+    let state = test.program_state().await;
+    let change = state.pending_config.unwrap();
+    assert_eq!(change.eta, now + ProgramState::DEFAULT_CONFIG_DELAY);
+    assert_eq!(state.fee_rate, FEE_RATE);
+    assert_eq!(state.treasury, test.treasury);
+
+    // One change at a time
+    let second = test.update_program_config_ix(&fee_manager.pubkey(), fee_change(100));
+    test.expect_error(&[second], &[&fee_manager], RaffleError::ConfigChangeAlreadyQueued).await;
+
+    let apply = [test.apply_config_change_ix()];
+    test.expect_error(&apply, &[], RaffleError::ConfigChangeNotReady).await;
+    test.warp_seconds(ProgramState::DEFAULT_CONFIG_DELAY - 1).await;
+    test.expect_error(&apply, &[], RaffleError::ConfigChangeNotReady).await;
+
+    // Anyone applies once the delay has elapsed
+    test.warp_seconds(1).await;
+    test.send(&apply, &[]).await.unwrap();
+    let state = test.program_state().await;
+    assert_eq!(state.fee_rate, 500);
+    assert_eq!(state.treasury, treasury);
+    assert!(state.pending_config.is_none());
+
+    test.expect_error(&apply, &[], RaffleError::NoPendingConfigChange).await;
+}
+
+#[tokio::test]
+async fn test_authority_transfer_needs_acceptance() {
+    let mut test = RaffleTest::start(0).await;
+    let authority = test.clone_keypair(&test.authority);
+    let successor = Keypair::new();
+
+    let accept = test.accept_authority_ix(&successor.pubkey());
+    test.expect_error(&[accept], &[&successor], RaffleError::NoPendingAuthority).await;
+
+    let propose = UpdateConfigParams {
+        new_authority: Some(successor.pubkey()),
+        ..no_changes()
+    };
+    let instruction = test.update_program_config_ix(&authority.pubkey(), propose);
+    test.send(&[instruction], &[&authority]).await.unwrap();
+
+    // Proposing does not hand over control
+    let state = test.program_state().await;
+    assert_eq!(state.authority, authority.pubkey());
+    assert_eq!(state.pending_authority, Some(successor.pubkey()));
+
+    let impostor = Keypair::new();
+    let accept = test.accept_authority_ix(&impostor.pubkey());
+    test.expect_error(&[accept], &[&impostor], RaffleError::UnauthorizedPendingAuthority).await;
+
+    let accept = test.accept_authority_ix(&successor.pubkey());
+    test.send(&[accept], &[&successor]).await.unwrap();
+    let state = test.program_state().await;
+    assert_eq!(state.authority, successor.pubkey());
+    assert!(state.pending_authority.is_none());
+
+    // The previous authority has no say any more
+    let propose = UpdateConfigParams {
+        new_authority: Some(authority.pubkey()),
+        ..no_changes()
+    };
+    let instruction = test.update_program_config_ix(&authority.pubkey(), propose);
+    test.expect_error(&[instruction], &[&authority], RaffleError::UnauthorizedAuthority).await;
+}
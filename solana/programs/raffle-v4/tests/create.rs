@@ -0,0 +1,40 @@
+//! Raffle creation: the creator funds the prize plus the platform fee
+
+mod common;
+
+use common::*;
+use raffle_v4::error::RaffleError;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn test_creation_funds_escrow_with_prize() {
+    let mut test = RaffleTest::start(0).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+
+    let raffle = test.raffle(1).await;
+    assert_eq!(raffle.prize_amount, PRIZE);
+    assert_eq!(raffle.fee_rate, FEE_RATE);
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    let escrow_rent = rent.minimum_balance(8 + raffle_v4::state::EscrowAccount::LEN);
+    assert_eq!(test.lamports(escrow_pda(1)).await, escrow_rent + PRIZE);
+}
+
+#[tokio::test]
+async fn test_creation_rejects_prize_plus_fee_overflow() {
+    let mut test = RaffleTest::start(0).await;
+    let creator = test.clone_keypair(&test.creator);
+
+    // The fee is taken in u128, so only the funding sum can overflow
+    let mut params = test.raffle_params(1);
+    params.prize_amount = u64::MAX - 1;
+    let create = test.create_raffle_ix(params);
+    test.expect_error(&[create], &[&creator], RaffleError::ArithmeticOverflow).await;
+
+    // A prize the creator cannot cover with its fee is refused up front
+    let balance = test.lamports(creator.pubkey()).await;
+    let mut params = test.raffle_params(1);
+    params.prize_amount = balance;
+    let create = test.create_raffle_ix(params);
+    test.expect_error(&[create], &[&creator], RaffleError::InsufficientFunds).await;
+}
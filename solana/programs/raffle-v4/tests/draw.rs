@@ -0,0 +1,171 @@
+//! ORAO draws: request seeds, fulfillment, winner binding and stalled-draw recovery
+
+mod common;
+
+use common::*;
+use raffle_v4::error::RaffleError;
+use raffle_v4::state::RaffleStatus;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn test_request_seed_is_fixed_at_send_time() {
+    let mut test = RaffleTest::start(1).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 3).await;
+    test.end_raffle(1).await;
+
+    // Built now, landing after the clock has moved on
+    let seed = test.draw_seed(1).await;
+    let instruction = test.request_winner_selection_ix(1, &seed);
+    test.warp_seconds(45).await;
+    test.send(&[instruction], &[]).await.unwrap();
+
+    let raffle = test.raffle(1).await;
+    assert_eq!(
+        raffle.vrf_request,
+        Some(mock_orao::client::randomness_address(&seed))
+    );
+    assert_eq!(raffle.randomness_request.unwrap().seed, seed);
+    assert_eq!(raffle.draw_attempts, 1);
+}
+
+/// Ended raffle with an ORAO request that has outlived the draw timeout
+async fn stalled_draw(test: &mut RaffleTest) -> [u8; 32] {
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 3).await;
+    test.end_raffle(1).await;
+    let seed = test.request_draw(1).await;
+    let timeout = test.raffle(1).await.draw_timeout;
+    test.warp_seconds(timeout).await;
+    seed
+}
+
+#[tokio::test]
+async fn test_stalled_draw_can_be_rerequested() {
+    let mut test = RaffleTest::start(1).await;
+    let previous_seed = stalled_draw(&mut test).await;
+
+    let seed = test.draw_seed(1).await;
+    assert_ne!(seed, previous_seed);
+    let instruction = test.rerequest_winner_selection_ix(1, &previous_seed, &seed);
+    test.send(&[instruction], &[]).await.unwrap();
+
+    let raffle = test.raffle(1).await;
+    assert_eq!(raffle.randomness_request.unwrap().seed, seed);
+    assert_eq!(raffle.draw_attempts, 2);
+}
+
+#[tokio::test]
+async fn test_stalled_draw_requires_recorded_request() {
+    let mut test = RaffleTest::start(1).await;
+    stalled_draw(&mut test).await;
+
+    // Any account other than the raffle's own request is rejected
+    let seed = test.draw_seed(1).await;
+    let instruction = test.rerequest_winner_selection_ix(1, &seed, &seed);
+    test.expect_error(&[instruction], &[], RaffleError::VRFOracleMismatch).await;
+
+    let instruction = test.cancel_stalled_raffle_ix(1, None);
+    test.expect_error(&[instruction], &[], RaffleError::VRFOracleMismatch).await;
+}
+
+#[tokio::test]
+async fn test_fulfilled_draw_is_not_stalled() {
+    let mut test = RaffleTest::start(1).await;
+    let previous_seed = stalled_draw(&mut test).await;
+    test.orao_fulfill(previous_seed, [7u8; 64]).await;
+
+    // Past the timeout, but ORAO answered: only the fulfil path remains
+    let seed = test.draw_seed(1).await;
+    let reroll = test.rerequest_winner_selection_ix(1, &previous_seed, &seed);
+    test.expect_error(&[reroll], &[], RaffleError::RandomnessAlreadyFulfilled).await;
+
+    let vrf_request = mock_orao::client::randomness_address(&previous_seed);
+    let cancel = test.cancel_stalled_raffle_ix(1, Some(vrf_request));
+    test.expect_error(&[cancel], &[], RaffleError::RandomnessAlreadyFulfilled).await;
+
+    let fulfill = test.fulfill_winner_selection_ix(1, &previous_seed);
+    test.send(&[fulfill], &[]).await.unwrap();
+    assert!(test.raffle(1).await.winning_ticket.is_some());
+}
+
+#[tokio::test]
+async fn test_cancelled_stalled_draw_opens_refunds() {
+    let mut test = RaffleTest::start(1).await;
+    let seed = stalled_draw(&mut test).await;
+
+    let creator = test.creator.pubkey();
+    let creator_before = test.lamports(creator).await;
+    let vrf_request = mock_orao::client::randomness_address(&seed);
+    let cancel = test.cancel_stalled_raffle_ix(1, Some(vrf_request));
+    test.send(&[cancel], &[]).await.unwrap();
+    assert_eq!(test.raffle(1).await.status, RaffleStatus::Cancelled);
+    assert_eq!(test.lamports(creator).await, creator_before + PRIZE);
+
+    let holder = test.buyer(0);
+    let before = test.lamports(holder.pubkey()).await;
+    let claim = test.claim_refund_ix(1, &holder.pubkey(), 0);
+    test.send(&[claim], &[&holder]).await.unwrap();
+    assert!(!test.exists(ticket_pda(1, 0)).await);
+    assert!(test.lamports(holder.pubkey()).await >= before + 3 * TICKET_PRICE);
+}
+
+/// Ended raffle with a fulfilled ORAO draw over two purchases: [0, 3) and [3, 5)
+async fn drawn_raffle(test: &mut RaffleTest) -> u32 {
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 3).await;
+    test.buy(1, 1, 2).await;
+    test.end_raffle(1).await;
+    let seed = test.request_draw(1).await;
+
+    // Binding needs the winning ticket number first
+    let early = test.bind_winner_ix(1, 0);
+    test.expect_error(&[early], &[], RaffleError::WinningTicketNotDrawn).await;
+
+    test.orao_fulfill(seed, [7u8; 64]).await;
+    let fulfill = test.fulfill_winner_selection_ix(1, &seed);
+    test.send(&[fulfill], &[]).await.unwrap();
+    test.raffle(1).await.winning_ticket.unwrap()
+}
+
+#[tokio::test]
+async fn test_bind_winner_uses_range_holding_winning_ticket() {
+    let mut test = RaffleTest::start(2).await;
+    let winning_ticket = drawn_raffle(&mut test).await;
+    let start = test.ticket_start(1, winning_ticket).await;
+    let holder = test.ticket(1, start).await.owner;
+
+    // The other purchase does not cover the winning ticket
+    let other = if start == 0 { 3 } else { 0 };
+    let wrong = test.bind_winner_ix(1, other);
+    test.expect_error(&[wrong], &[], RaffleError::InvalidTicketNumber).await;
+
+    let bind = test.bind_winner_ix(1, start);
+    test.send(&[bind], &[]).await.unwrap();
+    let raffle = test.raffle(1).await;
+    assert_eq!(raffle.status, RaffleStatus::Complete);
+    assert_eq!(raffle.winner, Some(holder));
+    assert!(raffle.can_distribute_prize());
+
+    // The winner is bound once
+    let again = test.bind_winner_ix(1, start);
+    test.expect_error(&[again], &[], RaffleError::InvalidRaffleState).await;
+}
+
+#[tokio::test]
+async fn test_bind_winner_rejects_frozen_raffle() {
+    let mut test = RaffleTest::start(2).await;
+    let winning_ticket = drawn_raffle(&mut test).await;
+    let start = test.ticket_start(1, winning_ticket).await;
+
+    test.set_frozen(1, true).await;
+    let bind = test.bind_winner_ix(1, start);
+    test.expect_error(&[bind], &[], RaffleError::RaffleFrozen).await;
+    test.set_frozen(1, false).await;
+
+    let bind = test.bind_winner_ix(1, start);
+    test.send(&[bind], &[]).await.unwrap();
+}
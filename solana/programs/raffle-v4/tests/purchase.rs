@@ -0,0 +1,105 @@
+//! Ticket purchases: range assignment, quantity, capacity and wallet limits
+
+mod common;
+
+use common::*;
+use raffle_v4::error::RaffleError;
+use raffle_v4::instructions::CreateRaffleParams;
+use raffle_v4::state::{TicketAccount, UserTicketsAccount};
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn test_batch_purchase_assigns_ranges() {
+    let mut test = RaffleTest::start(2).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+
+    assert_eq!(test.buy(1, 0, 5).await, 0);
+    assert_eq!(test.buy(1, 1, 3).await, 5);
+
+    let first = test.ticket(1, 0).await;
+    assert_eq!(first.owner, test.buyers[0].pubkey());
+    assert_eq!(first.quantity, 5);
+    assert_eq!(first.amount_paid, 5 * TICKET_PRICE);
+
+    let second = test.ticket(1, 5).await;
+    assert_eq!(second.owner, test.buyers[1].pubkey());
+    assert_eq!(second.end_ticket(), 8);
+
+    let raffle = test.raffle(1).await;
+    assert_eq!(raffle.tickets_sold, 8);
+    assert_eq!(raffle.ticket_revenue, 8 * TICKET_PRICE);
+    assert_eq!(raffle.open_ticket_accounts, 2);
+}
+
+#[tokio::test]
+async fn test_batch_quantity_limits() {
+    let mut test = RaffleTest::start(1).await;
+    let params = CreateRaffleParams {
+        max_tickets: 10,
+        ..test.raffle_params(1)
+    };
+    test.create_raffle(params).await;
+    let buyer = test.buyer(0);
+
+    for quantity in [0, TicketAccount::MAX_TICKETS_PER_PURCHASE + 1] {
+        let instruction = test.purchase_tickets_ix(1, &buyer.pubkey(), 0, quantity);
+        test.expect_error(&[instruction], &[&buyer], RaffleError::InvalidTicketQuantity)
+            .await;
+    }
+
+    test.buy(1, 0, 8).await;
+
+    // The range must fit in the remaining capacity
+    let instruction = test.purchase_tickets_ix(1, &buyer.pubkey(), 8, 3);
+    test.expect_error(&[instruction], &[&buyer], RaffleError::RaffleFull)
+        .await;
+
+    assert_eq!(test.buy(1, 0, 2).await, 8);
+    assert_eq!(test.raffle(1).await.tickets_sold, 10);
+}
+
+#[tokio::test]
+async fn test_purchase_count_capped_only_with_wallet_limit() {
+    let mut test = RaffleTest::start(2).await;
+    let purchases = UserTicketsAccount::MAX_PURCHASES as u32;
+    let unlimited = test.raffle_params(1);
+    test.create_raffle(unlimited).await;
+    let limited = CreateRaffleParams {
+        max_tickets_per_wallet: Some(50),
+        ..test.raffle_params(2)
+    };
+    test.create_raffle(limited).await;
+
+    for _ in 0..purchases {
+        test.buy(1, 0, 1).await;
+        test.buy(2, 1, 1).await;
+    }
+
+    // A wallet limit bounds the purchase list at its initial capacity
+    let buyer = test.buyer(1);
+    let instruction = test.purchase_tickets_ix(2, &buyer.pubkey(), purchases, 1);
+    test.expect_error(&[instruction], &[&buyer], RaffleError::UserPurchaseLimitExceeded)
+        .await;
+
+    // Without one the account grows, funded by the buyer
+    test.buy(1, 0, 1).await;
+    let owner = test.buyers[0].pubkey();
+    let user_tickets = test.user_tickets(1, &owner).await;
+    assert_eq!(user_tickets.tickets_owned, purchases + 1);
+    assert_eq!(user_tickets.ticket_starts.len(), purchases as usize + 1);
+    assert_eq!(user_tickets.ticket_starts.last(), Some(&purchases));
+
+    let address = user_tickets_pda(1, &owner);
+    let account = test
+        .context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let len = 8 + UserTicketsAccount::space_for(purchases as usize + 1);
+    assert_eq!(account.data.len(), len);
+    assert!(account.lamports >= Rent::default().minimum_balance(len));
+}
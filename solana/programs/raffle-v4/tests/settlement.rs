@@ -0,0 +1,178 @@
+//! Settling tickets and raffles: batch refund validation, refunds and ticket
+//! closing returning every account's rent, and closing a settled raffle
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use raffle_v4::error::RaffleError;
+use raffle_v4::state::EscrowSweptEvent;
+use solana_sdk::{signature::Signer, system_instruction};
+
+#[tokio::test]
+async fn test_refunds_close_wallet_tracking_account() {
+    let mut test = RaffleTest::start(2).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 2).await;
+    test.buy(1, 1, 3).await;
+    test.buy(1, 1, 1).await;
+    test.cancel_raffle(1).await;
+
+    let holder = test.buyer(0);
+    let holder_tickets = user_tickets_pda(1, &holder.pubkey());
+    let rent = test.lamports(holder_tickets).await;
+    let before = test.lamports(holder.pubkey()).await;
+    let claim = test.claim_refund_ix(1, &holder.pubkey(), 0);
+    test.send(&[claim], &[&holder]).await.unwrap();
+    assert!(!test.exists(holder_tickets).await);
+    assert!(test.lamports(holder.pubkey()).await >= before + 2 * TICKET_PRICE + rent);
+
+    // The batch keeps the tracking account until the owner's last range closes
+    let owner = test.buyers[1].pubkey();
+    let owner_tickets = user_tickets_pda(1, &owner);
+    let first = test.refund_batch_ix(1, &[(2, owner)]);
+    test.send(&[first], &[]).await.unwrap();
+    assert_eq!(test.user_tickets(1, &owner).await.ticket_starts, vec![5]);
+
+    let rent = test.lamports(owner_tickets).await;
+    let before = test.lamports(owner).await;
+    let last = test.refund_batch_ix(1, &[(5, owner)]);
+    test.send(&[last], &[]).await.unwrap();
+    assert!(!test.exists(owner_tickets).await);
+    assert_eq!(test.lamports(owner).await, before + TICKET_PRICE + rent + ticket_rent(&mut test).await);
+}
+
+#[tokio::test]
+async fn test_close_ticket_closes_wallet_tracking_account() {
+    let mut test = RaffleTest::start(2).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 4).await;
+    test.buy(1, 1, 2).await;
+    test.buy(1, 0, 1).await;
+    test.draw_and_distribute(1, [7u8; 64]).await;
+
+    let owner = test.buyers[0].pubkey();
+    let tracking = user_tickets_pda(1, &owner);
+    let close = test.close_ticket_ix(1, 0, &owner);
+    test.send(&[close], &[]).await.unwrap();
+    assert_eq!(test.user_tickets(1, &owner).await.ticket_starts, vec![6]);
+
+    let rent = test.lamports(tracking).await;
+    let before = test.lamports(owner).await;
+    let close = test.close_ticket_ix(1, 6, &owner);
+    test.send(&[close], &[]).await.unwrap();
+    assert!(!test.exists(tracking).await);
+    assert_eq!(test.lamports(owner).await, before + rent + ticket_rent(&mut test).await);
+}
+
+#[tokio::test]
+async fn test_refund_batch_validates_remaining_accounts() {
+    let mut test = RaffleTest::start(2).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 2).await;
+    test.buy(1, 1, 3).await;
+    test.cancel_raffle(1).await;
+    let first = test.buyers[0].pubkey();
+    let second = test.buyers[1].pubkey();
+
+    // Accounts must come in whole (ticket, owner, user_tickets) triples
+    let empty = test.refund_batch_ix(1, &[]);
+    test.expect_error(&[empty], &[], RaffleError::InvalidRemainingAccounts).await;
+    let mut partial = test.refund_batch_ix(1, &[(0, first), (2, second)]);
+    partial.accounts.pop();
+    test.expect_error(&[partial], &[], RaffleError::InvalidRemainingAccounts).await;
+
+    // Refunds go to the ticket's owner only
+    let misdirected = test.refund_batch_ix(1, &[(0, second)]);
+    test.expect_error(&[misdirected], &[], RaffleError::UnauthorizedTicketOwner).await;
+
+    let refund = test.refund_batch_ix(1, &[(0, first)]);
+    test.send(&[refund], &[]).await.unwrap();
+    assert!(!test.exists(ticket_pda(1, 0)).await);
+
+    // A closed ticket cannot be refunded twice, alone or inside a batch
+    let escrow = test.lamports(escrow_pda(1)).await;
+    let repeat = test.refund_batch_ix(1, &[(2, second), (0, first)]);
+    test.expect_error(&[repeat], &[], ErrorCode::AccountNotInitialized).await;
+    assert_eq!(test.lamports(escrow_pda(1)).await, escrow);
+    assert!(test.exists(ticket_pda(1, 2)).await);
+    assert_eq!(test.raffle(1).await.tickets_refunded, 2);
+}
+
+#[tokio::test]
+async fn test_close_raffle_requires_settlement() {
+    let mut test = RaffleTest::start(2).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 4).await;
+    test.buy(1, 1, 2).await;
+    let creator = test.creator.pubkey();
+    let treasury = test.raffle(1).await.treasury;
+
+    let close = test.close_raffle_ix(1, &creator, &treasury);
+    test.expect_error(&[close], &[], RaffleError::RaffleNotSettled).await;
+
+    // Distributed, but the ticket accounts still point at the raffle
+    test.draw_and_distribute(1, [7u8; 64]).await;
+    let close = test.close_raffle_ix(1, &creator, &treasury);
+    test.expect_error(&[close], &[], RaffleError::TicketAccountsOpen).await;
+}
+
+#[tokio::test]
+async fn test_close_raffle_sweeps_surplus_and_returns_rent() {
+    let mut test = RaffleTest::start(2).await;
+    let params = test.raffle_params(1);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 4).await;
+    test.buy(1, 1, 2).await;
+    test.draw_and_distribute(1, [7u8; 64]).await;
+    for (ticket_number, owner) in [(0, test.buyers[0].pubkey()), (4, test.buyers[1].pubkey())] {
+        let close = test.close_ticket_ix(1, ticket_number, &owner);
+        test.send(&[close], &[]).await.unwrap();
+    }
+
+    // The prize and net ticket revenue went to the winner, leaving only rent
+    let escrow_rent = escrow_rent(&mut test).await;
+    assert_eq!(test.lamports(escrow_pda(1)).await, escrow_rent);
+
+    // A stray transfer into the escrow is swept to the treasury
+    let stray = 5_000;
+    let payer = test.payer.pubkey();
+    let transfer = system_instruction::transfer(&payer, &escrow_pda(1), stray);
+    test.send(&[transfer], &[]).await.unwrap();
+
+    let creator = test.creator.pubkey();
+    let treasury = test.raffle(1).await.treasury;
+    let raffle_rent = test.lamports(raffle_pda(1)).await;
+    let creator_before = test.lamports(creator).await;
+    let treasury_before = test.lamports(treasury).await;
+
+    let close = test.close_raffle_ix(1, &creator, &treasury);
+    let logs = test.send_with_logs(&[close], &[]).await.unwrap();
+
+    let swept = events::<EscrowSweptEvent>(&logs);
+    assert_eq!(swept.len(), 1);
+    assert_eq!(swept[0].raffle_id, 1);
+    assert_eq!(swept[0].treasury, treasury);
+    assert_eq!(swept[0].amount, stray);
+
+    assert_eq!(test.lamports(treasury).await, treasury_before + stray);
+    assert_eq!(test.lamports(creator).await, creator_before + escrow_rent + raffle_rent);
+    assert!(!test.exists(raffle_pda(1)).await);
+    assert!(!test.exists(escrow_pda(1)).await);
+}
+
+/// Rent held by a ticket account
+async fn ticket_rent(test: &mut RaffleTest) -> u64 {
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    rent.minimum_balance(8 + raffle_v4::state::TicketAccount::LEN)
+}
+
+/// Rent held by a raffle's escrow account
+async fn escrow_rent(test: &mut RaffleTest) -> u64 {
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    rent.minimum_balance(8 + raffle_v4::state::EscrowAccount::LEN)
+}
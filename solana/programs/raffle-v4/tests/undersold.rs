@@ -0,0 +1,65 @@
+//! Minimum-ticket threshold: undersold raffles cancel into refund mode
+
+mod common;
+
+use common::*;
+use raffle_v4::error::RaffleError;
+use raffle_v4::state::RaffleStatus;
+use solana_sdk::signature::Signer;
+
+async fn undersold_raffle(test: &mut RaffleTest) {
+    let mut params = test.raffle_params(1);
+    params.min_tickets = Some(5);
+    test.create_raffle(params).await;
+    test.buy(1, 0, 2).await;
+}
+
+#[tokio::test]
+async fn test_undersold_raffle_refunds_prize_and_tickets() {
+    let mut test = RaffleTest::start(1).await;
+    undersold_raffle(&mut test).await;
+
+    let early = test.cancel_undersold_raffle_ix(1);
+    test.expect_error(&[early], &[], RaffleError::RaffleNotEnded).await;
+
+    // Below the threshold the raffle cannot be drawn
+    test.end_raffle(1).await;
+    let seed = test.draw_seed(1).await;
+    let draw = test.request_winner_selection_ix(1, &seed);
+    test.expect_error(&[draw], &[], RaffleError::MinimumTicketsNotMet).await;
+
+    // Anyone can cancel; the creator gets the full prize back
+    let creator = test.creator.pubkey();
+    let creator_before = test.lamports(creator).await;
+    let escrow_before = test.lamports(escrow_pda(1)).await;
+    let cancel = test.cancel_undersold_raffle_ix(1);
+    test.send(&[cancel], &[]).await.unwrap();
+    assert_eq!(test.lamports(creator).await, creator_before + PRIZE);
+    assert_eq!(test.lamports(escrow_pda(1)).await, escrow_before - PRIZE);
+    assert_eq!(test.raffle(1).await.status, RaffleStatus::Cancelled);
+
+    // Buyers then reclaim what they paid
+    let buyer = test.buyer(0);
+    let before = test.lamports(buyer.pubkey()).await;
+    let refund = test.claim_refund_ix(1, &buyer.pubkey(), 0);
+    test.send(&[refund], &[&buyer]).await.unwrap();
+    assert!(test.lamports(buyer.pubkey()).await >= before + 2 * TICKET_PRICE);
+    assert_eq!(test.lamports(escrow_pda(1)).await, escrow_before - PRIZE - 2 * TICKET_PRICE);
+
+    let again = test.cancel_undersold_raffle_ix(1);
+    test.expect_error(&[again], &[], RaffleError::InvalidRaffleState).await;
+}
+
+#[tokio::test]
+async fn test_raffle_at_threshold_cannot_be_cancelled() {
+    let mut test = RaffleTest::start(2).await;
+    undersold_raffle(&mut test).await;
+    test.buy(1, 1, 3).await;
+    test.end_raffle(1).await;
+
+    let cancel = test.cancel_undersold_raffle_ix(1);
+    test.expect_error(&[cancel], &[], RaffleError::MinimumTicketsReached).await;
+
+    test.request_draw(1).await;
+    assert_eq!(test.raffle(1).await.status, RaffleStatus::Drawing);
+}